@@ -0,0 +1,389 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Serialize;
+use tauri::State;
+
+use crate::{
+    background::{TaskContext, TaskKind},
+    cleanup, git_output, mtime, AppState, Project,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleProject {
+    project_id: String,
+    name: String,
+    path: String,
+    last_modified: Option<String>,
+    last_commit: Option<String>,
+    days_inactive: i64,
+    /// 可通过 clean_project_artifacts 回收的构建产物大小
+    reclaimable_bytes: u64,
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|v| v.with_timezone(&Utc))
+}
+
+pub fn last_commit_iso(project_path: &Path) -> Option<String> {
+    git_output(project_path, &["log", "-1", "--format=%cI"]).filter(|v| !v.is_empty())
+}
+
+/// 取最后修改时间与最后提交时间中较新的一个
+pub fn last_activity(
+    last_modified: Option<&str>,
+    last_commit: Option<&str>,
+) -> Option<DateTime<Utc>> {
+    [last_modified, last_commit]
+        .into_iter()
+        .flatten()
+        .filter_map(parse_time)
+        .max()
+}
+
+/// 计算可回收空间需要遍历产物目录，在后台任务队列中执行；取消时返回已统计的部分
+#[tauri::command(async)]
+pub fn find_stale_projects(
+    threshold_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Vec<StaleProject> {
+    let threshold_days = threshold_days.unwrap_or_else(|| {
+        state
+            .settings
+            .lock()
+            .expect("settings lock poisoned")
+            .stale_threshold_days
+    });
+    let projects = state.store.projects().clone();
+
+    let now = Utc::now();
+    let total = projects.len();
+    let task = |task: &TaskContext| -> Result<Vec<StaleProject>, String> {
+        Ok(projects
+            .into_iter()
+            .enumerate()
+            .take_while(|_| !task.is_cancelled())
+            .filter_map(|(done, project)| {
+                task.progress(done, total);
+                let root = Path::new(&project.path);
+                if !root.is_dir() {
+                    return None;
+                }
+                let last_modified = mtime::project_mtime_iso(&project.path);
+                let last_commit = last_commit_iso(root);
+                let activity = last_activity(last_modified.as_deref(), last_commit.as_deref())?;
+                let days_inactive = (now - activity).num_days();
+                if days_inactive < threshold_days as i64 {
+                    return None;
+                }
+                let reclaimable_bytes = cleanup::find_artifacts(root, &[])
+                    .iter()
+                    .map(|a| a.bytes)
+                    .sum();
+                Some(StaleProject {
+                    project_id: project.id,
+                    name: project.name,
+                    path: project.path,
+                    last_modified,
+                    last_commit,
+                    days_inactive,
+                    reclaimable_bytes,
+                })
+            })
+            .collect())
+    };
+    let mut stale = state
+        .background
+        .run(TaskKind::DiskUsage, "长期未动项目", task)
+        .unwrap_or_default();
+
+    stale.sort_by_key(|p| std::cmp::Reverse(p.days_inactive));
+    stale
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagCount {
+    tag: String,
+    count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageTotal {
+    language: String,
+    lines: u64,
+    files: u32,
+    /// 包含该语言的项目数
+    projects: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCount {
+    project_id: String,
+    name: String,
+    opens: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthCount {
+    /// `YYYY-MM`
+    month: String,
+    count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardStats {
+    total_projects: usize,
+    favorites: usize,
+    by_type: BTreeMap<String, usize>,
+    by_tag: Vec<TagCount>,
+    lines_by_language: Vec<LanguageTotal>,
+    most_opened: Vec<OpenCount>,
+    added_per_month: Vec<MonthCount>,
+    stale_projects: usize,
+    stale_threshold_days: u32,
+}
+
+const MOST_OPENED_LIMIT: usize = 10;
+
+/// 用户标签与系统标签一起计数，按数量从多到少排列
+fn count_tags(projects: &[Project]) -> Vec<TagCount> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for project in projects {
+        for tag in project.tags.iter().chain(&project.metadata.system_tags) {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount {
+            tag: tag.to_string(),
+            count,
+        })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    tags
+}
+
+/// 只统计已做过语言分析的项目
+fn sum_languages(projects: &[Project]) -> Vec<LanguageTotal> {
+    let mut totals: HashMap<&str, LanguageTotal> = HashMap::new();
+    for stats in projects
+        .iter()
+        .filter_map(|p| p.metadata.language_stats.as_ref())
+    {
+        for entry in &stats.languages {
+            let total = totals
+                .entry(entry.language.as_str())
+                .or_insert_with(|| LanguageTotal {
+                    language: entry.language.clone(),
+                    lines: 0,
+                    files: 0,
+                    projects: 0,
+                });
+            total.lines += entry.lines;
+            total.files += entry.files;
+            total.projects += 1;
+        }
+    }
+    let mut languages: Vec<LanguageTotal> = totals.into_values().collect();
+    languages.sort_by(|a, b| {
+        b.lines
+            .cmp(&a.lines)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    languages
+}
+
+fn added_per_month(projects: &[Project]) -> Vec<MonthCount> {
+    let mut months: BTreeMap<String, usize> = BTreeMap::new();
+    for project in projects {
+        if let Some(created) = parse_time(&project.created_at) {
+            *months
+                .entry(created.format("%Y-%m").to_string())
+                .or_default() += 1;
+        }
+    }
+    months
+        .into_iter()
+        .map(|(month, count)| MonthCount { month, count })
+        .collect()
+}
+
+fn is_stale(project: &Project, threshold_days: u32, now: DateTime<Utc>) -> bool {
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return false;
+    }
+    let last_modified = mtime::project_mtime_iso(&project.path);
+    let last_commit = last_commit_iso(root);
+    last_activity(last_modified.as_deref(), last_commit.as_deref())
+        .map(|activity| (now - activity).num_days() >= threshold_days as i64)
+        .unwrap_or(false)
+}
+
+/// 仪表盘所需的全部统计一次返回，打开次数来自启动记录
+#[tauri::command(async)]
+pub fn get_dashboard_stats(state: State<'_, AppState>) -> DashboardStats {
+    let threshold_days = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .stale_threshold_days;
+    let projects = state.store.projects().clone();
+
+    let mut opens: HashMap<String, usize> = HashMap::new();
+    for record in &state.history.lock().expect("history lock poisoned").records {
+        *opens.entry(record.project_id.clone()).or_default() += 1;
+    }
+    let mut most_opened: Vec<OpenCount> = projects
+        .iter()
+        .filter_map(|p| {
+            opens.get(&p.id).map(|count| OpenCount {
+                project_id: p.id.clone(),
+                name: p.name.clone(),
+                opens: *count,
+            })
+        })
+        .collect();
+    most_opened.sort_by(|a, b| b.opens.cmp(&a.opens).then_with(|| a.name.cmp(&b.name)));
+    most_opened.truncate(MOST_OPENED_LIMIT);
+
+    let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+    for project in &projects {
+        *by_type
+            .entry(format!("{:?}", project.project_type))
+            .or_default() += 1;
+    }
+
+    let now = Utc::now();
+    DashboardStats {
+        total_projects: projects.len(),
+        favorites: projects.iter().filter(|p| p.favorite).count(),
+        by_type,
+        by_tag: count_tags(&projects),
+        lines_by_language: sum_languages(&projects),
+        most_opened,
+        added_per_month: added_per_month(&projects),
+        stale_projects: projects
+            .iter()
+            .filter(|p| is_stale(p, threshold_days, now))
+            .count(),
+        stale_threshold_days: threshold_days,
+    }
+}
+
+const DEFAULT_HEATMAP_DAYS: u32 = 365;
+const MAX_HEATMAP_DAYS: u32 = 3660;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayCount {
+    /// 本地日期 `YYYY-MM-DD`
+    date: String,
+    count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectHeatmap {
+    project_id: String,
+    name: String,
+    total: usize,
+    /// 只包含有打开记录的日期
+    days: Vec<DayCount>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityHeatmap {
+    start: String,
+    end: String,
+    /// 范围内每一天都有一项，没有打开记录的日期为 0
+    days: Vec<DayCount>,
+    max_count: usize,
+    projects: Vec<ProjectHeatmap>,
+}
+
+fn day_counts(counts: &BTreeMap<NaiveDate, usize>) -> Vec<DayCount> {
+    counts
+        .iter()
+        .map(|(date, count)| DayCount {
+            date: date.to_string(),
+            count: *count,
+        })
+        .collect()
+}
+
+/// 按本地日期统计最近 range 天（含今天）的打开次数，整体与按项目各一份
+#[tauri::command]
+pub fn get_activity_heatmap(range: Option<u32>, state: State<'_, AppState>) -> ActivityHeatmap {
+    let range = range
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_HEATMAP_DAYS)
+        .min(MAX_HEATMAP_DAYS);
+    let end = Local::now().date_naive();
+    let start = end - chrono::Days::new(range as u64 - 1);
+
+    let names: HashMap<String, String> = state
+        .store
+        .projects()
+        .iter()
+        .map(|p| (p.id.clone(), p.name.clone()))
+        .collect();
+
+    let mut overall: BTreeMap<NaiveDate, usize> = start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .map(|day| (day, 0))
+        .collect();
+    let mut per_project: HashMap<String, BTreeMap<NaiveDate, usize>> = HashMap::new();
+    for record in &state.history.lock().expect("history lock poisoned").records {
+        // 已删除项目的记录不计入
+        if !names.contains_key(&record.project_id) {
+            continue;
+        }
+        let Some(at) = parse_time(&record.launched_at) else {
+            continue;
+        };
+        let day = at.with_timezone(&Local).date_naive();
+        if day < start || day > end {
+            continue;
+        }
+        *overall.entry(day).or_default() += 1;
+        *per_project
+            .entry(record.project_id.clone())
+            .or_default()
+            .entry(day)
+            .or_default() += 1;
+    }
+
+    let mut projects: Vec<ProjectHeatmap> = per_project
+        .into_iter()
+        .map(|(project_id, counts)| ProjectHeatmap {
+            name: names.get(&project_id).cloned().unwrap_or_default(),
+            total: counts.values().sum(),
+            days: day_counts(&counts),
+            project_id,
+        })
+        .collect();
+    projects.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+
+    ActivityHeatmap {
+        start: start.to_string(),
+        end: end.to_string(),
+        max_count: overall.values().copied().max().unwrap_or(0),
+        days: day_counts(&overall),
+        projects,
+    }
+}
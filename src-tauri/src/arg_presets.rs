@@ -0,0 +1,265 @@
+use std::{collections::BTreeMap, path::Path, sync::RwLock};
+
+use serde::Serialize;
+use tauri::State;
+use uuid::Uuid;
+
+use crate::{
+    persist,
+    settings::{self, ArgPreset},
+    AppState, IdeConfig, Project,
+};
+
+/// 用户保存的参数预设，按 IDE id 分组；修改设置时同步更新，启动时无需再锁设置
+static USER_PRESETS: RwLock<BTreeMap<String, Vec<ArgPreset>>> = RwLock::new(BTreeMap::new());
+
+pub fn configure(presets: &BTreeMap<String, Vec<ArgPreset>>) {
+    *USER_PRESETS.write().expect("presets lock poisoned") = presets.clone();
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdeArgPreset {
+    id: String,
+    label: String,
+    args_template: String,
+    /// 内置预设不能修改或删除
+    builtin: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    VsCode,
+    JetBrains,
+    Neovim,
+}
+
+/// 按内置 id 识别，手动添加的 IDE 按可执行文件名识别
+fn family(ide: &IdeConfig) -> Option<Family> {
+    match ide.id.as_str() {
+        "vscode" | "cursor" | "positron" => return Some(Family::VsCode),
+        "webstorm" | "intellij" | "pycharm" | "clion" | "goland" | "rider" | "android-studio"
+        | "rustrover" | "phpstorm" | "rubymine" | "datagrip" => return Some(Family::JetBrains),
+        "neovim" => return Some(Family::Neovim),
+        _ => {}
+    }
+    let stem = Path::new(&ide.executable)
+        .file_stem()?
+        .to_string_lossy()
+        .to_ascii_lowercase();
+    match stem.trim_end_matches("64") {
+        "code" | "code-insiders" | "codium" | "cursor" | "windsurf" | "positron" => {
+            Some(Family::VsCode)
+        }
+        "idea" | "webstorm" | "pycharm" | "clion" | "goland" | "rider" | "studio" | "rustrover"
+        | "phpstorm" | "rubymine" | "datagrip" => Some(Family::JetBrains),
+        "nvim" => Some(Family::Neovim),
+        _ => None,
+    }
+}
+
+fn builtin_presets(ide: &IdeConfig) -> Vec<IdeArgPreset> {
+    let presets: &[(&str, &str, &str)] = match family(ide) {
+        Some(Family::VsCode) => &[
+            (
+                "vscode-new-window",
+                "新窗口打开",
+                "--new-window {projectPath}",
+            ),
+            (
+                "vscode-reuse-window",
+                "在当前窗口打开",
+                "--reuse-window {projectPath}",
+            ),
+            ("vscode-add", "添加到当前工作区", "--add {projectPath}"),
+        ],
+        Some(Family::JetBrains) => &[(
+            "jetbrains-nosplash",
+            "不显示启动画面",
+            "nosplash {projectPath}",
+        )],
+        Some(Family::Neovim) => &[
+            (
+                "nvim-telescope",
+                "打开文件搜索",
+                "{projectPath} +'Telescope find_files'",
+            ),
+            (
+                "nvim-cd",
+                "切换到项目目录",
+                "-c 'cd {projectPath}' {projectPath}",
+            ),
+        ],
+        None => &[],
+    };
+    presets
+        .iter()
+        .map(|(id, label, args_template)| IdeArgPreset {
+            id: id.to_string(),
+            label: label.to_string(),
+            args_template: args_template.to_string(),
+            builtin: true,
+        })
+        .collect()
+}
+
+fn presets_for(ide: &IdeConfig) -> Vec<IdeArgPreset> {
+    let mut presets = builtin_presets(ide);
+    if let Some(user) = USER_PRESETS
+        .read()
+        .expect("presets lock poisoned")
+        .get(&ide.id)
+    {
+        presets.extend(user.iter().map(|preset| IdeArgPreset {
+            id: preset.id.clone(),
+            label: preset.label.clone(),
+            args_template: preset.args_template.clone(),
+            builtin: false,
+        }));
+    }
+    presets
+}
+
+/// 项目为该 IDE 选择了参数预设时，返回使用预设参数的 IDE 配置；预设已删除时使用 IDE 自身的参数
+pub fn effective_ide(project: &Project, ide: &IdeConfig) -> IdeConfig {
+    let mut ide = ide.clone();
+    if let Some(preset) = project
+        .metadata
+        .ide_arg_presets
+        .get(&ide.id)
+        .and_then(|preset_id| presets_for(&ide).into_iter().find(|p| p.id == *preset_id))
+    {
+        ide.args_template = preset.args_template;
+    }
+    ide
+}
+
+fn find_ide(state: &AppState, ide_id: &str) -> Result<IdeConfig, String> {
+    state
+        .store
+        .ides()
+        .iter()
+        .find(|i| i.id == ide_id)
+        .cloned()
+        .ok_or_else(|| "IDE 不存在".to_string())
+}
+
+/// 内置预设在前，用户预设在后
+#[tauri::command]
+pub fn get_ide_arg_presets(
+    ide_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<IdeArgPreset>, String> {
+    Ok(presets_for(&find_ide(&state, &ide_id)?))
+}
+
+fn save_presets(
+    state: &AppState,
+    apply: impl FnOnce(&mut BTreeMap<String, Vec<ArgPreset>>) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut settings = state.settings.lock().expect("settings lock poisoned");
+    let mut next = settings.clone();
+    apply(&mut next.ide_arg_presets)?;
+    next.ide_arg_presets
+        .retain(|_, presets| !presets.is_empty());
+    settings::save_settings(&settings::settings_path(&state.file_path), &next)?;
+    configure(&next.ide_arg_presets);
+    *settings = next;
+    Ok(())
+}
+
+/// 新建或修改（传 preset_id）用户预设
+#[tauri::command]
+pub fn save_ide_arg_preset(
+    ide_id: String,
+    preset_id: Option<String>,
+    label: String,
+    args_template: String,
+    state: State<'_, AppState>,
+) -> Result<IdeArgPreset, String> {
+    let ide = find_ide(&state, &ide_id)?;
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("预设名称不能为空".to_string());
+    }
+    let preset = ArgPreset {
+        id: preset_id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+        label,
+        args_template: args_template.trim().to_string(),
+    };
+    if builtin_presets(&ide).iter().any(|p| p.id == preset.id) {
+        return Err("内置预设不能修改".to_string());
+    }
+    save_presets(&state, |presets| {
+        let list = presets.entry(ide_id).or_default();
+        match list.iter_mut().find(|p| p.id == preset.id) {
+            Some(existing) => *existing = preset.clone(),
+            None => list.push(preset.clone()),
+        }
+        Ok(())
+    })?;
+    Ok(IdeArgPreset {
+        id: preset.id,
+        label: preset.label,
+        args_template: preset.args_template,
+        builtin: false,
+    })
+}
+
+/// 删除用户预设，并清除项目中对它的选择
+#[tauri::command]
+pub fn delete_ide_arg_preset(
+    ide_id: String,
+    preset_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    save_presets(&state, |presets| {
+        let list = presets.entry(ide_id.clone()).or_default();
+        let before = list.len();
+        list.retain(|p| p.id != preset_id);
+        if list.len() == before {
+            return Err("预设不存在".to_string());
+        }
+        Ok(())
+    })?;
+    let mut projects = state.store.projects_mut();
+    for project in projects.iter_mut() {
+        if project.metadata.ide_arg_presets.get(&ide_id) == Some(&preset_id) {
+            project.metadata.ide_arg_presets.remove(&ide_id);
+        }
+    }
+    persist::mark_dirty();
+    Ok(())
+}
+
+/// 为项目选择用某个 IDE 打开时使用的参数预设，传 None 恢复使用 IDE 自身的参数
+#[tauri::command]
+pub fn set_project_arg_preset(
+    project_id: String,
+    ide_id: String,
+    preset_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let ide = find_ide(&state, &ide_id)?;
+    if let Some(preset_id) = &preset_id {
+        if !presets_for(&ide).iter().any(|p| p.id == *preset_id) {
+            return Err("预设不存在".to_string());
+        }
+    }
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    match preset_id {
+        Some(preset_id) => {
+            project.metadata.ide_arg_presets.insert(ide_id, preset_id);
+        }
+        None => {
+            project.metadata.ide_arg_presets.remove(&ide_id);
+        }
+    }
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -0,0 +1,81 @@
+use std::env;
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::{portable, settings, AppState};
+
+/// 开机自启时附加的命令行参数，用于区分用户手动启动
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartStatus {
+    enabled: bool,
+    start_minimized: bool,
+}
+
+/// 开机自启时附加的全部参数；以参数进入便携模式时自启也保持便携模式
+pub fn launch_args() -> Vec<&'static str> {
+    let mut args = vec![AUTOSTART_ARG];
+    if portable::launched_with_arg() {
+        args.push(portable::PORTABLE_ARG);
+    }
+    args
+}
+
+pub fn launched_by_autostart() -> bool {
+    env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+fn autostart_status(app: &AppHandle, state: &AppState) -> Result<AutostartStatus, String> {
+    let enabled = app
+        .autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("读取开机自启状态失败: {e}"))?;
+    let start_minimized = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .start_minimized;
+    Ok(AutostartStatus {
+        enabled,
+        start_minimized,
+    })
+}
+
+#[tauri::command]
+pub fn get_autostart(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<AutostartStatus, String> {
+    autostart_status(&app, &state)
+}
+
+/// start_minimized 为真时，开机自启后只驻留托盘而不显示主窗口
+#[tauri::command]
+pub fn set_autostart(
+    enabled: bool,
+    start_minimized: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<AutostartStatus, String> {
+    let autolaunch = app.autolaunch();
+    if enabled {
+        autolaunch
+            .enable()
+            .map_err(|e| format!("开启开机自启失败: {e}"))?;
+    } else {
+        autolaunch
+            .disable()
+            .map_err(|e| format!("关闭开机自启失败: {e}"))?;
+    }
+
+    if let Some(start_minimized) = start_minimized {
+        let mut settings = state.settings.lock().expect("settings lock poisoned");
+        settings.start_minimized = start_minimized;
+        settings::save_settings(&settings::settings_path(&state.file_path), &settings)?;
+    }
+    autostart_status(&app, &state)
+}
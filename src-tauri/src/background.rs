@@ -0,0 +1,251 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Condvar, Mutex, OnceLock,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+use crate::{now_iso, AppState};
+
+/// 同时运行的后台任务上限，其余任务排队等待
+const MAX_WORKERS: usize = 3;
+/// 保留的已结束任务数
+const MAX_FINISHED: usize = 50;
+
+pub const CANCELLED: &str = "任务已取消";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum TaskKind {
+    Scan,
+    LanguageStats,
+    DiskUsage,
+    IconDownload,
+    Indexing,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum TaskStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundTask {
+    id: String,
+    kind: TaskKind,
+    label: String,
+    status: TaskStatus,
+    /// 已完成与总数，任务未报告进度时为 None
+    progress: Option<(usize, usize)>,
+    error: Option<String>,
+    created_at: String,
+    finished_at: Option<String>,
+}
+
+/// 任务结束（包括 panic）时归还并发名额
+struct Slot<'a>(&'a TaskQueue);
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+struct TaskEntry {
+    info: BackgroundTask,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// 所有重量级后台工作（扫描、语言统计、磁盘占用、图标下载、索引）都经由这里执行，
+/// 限制并发数量并记录状态供 `list_background_tasks` 查询
+#[derive(Default)]
+pub struct TaskQueue {
+    tasks: Mutex<Vec<TaskEntry>>,
+    running: Mutex<usize>,
+    slot_freed: Condvar,
+    app: OnceLock<AppHandle>,
+}
+
+/// 传给任务闭包，用于报告进度与检查是否已被取消
+pub struct TaskContext<'a> {
+    queue: &'a TaskQueue,
+    id: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskContext<'_> {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn progress(&self, done: usize, total: usize) {
+        self.queue
+            .update(&self.id, |task| task.progress = Some((done, total)));
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 发送任务自身的事件，如扫描中找到的项目
+    pub fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Some(app) = self.queue.app.get() {
+            let _ = app.emit(event, payload);
+        }
+    }
+}
+
+impl TaskQueue {
+    /// 设置后任务状态变化时发送 `background://updated` 事件
+    pub fn attach(&self, app: &AppHandle) {
+        let _ = self.app.set(app.clone());
+    }
+
+    fn update(&self, id: &str, apply: impl FnOnce(&mut BackgroundTask)) {
+        let mut tasks = self.tasks.lock().expect("task queue lock poisoned");
+        let Some(entry) = tasks.iter_mut().find(|t| t.info.id == id) else {
+            return;
+        };
+        apply(&mut entry.info);
+        let info = entry.info.clone();
+        drop(tasks);
+        if let Some(app) = self.app.get() {
+            let _ = app.emit("background://updated", info);
+        }
+    }
+
+    fn acquire(&self, cancelled: &AtomicBool) -> bool {
+        let mut running = self.running.lock().expect("task queue lock poisoned");
+        while *running >= MAX_WORKERS {
+            if cancelled.load(Ordering::SeqCst) {
+                return false;
+            }
+            running = self
+                .slot_freed
+                .wait(running)
+                .expect("task queue lock poisoned");
+        }
+        *running += 1;
+        true
+    }
+
+    fn release(&self) {
+        *self.running.lock().expect("task queue lock poisoned") -= 1;
+        self.slot_freed.notify_all();
+    }
+
+    /// 在当前线程中执行任务，并发已满时先排队。任务内部不要再嵌套调用 run，
+    /// 否则可能在所有名额被占用时互相等待
+    pub fn run<T>(
+        &self,
+        kind: TaskKind,
+        label: impl Into<String>,
+        job: impl FnOnce(&TaskContext) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let id = Uuid::new_v4().to_string();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        {
+            let mut tasks = self.tasks.lock().expect("task queue lock poisoned");
+            tasks.push(TaskEntry {
+                info: BackgroundTask {
+                    id: id.clone(),
+                    kind,
+                    label: label.into(),
+                    status: TaskStatus::Queued,
+                    progress: None,
+                    error: None,
+                    created_at: now_iso(),
+                    finished_at: None,
+                },
+                cancelled: cancelled.clone(),
+            });
+        }
+
+        let result = if self.acquire(&cancelled) {
+            self.update(&id, |task| task.status = TaskStatus::Running);
+            let ctx = TaskContext {
+                queue: self,
+                id: id.clone(),
+                cancelled: cancelled.clone(),
+            };
+            let _slot = Slot(self);
+            job(&ctx)
+        } else {
+            Err(CANCELLED.to_string())
+        };
+
+        let was_cancelled = cancelled.load(Ordering::SeqCst);
+        self.update(&id, |task| {
+            task.status = match &result {
+                _ if was_cancelled => TaskStatus::Cancelled,
+                Ok(_) => TaskStatus::Completed,
+                Err(_) => TaskStatus::Failed,
+            };
+            task.error = result.as_ref().err().cloned();
+            task.finished_at = Some(now_iso());
+        });
+        self.prune();
+        result
+    }
+
+    fn prune(&self) {
+        let mut tasks = self.tasks.lock().expect("task queue lock poisoned");
+        let finished = tasks
+            .iter()
+            .filter(|t| t.info.finished_at.is_some())
+            .count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED);
+        tasks.retain(|t| {
+            if excess > 0 && t.info.finished_at.is_some() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    fn list(&self) -> Vec<BackgroundTask> {
+        self.tasks
+            .lock()
+            .expect("task queue lock poisoned")
+            .iter()
+            .map(|t| t.info.clone())
+            .collect()
+    }
+
+    fn cancel(&self, id: &str) -> Result<(), String> {
+        let tasks = self.tasks.lock().expect("task queue lock poisoned");
+        let entry = tasks
+            .iter()
+            .find(|t| t.info.id == id)
+            .ok_or_else(|| "任务不存在".to_string())?;
+        if entry.info.finished_at.is_some() {
+            return Err("任务已结束".to_string());
+        }
+        entry.cancelled.store(true, Ordering::SeqCst);
+        drop(tasks);
+        // 持有名额锁再唤醒，避免排队任务在检查标记与等待之间错过通知
+        let _running = self.running.lock().expect("task queue lock poisoned");
+        self.slot_freed.notify_all();
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn list_background_tasks(state: State<'_, AppState>) -> Vec<BackgroundTask> {
+    state.background.list()
+}
+
+/// 排队中的任务立即取消；运行中的任务在下一个检查点停止
+#[tauri::command]
+pub fn cancel_background_task(task_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state.background.cancel(&task_id)
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{git, AppState};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Branch {
+    /// 本地分支为 `main`，远程分支为 `origin/main`
+    name: String,
+    current: bool,
+    remote: bool,
+    upstream: Option<String>,
+    last_commit: Option<String>,
+}
+
+fn repo_path(state: &AppState, project_id: &str) -> Result<PathBuf, String> {
+    let projects = state.store.projects();
+    let project = projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    let path = PathBuf::from(&project.path);
+    if !path.join(".git").exists() {
+        return Err("项目不是 git 仓库".to_string());
+    }
+    Ok(path)
+}
+
+fn branches(dir: &Path) -> Result<Vec<Branch>, String> {
+    let output = git(
+        dir,
+        &[
+            "for-each-ref",
+            "--format=%(refname)%00%(refname:short)%00%(HEAD)%00%(upstream:short)%00%(committerdate:iso-strict)",
+            "refs/heads",
+            "refs/remotes",
+        ],
+    )?;
+    let mut branches: Vec<Branch> = output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let refname = fields.next()?;
+            // 远程的 HEAD 只是指向默认分支的符号引用
+            if refname.ends_with("/HEAD") {
+                return None;
+            }
+            let non_empty = |v: Option<&str>| v.filter(|v| !v.is_empty()).map(str::to_string);
+            Some(Branch {
+                name: fields.next()?.to_string(),
+                current: fields.next() == Some("*"),
+                remote: refname.starts_with("refs/remotes/"),
+                upstream: non_empty(fields.next()),
+                last_commit: non_empty(fields.next()),
+            })
+        })
+        .collect();
+    // 当前分支在前，其次本地分支，同类按最近提交排序
+    branches.sort_by(|a, b| {
+        b.current
+            .cmp(&a.current)
+            .then(a.remote.cmp(&b.remote))
+            .then_with(|| b.last_commit.cmp(&a.last_commit))
+    });
+    Ok(branches)
+}
+
+/// 本地与远程分支，远程分支不会自动拉取，需要时先在仓库中 fetch
+#[tauri::command(async)]
+pub fn list_branches(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Branch>, String> {
+    branches(&repo_path(&state, &project_id)?)
+}
+
+/// 切换到本地分支；传入远程分支（如 `origin/feature`）且本地没有同名分支时创建跟踪分支。
+/// 工作区有未提交的修改时拒绝切换，未跟踪的文件不影响
+#[tauri::command(async)]
+pub fn checkout_branch(
+    project_id: String,
+    branch: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Branch>, String> {
+    let dir = repo_path(&state, &project_id)?;
+    let branch = branch.trim();
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err("分支名称无效".to_string());
+    }
+    if !git(&dir, &["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        return Err("工作区有未提交的修改，请先提交或暂存后再切换分支".to_string());
+    }
+    let exists =
+        |refname: String| git(&dir, &["rev-parse", "--verify", "--quiet", &refname]).is_ok();
+    if exists(format!("refs/heads/{branch}")) {
+        git(&dir, &["switch", branch])?;
+    } else if exists(format!("refs/remotes/{branch}")) {
+        let local = branch
+            .split_once('/')
+            .map(|(_, name)| name)
+            .unwrap_or(branch);
+        if exists(format!("refs/heads/{local}")) {
+            git(&dir, &["switch", local])?;
+        } else {
+            git(&dir, &["switch", "--track", branch])?;
+        }
+    } else {
+        return Err(format!("分支不存在: {branch}"));
+    }
+    tracing::info!(project_id, branch, "已切换分支");
+    branches(&dir)
+}
@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{git_output, network, now_iso, relocate, secrets, AppState};
+
+const USER_AGENT: &str = concat!("dev-boom/", env!("CARGO_PKG_VERSION"), " ci");
+/// 卡片列表会频繁请求，短时间内复用上次的结果
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+static CACHE: Mutex<Option<HashMap<String, (Instant, CiStatus)>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CiProvider {
+    GitHub,
+    GitLab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CiState {
+    Success,
+    Failed,
+    Running,
+    Pending,
+    Cancelled,
+    /// 跳过、需要手动触发等无法归为成功或失败的结果
+    Unknown,
+    /// 默认分支上还没有运行记录
+    NoRuns,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiStatus {
+    provider: CiProvider,
+    branch: String,
+    state: CiState,
+    /// 工作流或流水线的网页地址
+    url: Option<String>,
+    commit: Option<String>,
+    updated_at: Option<String>,
+    fetched_at: String,
+}
+
+struct Remote {
+    provider: CiProvider,
+    host: String,
+    /// `owner/repo`，GitLab 可能包含子组
+    path: String,
+}
+
+/// 支持 `https://host/a/b(.git)`、`ssh://git@host:22/a/b` 与 `git@host:a/b`；
+/// 主机必须与 github.com、gitlab.com 或设置中的自建 GitLab 主机完全一致
+fn parse_remote(url: &str, gitlab_hosts: &[String]) -> Option<Remote> {
+    let url = url.trim();
+    let (host, path) = match url::Url::parse(url) {
+        Ok(parsed) if parsed.host_str().is_some() => {
+            (parsed.host_str()?.to_string(), parsed.path().to_string())
+        }
+        _ => {
+            let (host, path) = url.split_once(':')?;
+            let host = host.rsplit_once('@').map(|(_, h)| h).unwrap_or(host);
+            (host.to_string(), path.to_string())
+        }
+    };
+    let host = host.to_lowercase();
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).to_string();
+    if path.split('/').filter(|s| !s.is_empty()).count() < 2 {
+        return None;
+    }
+    let provider = if host == "github.com" {
+        CiProvider::GitHub
+    } else if host == "gitlab.com" || gitlab_hosts.contains(&host) {
+        CiProvider::GitLab
+    } else {
+        return None;
+    };
+    Some(Remote {
+        provider,
+        host,
+        path,
+    })
+}
+
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// 优先使用本地记录的 `origin/HEAD`，避免多一次请求
+fn local_default_branch(root: &Path) -> Option<String> {
+    git_output(
+        root,
+        &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"],
+    )?
+    .strip_prefix("origin/")
+    .map(str::to_string)
+    .filter(|b| !b.is_empty())
+}
+
+fn get_json<T: serde::de::DeserializeOwned>(
+    request: reqwest::blocking::RequestBuilder,
+) -> Result<T, String> {
+    let response = request
+        .send()
+        .map_err(|e| format!("获取 CI 状态失败: {e}"))?;
+    match response.status().as_u16() {
+        401 | 403 | 404 => {
+            return Err("仓库不存在或无权访问，私有仓库请先保存访问令牌".to_string());
+        }
+        _ => {}
+    }
+    let body = response
+        .error_for_status()
+        .and_then(|r| r.bytes())
+        .map_err(|e| format!("获取 CI 状态失败: {e}"))?;
+    serde_json::from_slice(&body).map_err(|e| format!("解析 CI 状态失败: {e}"))
+}
+
+#[derive(Deserialize)]
+struct GithubRepo {
+    default_branch: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRuns {
+    workflow_runs: Vec<GithubRun>,
+}
+
+#[derive(Deserialize)]
+struct GithubRun {
+    status: Option<String>,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+    head_sha: Option<String>,
+    updated_at: Option<String>,
+}
+
+fn github_state(run: &GithubRun) -> CiState {
+    match run.status.as_deref() {
+        Some("completed") | None => match run.conclusion.as_deref() {
+            Some("success") => CiState::Success,
+            Some("failure" | "timed_out" | "startup_failure") => CiState::Failed,
+            Some("cancelled") => CiState::Cancelled,
+            _ => CiState::Unknown,
+        },
+        Some("in_progress") => CiState::Running,
+        _ => CiState::Pending,
+    }
+}
+
+fn fetch_github(remote: &Remote, branch: Option<String>) -> Result<CiStatus, String> {
+    let client = network::client(Duration::from_secs(15), USER_AGENT)?;
+    let token = secrets::get(secrets::GITHUB_TOKEN);
+    let get = |url: String| {
+        let request = client
+            .get(url)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json");
+        match &token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    };
+    let api = format!("https://api.github.com/repos/{}", remote.path);
+    let branch = match branch {
+        Some(branch) => branch,
+        None => get_json::<GithubRepo>(get(api.clone()))?.default_branch,
+    };
+    let runs: GithubRuns = get_json(get(format!(
+        "{api}/actions/runs?branch={}&per_page=1",
+        encode(&branch)
+    )))?;
+    let run = runs.workflow_runs.into_iter().next();
+    Ok(CiStatus {
+        provider: CiProvider::GitHub,
+        branch,
+        state: run.as_ref().map(github_state).unwrap_or(CiState::NoRuns),
+        url: run.as_ref().and_then(|r| r.html_url.clone()),
+        commit: run.as_ref().and_then(|r| r.head_sha.clone()),
+        updated_at: run.and_then(|r| r.updated_at),
+        fetched_at: now_iso(),
+    })
+}
+
+#[derive(Deserialize)]
+struct GitlabProject {
+    default_branch: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitlabPipeline {
+    status: String,
+    web_url: Option<String>,
+    sha: Option<String>,
+    updated_at: Option<String>,
+}
+
+fn gitlab_state(status: &str) -> CiState {
+    match status {
+        "success" => CiState::Success,
+        "failed" => CiState::Failed,
+        "running" => CiState::Running,
+        "canceled" | "canceling" => CiState::Cancelled,
+        "created" | "waiting_for_resource" | "preparing" | "pending" | "scheduled" => {
+            CiState::Pending
+        }
+        _ => CiState::Unknown,
+    }
+}
+
+fn fetch_gitlab(remote: &Remote, branch: Option<String>) -> Result<CiStatus, String> {
+    let client = network::client(Duration::from_secs(15), USER_AGENT)?;
+    let token = secrets::get(secrets::GITLAB_TOKEN);
+    let get = |url: String| {
+        let request = client.get(url);
+        match &token {
+            Some(token) => request.header("PRIVATE-TOKEN", token),
+            None => request,
+        }
+    };
+    let api = format!(
+        "https://{}/api/v4/projects/{}",
+        remote.host,
+        encode(&remote.path)
+    );
+    let branch = match branch {
+        Some(branch) => branch,
+        None => get_json::<GitlabProject>(get(api.clone()))?
+            .default_branch
+            .ok_or_else(|| "仓库没有默认分支".to_string())?,
+    };
+    let pipelines: Vec<GitlabPipeline> = get_json(get(format!(
+        "{api}/pipelines?ref={}&per_page=1",
+        encode(&branch)
+    )))?;
+    let pipeline = pipelines.into_iter().next();
+    Ok(CiStatus {
+        provider: CiProvider::GitLab,
+        branch,
+        state: pipeline
+            .as_ref()
+            .map(|p| gitlab_state(&p.status))
+            .unwrap_or(CiState::NoRuns),
+        url: pipeline.as_ref().and_then(|p| p.web_url.clone()),
+        commit: pipeline.as_ref().and_then(|p| p.sha.clone()),
+        updated_at: pipeline.and_then(|p| p.updated_at),
+        fetched_at: now_iso(),
+    })
+}
+
+fn fetch(
+    root: &Path,
+    remote_url: &str,
+    gitlab_hosts: &[String],
+) -> Result<Option<CiStatus>, String> {
+    let Some(remote) = parse_remote(remote_url, gitlab_hosts) else {
+        return Ok(None);
+    };
+    let branch = local_default_branch(root);
+    match remote.provider {
+        CiProvider::GitHub => fetch_github(&remote, branch),
+        CiProvider::GitLab => fetch_gitlab(&remote, branch),
+    }
+    .map(Some)
+}
+
+/// 远程仓库默认分支上最近一次 GitHub Actions 工作流或 GitLab 流水线的状态；
+/// 不是 GitHub/GitLab 仓库时返回 None，refresh 为 true 时忽略缓存
+#[tauri::command]
+pub async fn get_ci_status(
+    project_id: String,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Option<CiStatus>, String> {
+    let (root, remote_url) = {
+        let projects = state.store.projects();
+        let project = projects
+            .iter()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| "项目不存在".to_string())?;
+        (
+            PathBuf::from(&project.path),
+            project.metadata.git_url.clone(),
+        )
+    };
+    let gitlab_hosts = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .gitlab_hosts
+        .clone();
+    if !refresh.unwrap_or(false) {
+        if let Some((at, cached)) = CACHE
+            .lock()
+            .expect("ci cache lock poisoned")
+            .get_or_insert_with(HashMap::new)
+            .get(&project_id)
+        {
+            if at.elapsed() < CACHE_TTL {
+                return Ok(Some(cached.clone()));
+            }
+        }
+    }
+    let status = tauri::async_runtime::spawn_blocking(move || {
+        let Some(remote_url) = remote_url.or_else(|| relocate::remote_url(&root)) else {
+            return Ok(None);
+        };
+        fetch(&root, &remote_url, &gitlab_hosts)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    let mut cache = CACHE.lock().expect("ci cache lock poisoned");
+    let cache = cache.get_or_insert_with(HashMap::new);
+    match &status {
+        Some(status) => {
+            cache.insert(project_id, (Instant::now(), status.clone()));
+        }
+        None => {
+            cache.remove(&project_id);
+        }
+    }
+    Ok(status)
+}
@@ -0,0 +1,302 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{background::TaskKind, find_project, git_output, AppState};
+
+/// 可清理的构建产物目录；markers 为同级目录中必须存在的清单文件，
+/// `*.ext` 表示任意该扩展名的文件，空表示无条件匹配
+struct ArtifactRule {
+    name: &'static str,
+    markers: &'static [&'static str],
+}
+
+const ARTIFACT_RULES: &[ArtifactRule] = &[
+    ArtifactRule {
+        name: "target",
+        markers: &["Cargo.toml", "pom.xml"],
+    },
+    ArtifactRule {
+        name: "node_modules",
+        markers: &["package.json"],
+    },
+    ArtifactRule {
+        name: "__pycache__",
+        markers: &[],
+    },
+    ArtifactRule {
+        name: ".pytest_cache",
+        markers: &[],
+    },
+    ArtifactRule {
+        name: "dist",
+        markers: &["package.json", "pyproject.toml", "setup.py"],
+    },
+    ArtifactRule {
+        name: "build",
+        markers: &[
+            "build.gradle",
+            "build.gradle.kts",
+            "CMakeLists.txt",
+            "pyproject.toml",
+            "setup.py",
+        ],
+    },
+    ArtifactRule {
+        name: ".next",
+        markers: &["package.json"],
+    },
+    ArtifactRule {
+        name: ".nuxt",
+        markers: &["package.json"],
+    },
+    ArtifactRule {
+        name: ".turbo",
+        markers: &["package.json"],
+    },
+    ArtifactRule {
+        name: "coverage",
+        markers: &["package.json"],
+    },
+    ArtifactRule {
+        name: ".gradle",
+        markers: &["build.gradle", "build.gradle.kts", "settings.gradle"],
+    },
+    ArtifactRule {
+        name: "bin",
+        markers: &["*.csproj", "*.fsproj"],
+    },
+    ArtifactRule {
+        name: "obj",
+        markers: &["*.csproj", "*.fsproj"],
+    },
+];
+
+const MAX_ARTIFACT_SEARCH_DEPTH: u32 = 6;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    /// 相对项目根目录的路径
+    path: String,
+    kind: &'static str,
+    pub bytes: u64,
+    #[serde(skip)]
+    absolute: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanedArtifact {
+    #[serde(flatten)]
+    artifact: Artifact,
+    deleted: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanReport {
+    dry_run: bool,
+    items: Vec<CleanedArtifact>,
+    bytes_freed: u64,
+}
+
+fn marker_present(dir: &Path, marker: &str) -> bool {
+    match marker.strip_prefix("*.") {
+        Some(ext) => fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(|entry| {
+                entry
+                    .path()
+                    .extension()
+                    .and_then(|v| v.to_str())
+                    .map(|v| v.eq_ignore_ascii_case(ext))
+                    .unwrap_or(false)
+            }),
+        None => dir.join(marker).exists(),
+    }
+}
+
+fn rule_matches(rule: &ArtifactRule, parent: &Path) -> bool {
+    rule.markers.is_empty() || rule.markers.iter().any(|m| marker_present(parent, m))
+}
+
+/// 统计目录大小，不跟随符号链接
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| dir_size(&entry.path()))
+        .sum()
+}
+
+fn find_artifacts_rec(
+    root: &Path,
+    dir: &Path,
+    kinds: &[&str],
+    depth: u32,
+    out: &mut Vec<Artifact>,
+) {
+    if depth > MAX_ARTIFACT_SEARCH_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // 符号链接目录既不进入也不删除
+        let is_real_dir = fs::symlink_metadata(&path)
+            .map(|m| m.is_dir())
+            .unwrap_or(false);
+        if !is_real_dir {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == ".git" {
+            continue;
+        }
+
+        let rule = ARTIFACT_RULES
+            .iter()
+            .find(|r| r.name == name && kinds.contains(&r.name));
+        if let Some(rule) = rule {
+            if rule_matches(rule, dir) {
+                out.push(Artifact {
+                    path: path
+                        .strip_prefix(root)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .to_string(),
+                    kind: rule.name,
+                    bytes: dir_size(&path),
+                    absolute: path,
+                });
+                continue;
+            }
+        }
+        // 不进入其它产物目录（如 node_modules 里的 dist）
+        if ARTIFACT_RULES
+            .iter()
+            .any(|r| r.name == name && rule_matches(r, dir))
+        {
+            continue;
+        }
+        find_artifacts_rec(root, &path, kinds, depth + 1, out);
+    }
+}
+
+/// 查找项目中的构建产物目录；kinds 为空表示全部类型
+pub fn find_artifacts(root: &Path, kinds: &[String]) -> Vec<Artifact> {
+    let kinds: Vec<&str> = if kinds.is_empty() {
+        ARTIFACT_RULES.iter().map(|r| r.name).collect()
+    } else {
+        kinds.iter().map(String::as_str).collect()
+    };
+    let mut out = vec![];
+    find_artifacts_rec(root, root, &kinds, 0, &mut out);
+    out
+}
+
+/// 目录中有纳入 git 版本管理的文件，说明是提交到仓库的源码（如 electron-builder 的 build 资源）而不是产物
+fn has_tracked_files(dir: &Path) -> bool {
+    git_output(dir, &["ls-files"]).is_some_and(|files| !files.is_empty())
+}
+
+fn delete_artifact(root: &Path, artifact: &Artifact) -> Result<(), String> {
+    // 删除前再次确认目标位于项目目录内且不是项目根目录本身
+    let canonical = artifact
+        .absolute
+        .canonicalize()
+        .map_err(|e| format!("无法解析路径: {e}"))?;
+    if canonical == root || !canonical.starts_with(root) {
+        return Err("路径不在项目目录内，已跳过".to_string());
+    }
+    fs::remove_dir_all(&canonical).map_err(|e| format!("删除失败: {e}"))
+}
+
+#[tauri::command(async)]
+pub fn clean_project_artifacts(
+    project_id: String,
+    kinds: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<CleanReport, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path)
+        .canonicalize()
+        .map_err(|_| "项目路径不存在或不是目录".to_string())?;
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+
+    let kinds = kinds.unwrap_or_default();
+    if let Some(unknown) = kinds
+        .iter()
+        .find(|k| !ARTIFACT_RULES.iter().any(|r| r.name == k.as_str()))
+    {
+        return Err(format!("不支持的产物类型: {unknown}"));
+    }
+
+    let dry_run = dry_run.unwrap_or(false);
+    let mut bytes_freed = 0;
+    let artifacts = state
+        .background
+        .run(TaskKind::DiskUsage, project.name.clone(), |_| {
+            Ok(find_artifacts(&root, &kinds))
+        })?;
+    let items = artifacts
+        .into_iter()
+        .map(|artifact| {
+            if has_tracked_files(&artifact.absolute) {
+                return CleanedArtifact {
+                    artifact,
+                    deleted: false,
+                    error: Some("目录中有 git 跟踪的文件，已跳过".to_string()),
+                };
+            }
+            if dry_run {
+                return CleanedArtifact {
+                    artifact,
+                    deleted: false,
+                    error: None,
+                };
+            }
+            match delete_artifact(&root, &artifact) {
+                Ok(()) => {
+                    bytes_freed += artifact.bytes;
+                    CleanedArtifact {
+                        artifact,
+                        deleted: true,
+                        error: None,
+                    }
+                }
+                Err(err) => CleanedArtifact {
+                    artifact,
+                    deleted: false,
+                    error: Some(err),
+                },
+            }
+        })
+        .collect();
+
+    Ok(CleanReport {
+        dry_run,
+        items,
+        bytes_freed,
+    })
+}
@@ -0,0 +1,46 @@
+use tauri::{AppHandle, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::{find_project, relocate, to_wsl_path, AppState};
+
+/// 复制项目路径，wsl 为 true 时复制为 WSL 中的形式（`/mnt/c/...`），返回复制的内容
+#[tauri::command(async)]
+pub fn copy_project_path(
+    project_id: String,
+    wsl: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let project = find_project(&state, &project_id)?;
+    let text = if wsl.unwrap_or(false) {
+        to_wsl_path(&project.path)
+    } else {
+        project.path
+    };
+    write_text(&app, &text)?;
+    Ok(text)
+}
+
+/// 复制远程仓库地址，项目中没有记录时读取 `origin`
+#[tauri::command(async)]
+pub fn copy_git_url(
+    project_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let project = find_project(&state, &project_id)?;
+    let url = project
+        .metadata
+        .git_url
+        .clone()
+        .or_else(|| relocate::remote_url(std::path::Path::new(&project.path)))
+        .ok_or_else(|| "项目没有远程仓库地址".to_string())?;
+    write_text(&app, &url)?;
+    Ok(url)
+}
+
+pub fn write_text(app: &AppHandle, text: &str) -> Result<(), String> {
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("写入剪贴板失败: {e}"))
+}
@@ -0,0 +1,118 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, process, AppState};
+
+const COMPOSE_FILE_NAMES: [&str; 4] = [
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeService {
+    name: String,
+    service: String,
+    state: String,
+    status: String,
+}
+
+/// 按 docker compose 的默认查找顺序定位项目根目录下的 compose 文件
+pub fn find_compose_file(project_path: &Path) -> Option<PathBuf> {
+    COMPOSE_FILE_NAMES
+        .iter()
+        .map(|name| project_path.join(name))
+        .find(|p| p.is_file())
+}
+
+fn compose_command(project_path: &str) -> Result<Command, String> {
+    let root = Path::new(project_path);
+    let compose_file =
+        find_compose_file(root).ok_or_else(|| "项目中没有 compose 文件".to_string())?;
+    let mut command = Command::new("docker");
+    command
+        .current_dir(root)
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file);
+    Ok(command)
+}
+
+#[tauri::command]
+pub fn compose_up(
+    project_id: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let project = find_project(&state, &project_id)?;
+    let mut command = compose_command(&project.path)?;
+    command.args(["up", "-d"]);
+    process::spawn_streaming(&app, command, &project.id, "compose")
+}
+
+#[tauri::command]
+pub fn compose_down(
+    project_id: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let project = find_project(&state, &project_id)?;
+    let mut command = compose_command(&project.path)?;
+    command.arg("down");
+    process::spawn_streaming(&app, command, &project.id, "compose")
+}
+
+fn parse_compose_ps(stdout: &str) -> Vec<ComposeService> {
+    // 新版 docker compose 输出 JSON Lines，旧版输出一个 JSON 数组
+    let values: Vec<serde_json::Value> = match serde_json::from_str(stdout.trim()) {
+        Ok(serde_json::Value::Array(items)) => items,
+        _ => stdout
+            .lines()
+            .filter_map(|line| serde_json::from_str(line.trim()).ok())
+            .collect(),
+    };
+
+    let field = |value: &serde_json::Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    values
+        .iter()
+        .map(|value| ComposeService {
+            name: field(value, "Name"),
+            service: field(value, "Service"),
+            state: field(value, "State"),
+            status: field(value, "Status"),
+        })
+        .collect()
+}
+
+#[tauri::command(async)]
+pub fn compose_status(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ComposeService>, String> {
+    let project = find_project(&state, &project_id)?;
+    let mut command = compose_command(&project.path)?;
+    command.args(["ps", "--all", "--format", "json"]);
+    process::hide_console_window(&mut command);
+    let output = command
+        .output()
+        .map_err(|e| format!("执行 docker compose 失败: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("docker compose ps 失败: {}", stderr.trim()));
+    }
+    Ok(parse_compose_ps(&String::from_utf8_lossy(&output.stdout)))
+}
@@ -0,0 +1,227 @@
+use std::{fs, path::Path};
+
+use ignore::{overrides::OverrideBuilder, WalkBuilder};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{find_project, should_skip_dir, AppState};
+
+const DEFAULT_MAX_RESULTS: usize = 200;
+const MAX_RESULTS_LIMIT: usize = 2000;
+const MAX_CONTEXT_LINES: usize = 5;
+/// 超过该大小的文件不搜索，多为数据或生成文件
+const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024;
+/// 单行返回的最大字符数，压缩后的脚本可能整个文件只有一行
+const MAX_LINE_CHARS: usize = 500;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    /// 为 false 时按字面文本搜索
+    regex: bool,
+    case_sensitive: bool,
+    whole_word: bool,
+    max_results: Option<usize>,
+    /// 匹配行前后各返回的行数
+    context_lines: Option<usize>,
+    /// 只搜索匹配的文件，如 `*.rs`，`!` 开头表示排除
+    glob: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentMatch {
+    /// 相对于项目根目录，使用 `/` 分隔，可直接传给 launch_project_at
+    relative_path: String,
+    /// 从 1 开始
+    line: usize,
+    /// 第一个匹配的列，从 1 开始（按字符计）
+    column: usize,
+    text: String,
+    /// text 中匹配部分的 `[开始, 结束)` 字符下标
+    ranges: Vec<[usize; 2]>,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSearchResult {
+    matches: Vec<ContentMatch>,
+    files_searched: usize,
+    /// 达到结果数上限，还有未返回的匹配
+    truncated: bool,
+}
+
+fn build_regex(pattern: &str, options: &SearchOptions) -> Result<Regex, String> {
+    let mut pattern = if options.regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    if options.whole_word {
+        pattern = format!(r"\b(?:{pattern})\b");
+    }
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| format!("正则表达式无效: {e}"))
+}
+
+fn truncate_line(line: &str) -> String {
+    line.chars().take(MAX_LINE_CHARS).collect()
+}
+
+fn char_index(line: &str, byte: usize) -> usize {
+    line[..byte].chars().count()
+}
+
+/// 文件开头含有 NUL 字节的视为二进制文件
+fn is_binary(content: &[u8]) -> bool {
+    content.iter().take(8192).any(|b| *b == 0)
+}
+
+fn search_file(
+    regex: &Regex,
+    path: &Path,
+    relative_path: &str,
+    context: usize,
+    remaining: usize,
+    out: &mut Vec<ContentMatch>,
+) -> bool {
+    let Ok(content) = fs::read(path) else {
+        return false;
+    };
+    if is_binary(&content) {
+        return false;
+    }
+    let content = String::from_utf8_lossy(&content);
+    let lines: Vec<&str> = content.lines().collect();
+    let mut found = 0;
+    for (idx, line) in lines.iter().enumerate() {
+        let ranges: Vec<[usize; 2]> = regex
+            .find_iter(line)
+            .filter(|m| !m.is_empty())
+            .map(|m| [char_index(line, m.start()), char_index(line, m.end())])
+            .filter(|range| range[0] < MAX_LINE_CHARS)
+            .map(|[start, end]| [start, end.min(MAX_LINE_CHARS)])
+            .collect();
+        let Some(first) = ranges.first() else {
+            continue;
+        };
+        if found == remaining {
+            return true;
+        }
+        found += 1;
+        out.push(ContentMatch {
+            relative_path: relative_path.to_string(),
+            line: idx + 1,
+            column: first[0] + 1,
+            text: truncate_line(line),
+            ranges,
+            before: lines[idx.saturating_sub(context)..idx]
+                .iter()
+                .map(|l| truncate_line(l))
+                .collect(),
+            after: lines[idx + 1..(idx + 1 + context).min(lines.len())]
+                .iter()
+                .map(|l| truncate_line(l))
+                .collect(),
+        });
+    }
+    false
+}
+
+/// 在项目文件内容中搜索，遵循 .gitignore 并跳过隐藏文件、依赖目录与二进制文件
+#[tauri::command(async)]
+pub fn search_in_project(
+    project_id: String,
+    pattern: String,
+    options: Option<SearchOptions>,
+    state: State<'_, AppState>,
+) -> Result<ContentSearchResult, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    if pattern.is_empty() {
+        return Err("搜索内容不能为空".to_string());
+    }
+    let options = options.unwrap_or_default();
+    let regex = build_regex(&pattern, &options)?;
+    let max_results = options
+        .max_results
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_RESULTS)
+        .min(MAX_RESULTS_LIMIT);
+    let context = options.context_lines.unwrap_or(0).min(MAX_CONTEXT_LINES);
+
+    let mut walker = WalkBuilder::new(root);
+    // 不是 git 仓库时也遵循 .gitignore
+    walker
+        .require_git(false)
+        .filter_entry(|entry| !should_skip_dir(entry.path()));
+    if let Some(glob) = options
+        .glob
+        .as_deref()
+        .map(str::trim)
+        .filter(|g| !g.is_empty())
+    {
+        let mut overrides = OverrideBuilder::new(root);
+        for glob in glob.split([',', ' ']).filter(|g| !g.is_empty()) {
+            overrides
+                .add(glob)
+                .map_err(|e| format!("文件匹配模式无效: {e}"))?;
+        }
+        walker.overrides(
+            overrides
+                .build()
+                .map_err(|e| format!("文件匹配模式无效: {e}"))?,
+        );
+    }
+
+    let mut matches = vec![];
+    let mut files_searched = 0;
+    for entry in walker.build().flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        if fs::metadata(path)
+            .map(|m| m.len() > MAX_FILE_SIZE)
+            .unwrap_or(true)
+        {
+            continue;
+        }
+        let relative_path = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        files_searched += 1;
+        let remaining = max_results - matches.len();
+        if search_file(
+            &regex,
+            path,
+            &relative_path,
+            context,
+            remaining,
+            &mut matches,
+        ) || matches.len() >= max_results
+        {
+            // 恰好取满时不能确定是否还有匹配，按有处理
+            return Ok(ContentSearchResult {
+                matches,
+                files_searched,
+                truncated: true,
+            });
+        }
+    }
+    Ok(ContentSearchResult {
+        matches,
+        files_searched,
+        truncated: false,
+    })
+}
@@ -0,0 +1,308 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{normalize_windows_path_for_ui, persist, portable, store_lock, AppState};
+
+/// 指定数据目录的环境变量，优先于设置中选择的目录
+pub const DATA_DIR_ENV: &str = "DEV_BOOM_DATA_DIR";
+/// 记录自定义数据目录的文件，始终位于系统的应用数据目录中
+const POINTER_FILE: &str = "data-location.json";
+/// 迁移时先复制到目标目录下的该目录，全部复制完成后再移入
+const STAGING_DIR: &str = ".migrating";
+/// 移入时目标目录中被替换的条目先移到这里，失败时移回
+const BACKUP_DIR: &str = ".migrating-backup";
+/// 日志文件在运行期间一直打开，不迁移
+const SKIP_ENTRIES: &[&str] = &[
+    "logs",
+    POINTER_FILE,
+    STAGING_DIR,
+    BACKUP_DIR,
+    store_lock::LOCK_FILE,
+];
+const STORE_FILE: &str = "store.json";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum DataDirSource {
+    Default,
+    Portable,
+    Environment,
+    Custom,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataLocation {
+    source: DataDirSource,
+    data_dir: String,
+    default_dir: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Pointer {
+    data_dir: String,
+}
+
+fn ui_path(path: &Path) -> String {
+    normalize_windows_path_for_ui(&path.to_string_lossy())
+}
+
+fn env_dir() -> Option<PathBuf> {
+    env::var_os(DATA_DIR_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+fn read_pointer(default_dir: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(default_dir.join(POINTER_FILE)).ok()?;
+    let pointer: Pointer = serde_json::from_str(&content).ok()?;
+    Some(PathBuf::from(pointer.data_dir)).filter(|p| p.is_absolute())
+}
+
+/// 依次取便携模式目录、环境变量、设置中选择的目录，都没有时使用系统的应用数据目录
+pub fn resolve(default_dir: &Path) -> (PathBuf, DataDirSource) {
+    if let Some(dir) = portable::data_dir() {
+        return (dir, DataDirSource::Portable);
+    }
+    if let Some(dir) = env_dir() {
+        return (dir, DataDirSource::Environment);
+    }
+    if let Some(dir) = read_pointer(default_dir) {
+        return (dir, DataDirSource::Custom);
+    }
+    (default_dir.to_path_buf(), DataDirSource::Default)
+}
+
+/// 先写临时文件再重命名，避免写到一半时退出留下损坏的文件
+fn write_pointer(default_dir: &Path, data_dir: Option<&Path>) -> Result<(), String> {
+    let path = default_dir.join(POINTER_FILE);
+    let Some(data_dir) = data_dir else {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("无法更新数据目录设置: {e}"))
+            }
+            _ => Ok(()),
+        };
+    };
+    let content = serde_json::to_string_pretty(&Pointer {
+        data_dir: data_dir.to_string_lossy().to_string(),
+    })
+    .map_err(|e| e.to_string())?;
+    fs::create_dir_all(default_dir).map_err(|e| format!("无法创建应用数据目录: {e}"))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content).map_err(|e| format!("无法保存数据目录设置: {e}"))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("无法保存数据目录设置: {e}"))
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("创建目录失败 {}: {e}", to.display()))?;
+    let entries = fs::read_dir(from).map_err(|e| format!("读取数据目录失败: {e}"))?;
+    for entry in entries.flatten() {
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        if source.is_dir() {
+            copy_dir(&source, &target)?;
+        } else {
+            fs::copy(&source, &target)
+                .map_err(|e| format!("复制文件失败 {}: {e}", source.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// 撤销已移入的条目：删除移入的新数据，把被替换的原有条目移回
+fn roll_back(target: &Path, backup: &Path, moved: &[(String, bool)]) {
+    for (name, replaced) in moved.iter().rev() {
+        let destination = target.join(name);
+        let _ = remove_entry(&destination);
+        if *replaced {
+            if let Err(e) = fs::rename(backup.join(name), &destination) {
+                tracing::error!(entry = %name, error = %e, "无法恢复目标目录中被替换的条目");
+            }
+        }
+    }
+}
+
+/// 把当前数据目录复制到 target：先完整复制到 target 下的临时目录，再逐项重命名移入，
+/// store.json 最后移入。目标目录中原有的同名条目先移到备份目录，中途失败时全部移回，
+/// 目标目录中不会出现不完整的数据
+fn migrate(current: &Path, target: &Path) -> Result<(), String> {
+    let staging = target.join(STAGING_DIR);
+    let backup = target.join(BACKUP_DIR);
+    let _ = fs::remove_dir_all(&staging);
+    let mut names = vec![];
+    let entries = fs::read_dir(current).map_err(|e| format!("读取数据目录失败: {e}"))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if SKIP_ENTRIES.contains(&name.as_str()) {
+            continue;
+        }
+        let source = entry.path();
+        let copied = if source.is_dir() {
+            copy_dir(&source, &staging.join(&name))
+        } else {
+            fs::create_dir_all(&staging)
+                .and_then(|_| fs::copy(&source, staging.join(&name)).map(|_| ()))
+                .map_err(|e| format!("复制文件失败 {}: {e}", source.display()))
+        };
+        if let Err(err) = copied {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(err);
+        }
+        names.push(name);
+    }
+    names.sort_by_key(|name| name == STORE_FILE);
+    let _ = fs::remove_dir_all(&backup);
+    let mut moved: Vec<(String, bool)> = vec![];
+    for name in names {
+        let destination = target.join(&name);
+        let replaced = destination.symlink_metadata().is_ok();
+        let result = if replaced {
+            fs::create_dir_all(&backup)
+                .and_then(|_| fs::rename(&destination, backup.join(&name)))
+                .map_err(|e| format!("无法替换 {name}: {e}"))
+        } else {
+            Ok(())
+        }
+        .and_then(|_| {
+            moved.push((name.clone(), replaced));
+            fs::rename(staging.join(&name), &destination)
+                .map_err(|e| format!("无法移动 {name}: {e}"))
+        });
+        if let Err(err) = result {
+            roll_back(target, &backup, &moved);
+            let _ = fs::remove_dir_all(&staging);
+            let _ = fs::remove_dir_all(&backup);
+            return Err(err);
+        }
+    }
+    let _ = fs::remove_dir_all(&staging);
+    let _ = fs::remove_dir_all(&backup);
+    Ok(())
+}
+
+/// 规范化路径，路径尚不存在时规范化最近的已存在上级目录后再拼接其余部分
+fn canonical(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = vec![];
+    loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            return rest.iter().rev().fold(resolved, |acc, part| acc.join(part));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+fn default_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取应用数据目录: {e}"))
+}
+
+#[tauri::command]
+pub fn get_data_location(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DataLocation, String> {
+    let default_dir = default_dir(&app)?;
+    let (_, source) = resolve(&default_dir);
+    let data_dir = state.file_path.parent().map(ui_path).unwrap_or_default();
+    Ok(DataLocation {
+        source,
+        data_dir,
+        default_dir: ui_path(&default_dir),
+    })
+}
+
+/// 切换数据目录，path 为空时恢复为系统的应用数据目录。当前数据复制到新目录后重启应用，
+/// 原目录中的数据保留作为备份。目标目录已有数据时（如从另一台机器同步的文件夹），
+/// use_existing 为 true 则直接使用已有数据，为 false 则用当前数据覆盖
+#[tauri::command]
+pub fn set_data_dir(
+    path: Option<String>,
+    use_existing: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let default_dir = default_dir(&app)?;
+    match resolve(&default_dir).1 {
+        DataDirSource::Portable => return Err("便携模式下不能更改数据目录".to_string()),
+        DataDirSource::Environment => {
+            return Err(format!("数据目录由环境变量 {DATA_DIR_ENV} 指定"))
+        }
+        DataDirSource::Default | DataDirSource::Custom => {}
+    }
+    let target = match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => default_dir.clone(),
+    };
+    if !target.is_absolute() {
+        return Err("请使用绝对路径".to_string());
+    }
+    let current = state
+        .file_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "无法确定当前数据目录".to_string())?;
+    let same = |a: &Path, b: &Path| match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    };
+    if same(&current, &target) {
+        return Err("已在使用该目录".to_string());
+    }
+    let (current_canonical, target_canonical) = (canonical(&current), canonical(&target));
+    if target_canonical.starts_with(&current_canonical) {
+        return Err("目标目录不能位于当前数据目录内".to_string());
+    }
+    if current_canonical.starts_with(&target_canonical) {
+        return Err("目标目录不能包含当前数据目录".to_string());
+    }
+    if target.is_file() {
+        return Err("目标不是目录".to_string());
+    }
+
+    let has_data = target.join(STORE_FILE).is_file();
+    if !has_data
+        && fs::read_dir(&target)
+            .is_ok_and(|mut entries| entries.any(|e| e.is_ok_and(|e| e.file_name() != STAGING_DIR)))
+        && !same(&target, &default_dir)
+    {
+        return Err("目标目录不为空，请选择空目录".to_string());
+    }
+    let adopt = match (has_data, use_existing) {
+        (true, None) => return Err("目标目录中已有数据，请选择使用已有数据或覆盖".to_string()),
+        (true, Some(adopt)) => adopt,
+        (false, _) => false,
+    };
+
+    if !adopt {
+        persist::flush(&app)?;
+        fs::create_dir_all(&target).map_err(|e| format!("无法创建数据目录: {e}"))?;
+        migrate(&current, &target)?;
+    }
+    let pointer = (!same(&target, &default_dir)).then_some(target.as_path());
+    write_pointer(&default_dir, pointer)?;
+    tracing::info!(from = %current.display(), to = %target.display(), adopt, "数据目录已更改");
+    app.request_restart();
+    Ok(())
+}
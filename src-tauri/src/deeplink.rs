@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{App, AppHandle, Emitter, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::{add_project, launch_project, launch_project_at, AppState, NewProjectInput};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DeepLinkAction {
+    /// `dev-boom://open?project=<id|name>&ide=<ideId>&file=<相对路径>&line=<行号>`
+    Open {
+        project: String,
+        ide: Option<String>,
+        file: Option<String>,
+        line: Option<u32>,
+    },
+    /// `dev-boom://add?path=<目录>&name=<名称>`
+    Add { path: String, name: Option<String> },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeepLinkResult {
+    url: String,
+    project_id: Option<String>,
+    error: Option<String>,
+}
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn parse_deep_link(url: &Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != crate::DEEP_LINK_SCHEME {
+        return Err(format!("不支持的链接协议: {}", url.scheme()));
+    }
+    // `dev-boom://open?..` 中 open 是 host，`dev-boom:open?..` 中是 path
+    let action = url
+        .host_str()
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/')
+        .to_ascii_lowercase();
+
+    match action.as_str() {
+        "open" => {
+            let project = query_param(url, "project").ok_or("链接缺少 project 参数")?;
+            let line = match query_param(url, "line") {
+                Some(v) => Some(v.parse::<u32>().map_err(|_| "line 参数必须是数字")?),
+                None => None,
+            };
+            Ok(DeepLinkAction::Open {
+                project,
+                ide: query_param(url, "ide"),
+                file: query_param(url, "file"),
+                line,
+            })
+        }
+        "add" => Ok(DeepLinkAction::Add {
+            path: query_param(url, "path").ok_or("链接缺少 path 参数")?,
+            name: query_param(url, "name"),
+        }),
+        other => Err(format!("不支持的链接操作: {other}")),
+    }
+}
+
+/// 按 id 查找项目，找不到时按名称或别名（不区分大小写）唯一匹配
+fn resolve_project_id(state: &AppState, key: &str) -> Result<String, String> {
+    let projects = state.store.projects();
+    if let Some(project) = projects.iter().find(|p| p.id == key) {
+        return Ok(project.id.clone());
+    }
+    let mut by_name = projects.iter().filter(|p| p.name.eq_ignore_ascii_case(key));
+    match (by_name.next(), by_name.next()) {
+        (Some(project), None) => Ok(project.id.clone()),
+        (Some(_), Some(_)) => Err(format!("存在多个名为 {key} 的项目，请使用项目 id")),
+        _ => projects
+            .iter()
+            .find(|p| p.aliases.iter().any(|a| a.eq_ignore_ascii_case(key)))
+            .map(|p| p.id.clone())
+            .ok_or_else(|| "项目不存在".to_string()),
+    }
+}
+
+fn run_action(app: &AppHandle, action: DeepLinkAction) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    match action {
+        DeepLinkAction::Open {
+            project,
+            ide,
+            file,
+            line,
+        } => {
+            let project_id = resolve_project_id(&state, &project)?;
+            match file {
+                Some(file) => launch_project_at(project_id.clone(), file, line, None, ide, state)?,
+                None => launch_project(project_id.clone(), ide, state)?,
+            }
+            Ok(project_id)
+        }
+        DeepLinkAction::Add { path, name } => {
+            let name = name
+                .or_else(|| {
+                    Path::new(&path)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                })
+                .ok_or("无法从路径推断项目名称")?;
+            let project = add_project(
+                NewProjectInput {
+                    name,
+                    path,
+                    project_type: None,
+                    favorite: None,
+                    tags: None,
+                    description: None,
+                    ide_preferences: None,
+                },
+                state,
+            )?;
+            if let Some(main_win) = app.get_webview_window("main") {
+                let _ = main_win.show();
+                let _ = main_win.set_focus();
+            }
+            Ok(project.id)
+        }
+    }
+}
+
+/// 处理结果通过 `deeplink://handled` 事件通知前端，失败时附带错误信息
+fn handle_url(app: &AppHandle, url: &Url) {
+    let result = parse_deep_link(url).and_then(|action| run_action(app, action));
+    let (project_id, error) = match result {
+        Ok(id) => (Some(id), None),
+        Err(err) => {
+            tracing::warn!(url = %url, error = %err, "处理深度链接失败");
+            (None, Some(err))
+        }
+    };
+    let _ = app.emit(
+        "deeplink://handled",
+        DeepLinkResult {
+            url: url.to_string(),
+            project_id,
+            error,
+        },
+    );
+}
+
+pub fn init_deep_links(app: &App) {
+    // 安装包会写入协议注册；开发模式下需要运行时注册
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        let _ = app.deep_link().register_all();
+    }
+
+    let handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        spawn_handle_urls(&handle, event.urls());
+    });
+
+    // 通过链接冷启动时，链接在启动参数中
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        spawn_handle_urls(app.handle(), urls);
+    }
+}
+
+/// 启动 IDE 会等待启动检查与就绪，放到后台线程处理，不阻塞 setup 与事件循环
+fn spawn_handle_urls(app: &AppHandle, urls: Vec<Url>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        for url in urls {
+            handle_url(&app, &url);
+        }
+    });
+}
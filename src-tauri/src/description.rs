@@ -0,0 +1,216 @@
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{
+    fulltext, manifest, persist,
+    readme::{self, ReadmeFormat},
+    AppState, Project, ProjectMetadata,
+};
+
+/// 自动描述的最大字符数，超出部分截断
+const MAX_DESCRIPTION_CHARS: usize = 300;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoDescription {
+    pub text: String,
+    /// 来源文件名，如 `Cargo.toml`、`README.md`
+    pub source: String,
+}
+
+/// 标题、徽章、图片、HTML 与分隔线等不属于正文的行
+fn is_decoration(line: &str, format: ReadmeFormat) -> bool {
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| line.starts_with(p));
+    if starts(&["[![", "![", "<", "---", "***", "```", "~~~"]) {
+        return true;
+    }
+    match format {
+        ReadmeFormat::Markdown => {
+            // `# 标题` 与引用式链接定义 `[name]: url`
+            line.starts_with('#')
+                || line.starts_with('[') && line.contains("]:") && !line.contains("](")
+        }
+        ReadmeFormat::Rst => starts(&[".. ", "|", ":"]),
+        ReadmeFormat::Asciidoc => starts(&["=", ":", "image:", "[", "//"]),
+        ReadmeFormat::Text => false,
+    }
+}
+
+/// rst 标题下方（或上方）由同一个标点重复组成的行
+fn is_underline(line: &str) -> bool {
+    let mut chars = line.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    line.len() >= 3 && first.is_ascii_punctuation() && chars.all(|c| c == first)
+}
+
+/// 去掉行内标记：图片整体删除，链接只保留文字，HTML 标签、代码与强调符号删除
+fn strip_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("![") {
+            if let Some(end) = link_end(&rest[1..]) {
+                rest = &rest[1 + end..];
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some(end) = link_end(rest) {
+                let label_end = rest.find("](").unwrap_or(end);
+                out.push_str(&rest[1..label_end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        if c == '<' {
+            if let Some(end) = rest.find('>') {
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        if !matches!(c, '`' | '*') {
+            out.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// `[文字](地址)` 结束位置之后的偏移，不是链接时返回 None
+fn link_end(text: &str) -> Option<usize> {
+    let label_end = text.find("](")?;
+    if text[1..label_end].contains('[') {
+        return None;
+    }
+    let close = text[label_end + 2..].find(')')?;
+    Some(label_end + 2 + close + 1)
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_DESCRIPTION_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_DESCRIPTION_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// README 正文的第一段，跳过开头的标题、徽章与图片
+pub fn first_paragraph(content: &str, format: ReadmeFormat) -> Option<String> {
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+    let mut paragraph: Vec<String> = vec![];
+    let mut in_fence = false;
+    for (index, line) in lines.iter().enumerate() {
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        let next_is_underline = lines.get(index + 1).is_some_and(|next| is_underline(next));
+        let skip = in_fence
+            || line.is_empty()
+            || is_decoration(line, format)
+            || is_underline(line)
+            || next_is_underline;
+        if skip {
+            if paragraph.is_empty() {
+                continue;
+            }
+            break;
+        }
+        let text = strip_inline(line);
+        let text = text.trim();
+        if !text.is_empty() {
+            paragraph.push(text.to_string());
+        }
+    }
+    let text = paragraph.join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then(|| truncate(&text))
+}
+
+/// 优先取清单中的 description，没有时取 README 的第一段
+pub fn detect(root: &Path) -> Option<AutoDescription> {
+    if let Some(summary) = manifest::manifest_summary(root) {
+        if let Some(text) = summary
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+        {
+            return Some(AutoDescription {
+                text: truncate(text),
+                source: summary.manifest.to_string(),
+            });
+        }
+    }
+    let readme = readme::read_readme(root)?;
+    Some(AutoDescription {
+        text: first_paragraph(&readme.content, readme.format)?,
+        source: readme.file_name,
+    })
+}
+
+/// 描述为空或此前是自动填写的才能写入，手动填写的描述不会被覆盖
+pub fn is_eligible(metadata: &ProjectMetadata) -> bool {
+    metadata.description_source.is_some()
+        || metadata
+            .description
+            .as_deref()
+            .is_none_or(|d| d.trim().is_empty())
+}
+
+/// 写入自动描述，返回是否有变化
+pub fn apply(metadata: &mut ProjectMetadata, auto: AutoDescription) -> bool {
+    if !is_eligible(metadata)
+        || metadata.description.as_deref() == Some(auto.text.as_str())
+            && metadata.description_source.as_deref() == Some(auto.source.as_str())
+    {
+        return false;
+    }
+    metadata.description = Some(auto.text);
+    metadata.description_source = Some(auto.source);
+    true
+}
+
+/// 为描述为空的项目从清单与 README 中提取描述，已自动填写的会重新提取；
+/// project_ids 为空时处理全部项目，返回有变化的项目
+#[tauri::command(async)]
+pub fn fill_auto_descriptions(
+    project_ids: Vec<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<Project>, String> {
+    let targets: Vec<(String, String)> = state
+        .store
+        .projects()
+        .iter()
+        .filter(|p| project_ids.is_empty() || project_ids.contains(&p.id))
+        .filter(|p| is_eligible(&p.metadata))
+        .map(|p| (p.id.clone(), p.path.clone()))
+        .collect();
+
+    // 读取文件在锁外进行
+    let detected: Vec<(String, AutoDescription)> = targets
+        .into_iter()
+        .filter_map(|(id, path)| Some((id, detect(Path::new(&path))?)))
+        .collect();
+
+    let mut projects = state.store.projects_mut();
+    let mut updated = vec![];
+    for (id, auto) in detected {
+        if let Some(project) = projects.iter_mut().find(|p| p.id == id) {
+            if apply(&mut project.metadata, auto) {
+                updated.push(project.clone());
+            }
+        }
+    }
+    drop(projects);
+    if !updated.is_empty() {
+        persist::mark_dirty();
+        fulltext::start_indexing(&app);
+    }
+    Ok(updated)
+}
@@ -0,0 +1,74 @@
+use std::{fs, path::Path, sync::RwLock};
+
+use crate::{settings::DetectionRule, wildcard_match, ProjectType};
+
+/// 设置中的自定义识别规则；修改设置时同步更新，扫描时无需再锁设置
+static RULES: RwLock<Vec<DetectionRule>> = RwLock::new(Vec::new());
+
+pub fn configure(rules: &[DetectionRule]) {
+    *RULES.write().expect("detection rules lock poisoned") = rules.to_vec();
+}
+
+pub enum RuleType {
+    Builtin(ProjectType),
+    /// 内置类型中没有的类型，如 `Deno`
+    Custom(String),
+}
+
+fn has_marker(root: &Path, marker: &str) -> bool {
+    if !marker.contains(['*', '?']) {
+        return root.join(marker).exists();
+    }
+    let pattern: Vec<char> = marker.chars().collect();
+    fs::read_dir(root)
+        .map(|entries| {
+            entries.flatten().any(|entry| {
+                let name: Vec<char> = entry.file_name().to_string_lossy().chars().collect();
+                wildcard_match(&pattern, &name)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn matching_rules(root: &Path) -> Vec<DetectionRule> {
+    RULES
+        .read()
+        .expect("detection rules lock poisoned")
+        .iter()
+        .filter(|rule| has_marker(root, &rule.marker))
+        .cloned()
+        .collect()
+}
+
+/// 目录中有任一规则的标记文件时视为项目根目录
+pub fn is_marked(root: &Path) -> bool {
+    !matching_rules(root).is_empty()
+}
+
+/// 第一条指定了类型的匹配规则决定项目类型，优先于内置识别
+pub fn rule_type(root: &Path) -> Option<RuleType> {
+    let name = matching_rules(root)
+        .into_iter()
+        .find_map(|rule| rule.project_type)?;
+    // 未知的类型名会被读取为 Generic
+    match serde_json::from_value(serde_json::Value::String(name.clone())) {
+        Ok(ProjectType::Generic) if name != "Generic" => Some(RuleType::Custom(name)),
+        Ok(project_type) => Some(RuleType::Builtin(project_type)),
+        Err(_) => Some(RuleType::Custom(name)),
+    }
+}
+
+pub fn custom_type(root: &Path) -> Option<String> {
+    match rule_type(root)? {
+        RuleType::Custom(name) => Some(name),
+        RuleType::Builtin(_) => None,
+    }
+}
+
+/// 匹配规则附带的标签，作为系统标签保存
+pub fn rule_tags(root: &Path) -> Vec<String> {
+    matching_rules(root)
+        .into_iter()
+        .flat_map(|rule| rule.tags)
+        .collect()
+}
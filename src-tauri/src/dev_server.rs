@@ -0,0 +1,517 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::{SocketAddr, TcpStream},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::{envfile, find_project, now_iso, persist, process, tasks, AppState, Project};
+
+/// 受管开发服务器的事件前缀：`dev-server://output`、`dev-server://exit`、`dev-server://ready`
+const EVENT_PREFIX: &str = "dev-server";
+
+/// 由本应用启动的开发服务器，按项目 id 索引
+static SERVERS: Mutex<Option<HashMap<String, DevServerStatus>>> = Mutex::new(None);
+
+/// 探测端口时每个地址的连接超时
+const PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// 这些变量通常就是开发服务器端口，`DB_PORT` 之类的不算
+const PORT_VARS: &[&str] = &[
+    "PORT",
+    "DEV_PORT",
+    "VITE_PORT",
+    "APP_PORT",
+    "SERVER_PORT",
+    "HTTP_PORT",
+];
+
+const ENV_FILES: &[&str] = &[
+    ".env.development.local",
+    ".env.local",
+    ".env.development",
+    ".env",
+];
+
+const VITE_CONFIGS: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.js",
+    "vite.config.mts",
+    "vite.config.mjs",
+];
+
+/// 脚本中出现这些命令且没有指定端口时使用的默认端口，靠前的优先
+const FRAMEWORK_PORTS: &[(&str, u16)] = &[
+    ("ng serve", 4200),
+    ("astro", 4321),
+    ("gatsby", 8000),
+    ("vue-cli-service", 8080),
+    ("webpack serve", 8080),
+    ("webpack-dev-server", 8080),
+    ("next", 3000),
+    ("nuxt", 3000),
+    ("remix", 3000),
+    ("react-scripts", 3000),
+    ("vite", 5173),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevUrl {
+    url: String,
+    port: u16,
+    /// 推断来源，如 `package.json` 或 `.env`
+    source: String,
+    listening: bool,
+}
+
+fn candidate(url: String, port: u16, source: &str) -> DevUrl {
+    DevUrl {
+        url,
+        port,
+        source: source.to_string(),
+        listening: false,
+    }
+}
+
+fn localhost(port: u16, source: &str) -> DevUrl {
+    candidate(format!("http://localhost:{port}"), port, source)
+}
+
+fn parse_port(value: &str) -> Option<u16> {
+    let digits: String = value
+        .trim()
+        .trim_matches(['"', '\''])
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok().filter(|port| *port > 0)
+}
+
+fn env_ports(root: &Path, project: &Project) -> Vec<DevUrl> {
+    let mut files: Vec<String> = ENV_FILES.iter().map(|f| f.to_string()).collect();
+    if let Some(env_file) = &project.metadata.env_file {
+        files.insert(0, env_file.clone());
+    }
+    files
+        .iter()
+        .filter_map(|file| Some((file, fs::read_to_string(root.join(file)).ok()?)))
+        .flat_map(|(file, content)| {
+            envfile::parse_env(&content)
+                .into_iter()
+                .filter(|(key, _)| PORT_VARS.contains(&key.as_str()))
+                .filter_map(|(_, value)| parse_port(&value))
+                .map(|port| localhost(port, file))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// 取 `server: { port: 3000 }` 中的端口，配置文件是代码，只做简单的文本匹配
+fn vite_ports(root: &Path) -> Vec<DevUrl> {
+    VITE_CONFIGS
+        .iter()
+        .filter_map(|name| Some((name, fs::read_to_string(root.join(name)).ok()?)))
+        .filter_map(|(name, content)| {
+            let server = content.find("server")?;
+            let rest = &content[server..];
+            let port = rest.find("port")?;
+            let value = rest[port + 4..].trim_start().strip_prefix(':')?;
+            Some(localhost(parse_port(value)?, name))
+        })
+        .collect()
+}
+
+/// `--port 3000`、`--port=3000`、`-p 3000` 与 `PORT=3000`
+fn script_port(script: &str) -> Option<u16> {
+    let words: Vec<&str> = script.split_whitespace().collect();
+    words.iter().enumerate().find_map(|(idx, word)| {
+        if let Some(value) = word
+            .strip_prefix("--port=")
+            .or_else(|| word.strip_prefix("PORT="))
+        {
+            return parse_port(value);
+        }
+        if matches!(*word, "--port" | "-p") {
+            return words.get(idx + 1).and_then(|v| parse_port(v));
+        }
+        None
+    })
+}
+
+fn package_json_ports(root: &Path) -> Vec<DevUrl> {
+    let Some(manifest) = fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return vec![];
+    };
+    let Some(scripts) = manifest.get("scripts").and_then(|v| v.as_object()) else {
+        return vec![];
+    };
+    ["dev", "start", "serve", "develop"]
+        .iter()
+        .filter_map(|name| scripts.get(*name)?.as_str())
+        .filter_map(|script| {
+            script_port(script).or_else(|| {
+                FRAMEWORK_PORTS
+                    .iter()
+                    .find(|(command, _)| script.contains(command))
+                    .map(|(_, port)| *port)
+            })
+        })
+        .map(|port| localhost(port, "package.json"))
+        .collect()
+}
+
+/// .NET 的 `Properties/launchSettings.json`：`"applicationUrl": "https://localhost:7001;http://localhost:5000"`
+fn launch_settings_urls(root: &Path) -> Vec<DevUrl> {
+    let Some(settings) = fs::read_to_string(root.join("Properties").join("launchSettings.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return vec![];
+    };
+    settings
+        .get("profiles")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flat_map(|profiles| profiles.values())
+        .filter_map(|profile| profile.get("applicationUrl")?.as_str())
+        .flat_map(|urls| urls.split(';'))
+        .filter_map(|url| {
+            let url = url
+                .trim()
+                .replace("0.0.0.0", "localhost")
+                .replace('*', "localhost");
+            let port = url::Url::parse(&url).ok()?.port_or_known_default()?;
+            Some(candidate(url, port, "launchSettings.json"))
+        })
+        .collect()
+}
+
+/// 其它常见框架的默认端口
+fn default_ports(root: &Path) -> Vec<DevUrl> {
+    let mut urls = vec![];
+    if root.join("manage.py").is_file() {
+        urls.push(localhost(8000, "manage.py"));
+    }
+    if root.join("bin").join("rails").is_file() {
+        urls.push(localhost(3000, "rails"));
+    }
+    urls
+}
+
+/// 按推断的可信程度排列，同一端口只保留第一个
+pub fn detect_dev_urls(project: &Project) -> Vec<DevUrl> {
+    let root = Path::new(&project.path);
+    let mut urls = env_ports(root, project);
+    urls.extend(vite_ports(root));
+    urls.extend(package_json_ports(root));
+    urls.extend(launch_settings_urls(root));
+    urls.extend(default_ports(root));
+    let mut seen = vec![];
+    urls.retain(|u| {
+        let new = !seen.contains(&u.port);
+        seen.push(u.port);
+        new
+    });
+    urls
+}
+
+/// 开发服务器可能只监听 IPv4 或 IPv6 的回环地址
+pub fn is_listening(port: u16) -> bool {
+    ["127.0.0.1", "[::1]"].iter().any(|host| {
+        format!("{host}:{port}")
+            .parse::<SocketAddr>()
+            .is_ok_and(|addr| TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).is_ok())
+    })
+}
+
+/// 受管开发服务器实际监听的端口排在最前
+fn probe(state: &AppState, project: &Project) -> Vec<DevUrl> {
+    let mut urls = detect_dev_urls(project);
+    if let Some(server) = running_server(state, &project.id) {
+        if let (Some(port), Some(url)) = (server.port, server.url) {
+            urls.retain(|u| u.port != port);
+            urls.insert(0, candidate(url, port, &server.command));
+        }
+    }
+    for url in &mut urls {
+        url.listening = is_listening(url.port);
+    }
+    urls
+}
+
+/// 从项目配置推断的开发服务器地址，并标记哪些端口正在监听
+#[tauri::command(async)]
+pub fn get_dev_urls(project_id: String, state: State<'_, AppState>) -> Result<Vec<DevUrl>, String> {
+    Ok(probe(&state, &find_project(&state, &project_id)?))
+}
+
+/// 在浏览器中打开第一个正在监听的开发服务器地址，都没有监听时打开项目配置的地址
+#[tauri::command(async)]
+pub fn open_dev_url(
+    project_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let project = find_project(&state, &project_id)?;
+    let urls = probe(&state, &project);
+    let url = urls
+        .iter()
+        .find(|u| u.listening)
+        .map(|u| u.url.clone())
+        .or_else(|| project.metadata.dev_url.clone())
+        .ok_or_else(|| {
+            if urls.is_empty() {
+                "未检测到开发服务器端口，请为项目设置地址".to_string()
+            } else {
+                let ports: Vec<String> = urls.iter().map(|u| u.port.to_string()).collect();
+                format!("开发服务器未运行（已检查端口 {}）", ports.join("、"))
+            }
+        })?;
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| format!("打开浏览器失败: {e}"))?;
+    Ok(url)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevServerStatus {
+    project_id: String,
+    /// 进程 key，与 `dev-server://output` 等事件中的 key 一致
+    key: String,
+    command: String,
+    pid: u32,
+    started_at: String,
+    /// 从输出中识别到的监听端口，识别到之前为空
+    port: Option<u16>,
+    url: Option<String>,
+}
+
+fn server_key(project_id: &str) -> String {
+    format!("{EVENT_PREFIX}/{project_id}")
+}
+
+/// 清理已退出的记录；pid 为 0 的是已登记、尚未启动完成的，保留
+fn prune_exited(state: &AppState, servers: &mut HashMap<String, DevServerStatus>) {
+    servers.retain(|_, s| s.pid == 0 || state.processes.is_running(&s.key));
+}
+
+/// 正在运行的受管开发服务器，顺带清理已退出的记录
+fn running_server(state: &AppState, project_id: &str) -> Option<DevServerStatus> {
+    let mut servers = SERVERS.lock().expect("dev server lock poisoned");
+    let servers = servers.get_or_insert_with(HashMap::new);
+    prune_exited(state, servers);
+    servers.get(project_id).cloned()
+}
+
+/// 去掉终端颜色等 ANSI 控制序列，vite 会把端口号单独加粗
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// 从 `Local: http://localhost:5173/`、`listening on port 3000` 等输出中取端口。
+/// 行中有已知地址时只看地址中的端口，没有时才找单独的 `port` 一词，`import 3` 之类的不算
+fn port_in_output(line: &str) -> Option<u16> {
+    let line = strip_ansi(line).to_lowercase();
+    let hosts = ["localhost:", "127.0.0.1:", "0.0.0.0:", "[::1]:", "[::]:"];
+    if hosts.iter().any(|host| line.contains(host)) {
+        return hosts
+            .iter()
+            .find_map(|host| parse_port(&line[line.find(host)? + host.len()..]));
+    }
+    line.match_indices("port").find_map(|(index, word)| {
+        let standalone = line[..index]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+        let rest = &line[index + word.len()..];
+        let value = rest.trim_start_matches([' ', ':', '=']);
+        (standalone && value.len() < rest.len())
+            .then(|| parse_port(value))
+            .flatten()
+    })
+}
+
+/// 没有设置命令时按 package.json 中的 dev/start 脚本推断
+fn default_command(root: &Path) -> Option<String> {
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(root.join("package.json")).ok()?).ok()?;
+    let scripts = manifest.get("scripts")?.as_object()?;
+    let script = ["dev", "start"]
+        .into_iter()
+        .find(|name| scripts.contains_key(*name))?;
+    Some(format!(
+        "{} run {script}",
+        tasks::node_package_manager(root)
+    ))
+}
+
+/// 以受管子进程启动开发服务器，输出通过 `dev-server://output` / `dev-server://exit` 事件推送；
+/// 从输出中识别到端口后发送 `dev-server://ready` 事件
+#[tauri::command]
+pub fn start_dev_server(
+    project_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DevServerStatus, String> {
+    let project = find_project(&state, &project_id)?;
+    if running_server(&state, &project.id).is_some() {
+        return Err("开发服务器已在运行".to_string());
+    }
+    let root = Path::new(&project.path);
+    let command_line = project
+        .metadata
+        .dev_command
+        .clone()
+        .or_else(|| default_command(root))
+        .ok_or_else(|| "未设置开发服务器命令".to_string())?;
+
+    let mut command = process::shell_command(&command_line);
+    command.current_dir(root);
+    envfile::apply_env_file(&mut command, &project);
+
+    let key = server_key(&project.id);
+    let on_line: process::LineHook = {
+        let app = app.clone();
+        let project_id = project.id.clone();
+        Arc::new(move |line: &str| {
+            let Some(port) = port_in_output(line) else {
+                return;
+            };
+            let mut servers = SERVERS.lock().expect("dev server lock poisoned");
+            let Some(server) = servers
+                .get_or_insert_with(HashMap::new)
+                .get_mut(&project_id)
+                .filter(|s| s.port.is_none())
+            else {
+                return;
+            };
+            server.port = Some(port);
+            server.url = Some(format!("http://localhost:{port}"));
+            let _ = app.emit(&format!("{EVENT_PREFIX}://ready"), server.clone());
+        })
+    };
+    // 先登记再启动，避免错过启动后立即输出的端口
+    let mut status = DevServerStatus {
+        project_id: project.id.clone(),
+        key: key.clone(),
+        command: command_line,
+        pid: 0,
+        started_at: now_iso(),
+        port: None,
+        url: None,
+    };
+    {
+        // 检查与登记在同一次加锁中完成，避免同时启动两个
+        let mut servers = SERVERS.lock().expect("dev server lock poisoned");
+        let servers = servers.get_or_insert_with(HashMap::new);
+        prune_exited(&state, servers);
+        if servers.contains_key(&project.id) {
+            return Err("开发服务器已在运行".to_string());
+        }
+        servers.insert(project.id.clone(), status.clone());
+    }
+    let pid = process::spawn_managed_with(&app, command, &key, EVENT_PREFIX, Some(on_line))
+        .inspect_err(|_| {
+            if let Some(servers) = SERVERS.lock().expect("dev server lock poisoned").as_mut() {
+                servers.remove(&project.id);
+            }
+        })?;
+
+    let mut servers = SERVERS.lock().expect("dev server lock poisoned");
+    if let Some(server) = servers.as_mut().and_then(|s| s.get_mut(&project.id)) {
+        server.pid = pid;
+        status = server.clone();
+    }
+    Ok(status)
+}
+
+/// 结束开发服务器的整个进程树
+#[tauri::command]
+pub fn stop_dev_server(project_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    if running_server(&state, &project_id).is_none() {
+        return Err("开发服务器未在运行".to_string());
+    }
+    state.processes.stop(&server_key(&project_id))
+}
+
+#[tauri::command]
+pub fn get_dev_server_status(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Option<DevServerStatus> {
+    running_server(&state, &project_id)
+}
+
+/// 检测不到端口时使用的地址，如 `http://localhost:8080/app`
+#[tauri::command]
+pub fn set_project_dev_url(
+    project_id: String,
+    dev_url: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let dev_url = dev_url
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    if let Some(url) = &dev_url {
+        let parsed = url::Url::parse(url).map_err(|_| format!("地址无效: {url}"))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err("只支持 http 与 https 地址".to_string());
+        }
+    }
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.metadata.dev_url = dev_url;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
+
+/// 设置 start_dev_server 运行的命令，如 `npm run dev`；为空时按 package.json 推断
+#[tauri::command]
+pub fn set_project_dev_command(
+    project_id: String,
+    command: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let command = command
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.metadata.dev_command = command;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -0,0 +1,191 @@
+use std::{collections::HashSet, process::Child};
+
+use tauri::State;
+
+use crate::{persist, AppState, IdeConfig, Project};
+
+/// IDE 配置为始终以管理员身份启动，或项目单独指定了该 IDE
+pub fn should_elevate(project: &Project, ide: &IdeConfig) -> bool {
+    ide.elevated || project.metadata.elevated_ides.contains(&ide.id)
+}
+
+/// 按 Windows 命令行规则给参数加引号
+#[cfg(target_os = "windows")]
+fn quote_windows_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        // 引号前的反斜杠需要加倍
+        let escaped = if c == '"' {
+            backslashes * 2 + 1
+        } else {
+            backslashes
+        };
+        quoted.push_str(&"\\".repeat(escaped));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// 通过 ShellExecuteEx 的 runas 动词启动，调用会阻塞到用户响应 UAC 提示。
+/// 提权后的进程不属于本应用，不计入使用时长；环境变量文件与版本激活也无法传递
+#[cfg(target_os = "windows")]
+pub fn spawn_elevated(
+    project: &Project,
+    ide: &IdeConfig,
+    args: &[String],
+) -> Result<Option<Child>, String> {
+    use windows::{
+        core::PCWSTR,
+        Win32::Foundation::ERROR_CANCELLED,
+        Win32::UI::Shell::{ShellExecuteExW, SEE_MASK_NOASYNC, SHELLEXECUTEINFOW},
+        Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL,
+    };
+
+    let wide =
+        |value: &str| -> Vec<u16> { value.encode_utf16().chain(std::iter::once(0)).collect() };
+    let verb = wide("runas");
+    let file = wide(&ide.executable);
+    let params = wide(
+        &args
+            .iter()
+            .map(|arg| quote_windows_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    let dir = wide(&project.path);
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOASYNC,
+        lpVerb: PCWSTR(verb.as_ptr()),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(params.as_ptr()),
+        lpDirectory: PCWSTR(dir.as_ptr()),
+        nShow: SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+    match unsafe { ShellExecuteExW(&mut info) } {
+        Ok(()) => Ok(None),
+        Err(err) if err.code() == ERROR_CANCELLED.to_hresult() => {
+            Err("已取消管理员权限请求".to_string())
+        }
+        Err(err) => Err(format!("以管理员身份启动失败: {err}")),
+    }
+}
+
+/// 通过 pkexec 启动。pkexec 会清空环境变量，需显式传入图形会话相关的变量。
+/// 提权后的 shell 把 IDE 放到后台后立即退出，等待 pkexec 自身的退出码即可得知认证结果，
+/// 调用会阻塞到用户响应认证对话框；与 Windows 一样拿不到 IDE 进程
+#[cfg(target_os = "linux")]
+pub fn spawn_elevated(
+    project: &Project,
+    ide: &IdeConfig,
+    args: &[String],
+) -> Result<Option<Child>, String> {
+    use std::process::{Command, Stdio};
+
+    use crate::find_executable_in_path;
+
+    if find_executable_in_path("pkexec").is_none() {
+        return Err("未找到 pkexec，无法以管理员身份启动".to_string());
+    }
+    let mut command = Command::new("pkexec");
+    command.arg("env");
+    for key in [
+        "DISPLAY",
+        "XAUTHORITY",
+        "WAYLAND_DISPLAY",
+        "XDG_RUNTIME_DIR",
+    ] {
+        if let Ok(value) = std::env::var(key) {
+            command.arg(format!("{key}={value}"));
+        }
+    }
+    // pkexec 不保留工作目录；找不到程序时以 2 退出，避免与 pkexec 的 127 混淆
+    command
+        .args([
+            "sh",
+            "-c",
+            "cd \"$1\" || exit 1; shift; command -v \"$1\" >/dev/null || exit 2; \
+             \"$@\" </dev/null >/dev/null 2>&1 &",
+            "sh",
+        ])
+        .arg(&project.path)
+        .arg(&ide.executable)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null());
+    let output = command
+        .output()
+        .map_err(|e| format!("以管理员身份启动失败: {e}"))?;
+    match output.status.code() {
+        Some(0) => Ok(None),
+        // 126：用户取消了认证对话框；127：未获授权
+        Some(126 | 127) => Err("管理员权限请求被拒绝或已取消".to_string()),
+        Some(1) => Err("无法进入项目目录".to_string()),
+        Some(2) => Err(format!("找不到可执行文件: {}", ide.executable)),
+        _ => Err(format!(
+            "以管理员身份启动失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn spawn_elevated(
+    _project: &Project,
+    _ide: &IdeConfig,
+    _args: &[String],
+) -> Result<Option<Child>, String> {
+    Err("当前系统不支持以管理员身份启动".to_string())
+}
+
+#[tauri::command]
+pub fn set_ide_elevated(
+    ide_id: String,
+    elevated: bool,
+    state: State<'_, AppState>,
+) -> Result<IdeConfig, String> {
+    let mut ides = state.store.ides_mut();
+    let ide = ides
+        .iter_mut()
+        .find(|x| x.id == ide_id)
+        .ok_or_else(|| "IDE 不存在".to_string())?;
+    ide.elevated = elevated;
+    let updated = ide.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
+
+/// 该项目中需要以管理员身份启动的 IDE
+#[tauri::command]
+pub fn set_project_elevated_ides(
+    project_id: String,
+    ide_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let valid: HashSet<String> = state.store.ides().iter().map(|i| i.id.clone()).collect();
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    let mut seen = HashSet::new();
+    project.metadata.elevated_ides = ide_ids
+        .into_iter()
+        .filter(|id| valid.contains(id) && seen.insert(id.clone()))
+        .collect();
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -0,0 +1,174 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, persist, AppState, Project};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvPreviewEntry {
+    key: String,
+    /// 只显示前两个字符
+    masked_value: String,
+    /// 当前进程环境中已有同名变量，启动时会被覆盖
+    overrides_existing: bool,
+}
+
+/// 日志与预览中不输出完整的值
+pub fn mask_value(value: &str) -> String {
+    if value.chars().count() <= 4 {
+        return "****".to_string();
+    }
+    let prefix: String = value.chars().take(2).collect();
+    format!("{prefix}****")
+}
+
+fn unescape_double_quoted(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 解析 dotenv 格式：支持 `export` 前缀、`#` 注释、单引号（原样）与双引号（转义）值
+pub fn parse_env(content: &str) -> Vec<(String, String)> {
+    let mut vars = vec![];
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            continue;
+        }
+        let value = value.trim();
+        let value = if let Some(inner) = value.strip_prefix('"').and_then(|v| v.rsplit_once('"')) {
+            unescape_double_quoted(inner.0)
+        } else if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.rsplit_once('\'')) {
+            inner.0.to_string()
+        } else {
+            // 未加引号时 ` #` 之后是行尾注释
+            value
+                .split_once(" #")
+                .map(|(v, _)| v)
+                .unwrap_or(value)
+                .trim()
+                .to_string()
+        };
+        vars.push((key.to_string(), value));
+    }
+    vars
+}
+
+/// 相对路径相对于项目根目录
+fn resolve_env_path(project: &Project, env_file: &str) -> PathBuf {
+    let path = Path::new(env_file);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        Path::new(&project.path).join(path)
+    }
+}
+
+fn read_env_file(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("读取环境变量文件失败 {}: {e}", path.display()))?;
+    Ok(parse_env(&content))
+}
+
+/// 项目配置的 .env 中的变量，未配置或读取失败时为空
+pub fn project_env(project: &Project) -> Vec<(String, String)> {
+    let Some(env_file) = project.metadata.env_file.as_deref() else {
+        return vec![];
+    };
+    match read_env_file(&resolve_env_path(project, env_file)) {
+        Ok(vars) => {
+            let masked: Vec<String> = vars
+                .iter()
+                .map(|(key, value)| format!("{key}={}", mask_value(value)))
+                .collect();
+            tracing::info!(project = %project.name, vars = %masked.join(" "), "注入环境变量");
+            vars
+        }
+        Err(err) => {
+            tracing::warn!(project = %project.name, "{err}");
+            vec![]
+        }
+    }
+}
+
+/// 为启动的 IDE、终端与任务注入项目 .env 中的变量
+pub fn apply_env_file(command: &mut Command, project: &Project) {
+    command.envs(project_env(project));
+}
+
+/// 预览将要设置的变量；传入 env_file 时预览该文件，否则预览项目当前配置的文件
+#[tauri::command]
+pub fn preview_env_file(
+    project_id: String,
+    env_file: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<EnvPreviewEntry>, String> {
+    let project = find_project(&state, &project_id)?;
+    let Some(env_file) = env_file.or_else(|| project.metadata.env_file.clone()) else {
+        return Ok(vec![]);
+    };
+    let vars = read_env_file(&resolve_env_path(&project, &env_file))?;
+    Ok(vars
+        .into_iter()
+        .map(|(key, value)| EnvPreviewEntry {
+            overrides_existing: env::var_os(&key).is_some(),
+            masked_value: mask_value(&value),
+            key,
+        })
+        .collect())
+}
+
+/// 传入空值表示不再加载环境变量文件
+#[tauri::command]
+pub fn set_project_env_file(
+    project_id: String,
+    env_file: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let env_file = env_file
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    if let Some(env_file) = &env_file {
+        let path = resolve_env_path(project, env_file);
+        if !path.is_file() {
+            return Err(format!("环境变量文件不存在: {}", path.display()));
+        }
+    }
+    project.metadata.env_file = env_file;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use url::Url;
+
+use crate::network;
+
+/// 首页最多读取的字节数，`<link rel="icon">` 一般位于 head 中
+const PAGE_LIMIT: usize = 512 * 1024;
+
+/// 补全协议并校验，仅接受 http/https 地址
+pub fn normalize_website(website: &str) -> Result<Option<String>, String> {
+    let website = website.trim();
+    if website.is_empty() {
+        return Ok(None);
+    }
+    let candidate = if website.contains("://") {
+        website.to_string()
+    } else {
+        format!("https://{website}")
+    };
+    let url = Url::parse(&candidate).map_err(|e| format!("网站地址无效: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err("网站地址必须是 http 或 https 地址".to_string());
+    }
+    Ok(Some(url.to_string()))
+}
+
+/// 读取标签中某个属性的值，支持双引号、单引号与无引号写法
+fn attr_value<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name) {
+        let start = from + pos;
+        from = start + name.len();
+        let preceded = lower[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_whitespace());
+        let rest = lower[from..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        return match value.chars().next()? {
+            quote @ ('"' | '\'') => value[1..].split(quote).next(),
+            _ => value
+                .split(|c: char| c.is_ascii_whitespace() || c == '>')
+                .next(),
+        };
+    }
+    None
+}
+
+/// 页面中声明的图标地址，apple-touch-icon 排在普通图标之后
+fn icon_links(html: &str) -> Vec<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut icons = vec![];
+    let mut touch_icons = vec![];
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find("<link") {
+        let start = from + pos;
+        let end = lower[start..]
+            .find('>')
+            .map(|i| start + i)
+            .unwrap_or(lower.len());
+        from = end;
+        let tag = &html[start..end];
+        let Some(rel) = attr_value(tag, "rel") else {
+            continue;
+        };
+        let rel = rel.to_ascii_lowercase();
+        let Some(href) = attr_value(tag, "href").filter(|v| !v.trim().is_empty()) else {
+            continue;
+        };
+        let href = href.trim().replace("&amp;", "&");
+        if rel.contains("apple-touch-icon") {
+            touch_icons.push(href);
+        } else if rel.split_whitespace().any(|v| v == "icon") {
+            icons.push(href);
+        }
+    }
+    icons.extend(touch_icons);
+    icons
+}
+
+fn fetch_page(url: &Url) -> Option<String> {
+    use std::io::Read;
+
+    let client = network::client(Duration::from_secs(6), "dev-boom/0.1 ide-icon-fetch").ok()?;
+    let response = client.get(url.as_str()).send().ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let mut body = Vec::new();
+    response
+        .take(PAGE_LIMIT as u64)
+        .read_to_end(&mut body)
+        .ok()?;
+    Some(String::from_utf8_lossy(&body).to_string())
+}
+
+/// 网站的候选图标地址：首页 `<link rel="icon">` 声明的图标，最后回退到 `/favicon.ico`
+pub fn site_icon_urls(website: &str) -> Vec<String> {
+    let Ok(base) = Url::parse(website) else {
+        return vec![];
+    };
+    let mut urls: Vec<String> = fetch_page(&base)
+        .map(|html| icon_links(&html))
+        .unwrap_or_default()
+        .into_iter()
+        // data: 图标无法走下载缓存
+        .filter_map(|href| base.join(&href).ok())
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(|url| url.to_string())
+        .collect();
+    if let Ok(favicon) = base.join("/favicon.ico") {
+        urls.push(favicon.to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    urls.retain(|url| seen.insert(url.clone()));
+    urls
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, mtime, AppState};
+
+const DEFAULT_LIMIT: usize = 50;
+/// 索引的最大文件数，超大仓库只索引前面的部分
+const MAX_INDEXED_FILES: usize = 100_000;
+/// 文件列表的缓存时间，连续输入时不必每次都重新列出文件
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+type FileIndex = Arc<Vec<String>>;
+
+static CACHE: Mutex<Option<HashMap<String, (Instant, FileIndex)>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHit {
+    /// 相对于项目根目录，使用 `/` 分隔，可直接传给 launch_project_at
+    relative_path: String,
+    name: String,
+    score: i64,
+    /// relative_path 中匹配字符的下标（按字符计），用于高亮
+    indices: Vec<usize>,
+}
+
+fn file_index(root: &Path) -> FileIndex {
+    let key = root.to_string_lossy().to_string();
+    if let Some((at, files)) = CACHE
+        .lock()
+        .expect("file index lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .get(&key)
+    {
+        if at.elapsed() < CACHE_TTL {
+            return files.clone();
+        }
+    }
+    let mut files: Vec<String> = mtime::project_files(root)
+        .into_iter()
+        .take(MAX_INDEXED_FILES)
+        .filter_map(|path| {
+            let relative = path.strip_prefix(root).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    files.sort();
+    let files = Arc::new(files);
+    CACHE
+        .lock()
+        .expect("file index lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .insert(key, (Instant::now(), files.clone()));
+    files
+}
+
+/// 文件名命中的权重高于目录部分命中
+fn match_file(matcher: &SkimMatcherV2, relative_path: &str, query: &str) -> Option<FileHit> {
+    let (dir_chars, name) = match relative_path.rsplit_once('/') {
+        Some((dir, name)) => (dir.chars().count() + 1, name),
+        None => (0, relative_path),
+    };
+    let by_name = matcher.fuzzy_indices(name, query).map(|(score, indices)| {
+        let indices = indices.into_iter().map(|i| i + dir_chars).collect();
+        (score * 2, indices)
+    });
+    let by_path = matcher.fuzzy_indices(relative_path, query);
+    let (score, indices) = match (by_name, by_path) {
+        (Some(n), Some(p)) => {
+            if n.0 >= p.0 {
+                n
+            } else {
+                p
+            }
+        }
+        (n, p) => n.or(p)?,
+    };
+    Some(FileHit {
+        relative_path: relative_path.to_string(),
+        name: name.to_string(),
+        score,
+        indices,
+    })
+}
+
+/// 在项目文件中按文件名模糊搜索，文件列表遵循 .gitignore 并缓存一段时间
+#[tauri::command(async)]
+pub fn find_files(
+    project_id: String,
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<FileHit>, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(vec![]);
+    }
+    let files = file_index(root);
+    let matcher = SkimMatcherV2::default().smart_case();
+    let mut hits: Vec<FileHit> = files
+        .iter()
+        .filter_map(|file| match_file(&matcher, file, query))
+        .collect();
+    // 同分时路径短的排在前面
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.relative_path.len().cmp(&b.relative_path.len()))
+    });
+    hits.truncate(limit.filter(|v| *v > 0).unwrap_or(DEFAULT_LIMIT));
+    Ok(hits)
+}
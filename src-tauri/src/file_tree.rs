@@ -0,0 +1,170 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, git_output, should_skip_dir, AppState};
+
+const DEFAULT_DEPTH: usize = 1;
+const MAX_DEPTH: usize = 5;
+/// 单次请求最多返回的节点数，超出的目录标记为 truncated，由前端展开时再取
+const MAX_NODES: usize = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNode {
+    name: String,
+    /// 相对于项目根目录，使用 `/` 分隔，可直接传给 launch_project_at
+    relative_path: String,
+    is_dir: bool,
+    size: Option<u64>,
+    /// 未展开的目录为空，展开时再以该目录为 sub_path 请求
+    children: Option<Vec<TreeNode>>,
+    /// 节点数达到上限，children 不完整
+    truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTree {
+    nodes: Vec<TreeNode>,
+    truncated: bool,
+}
+
+/// 被 .gitignore 忽略的文件与目录（目录不再展开列出其中的文件），不是 git 仓库时为空
+fn ignored_paths(root: &Path, sub_path: &str) -> HashSet<String> {
+    let mut args = vec![
+        "ls-files",
+        "-z",
+        "--others",
+        "--ignored",
+        "--exclude-standard",
+        "--directory",
+    ];
+    if !sub_path.is_empty() {
+        args.extend(["--", sub_path]);
+    }
+    git_output(root, &args)
+        .map(|output| {
+            output
+                .split('\0')
+                .filter(|name| !name.is_empty())
+                .map(|name| name.trim_end_matches('/').to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 解析并校验项目内的子目录，返回绝对路径与规范化的相对路径
+fn resolve_dir(root: &Path, sub_path: &str) -> Result<(PathBuf, String), String> {
+    let relative = sub_path.trim().replace('\\', "/");
+    let relative = relative.trim_matches('/');
+    if relative.is_empty() {
+        return Ok((root.to_path_buf(), String::new()));
+    }
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| format!("无法读取项目路径: {e}"))?;
+    let dir = root.join(relative);
+    let canonical = dir.canonicalize().map_err(|_| "目录不存在".to_string())?;
+    if !canonical.starts_with(&canonical_root) {
+        return Err("目录不在项目目录内".to_string());
+    }
+    if !canonical.is_dir() {
+        return Err("目标不是目录".to_string());
+    }
+    Ok((dir, relative.to_string()))
+}
+
+struct Walker {
+    ignored: HashSet<String>,
+    remaining: usize,
+}
+
+impl Walker {
+    fn read(&mut self, dir: &Path, relative: &str, depth: usize) -> (Vec<TreeNode>, bool) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return (vec![], false);
+        };
+        let mut entries: Vec<(String, PathBuf, bool)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                // 不跟随符号链接，指向目录的链接也按文件显示，避免循环
+                let is_dir = entry.file_type().ok()?.is_dir();
+                Some((name, entry.path(), is_dir))
+            })
+            .filter(|(name, path, is_dir)| {
+                let child = join(relative, name);
+                !(self.ignored.contains(&child) || *is_dir && should_skip_dir(path))
+            })
+            .collect();
+        // 目录在前，同类按名称排序（不区分大小写）
+        entries.sort_by(|a, b| {
+            b.2.cmp(&a.2)
+                .then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase()))
+        });
+
+        let mut nodes = vec![];
+        for (name, path, is_dir) in entries {
+            if self.remaining == 0 {
+                return (nodes, true);
+            }
+            self.remaining -= 1;
+            let child = join(relative, &name);
+            let (children, truncated) = if is_dir && depth > 1 {
+                let (children, truncated) = self.read(&path, &child, depth - 1);
+                (Some(children), truncated)
+            } else {
+                (None, false)
+            };
+            nodes.push(TreeNode {
+                size: (!is_dir)
+                    .then(|| fs::metadata(&path).ok().map(|m| m.len()))
+                    .flatten(),
+                name,
+                relative_path: child,
+                is_dir,
+                children,
+                truncated,
+            });
+        }
+        (nodes, false)
+    }
+}
+
+fn join(relative: &str, name: &str) -> String {
+    if relative.is_empty() {
+        name.to_string()
+    } else {
+        format!("{relative}/{name}")
+    }
+}
+
+/// 项目目录树，跳过依赖目录与 .gitignore 忽略的文件；sub_path 为空时从项目根目录开始，
+/// depth 为展开的层数
+#[tauri::command(async)]
+pub fn get_project_tree(
+    project_id: String,
+    sub_path: Option<String>,
+    depth: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<ProjectTree, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    let (dir, relative) = resolve_dir(root, sub_path.as_deref().unwrap_or(""))?;
+    let depth = depth.unwrap_or(DEFAULT_DEPTH).clamp(1, MAX_DEPTH);
+    let mut walker = Walker {
+        ignored: ignored_paths(root, &relative),
+        remaining: MAX_NODES,
+    };
+    let (nodes, truncated) = walker.read(&dir, &relative, depth);
+    Ok(ProjectTree { nodes, truncated })
+}
@@ -0,0 +1,480 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::{
+    background::{TaskContext, TaskKind},
+    file_mtime_iso, readme, stable_hash, AppState, Project,
+};
+
+/// README 只索引前 64KB，索引文件也保存这部分原文用于生成摘要
+const MAX_INDEXED_README_CHARS: usize = 64 * 1024;
+const DEFAULT_FULL_TEXT_LIMIT: usize = 30;
+const SNIPPET_BEFORE_CHARS: usize = 40;
+const SNIPPET_AFTER_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "PascalCase")]
+pub enum IndexField {
+    Name,
+    Tags,
+    Description,
+    Notes,
+    Readme,
+}
+
+impl IndexField {
+    fn weight(self) -> f64 {
+        match self {
+            IndexField::Name => 5.0,
+            IndexField::Tags => 3.0,
+            IndexField::Description | IndexField::Notes => 2.0,
+            IndexField::Readme => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedField {
+    field: IndexField,
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedDoc {
+    /// 元数据与 README 修改时间的指纹，未变化时跳过重建
+    fingerprint: String,
+    fields: Vec<IndexedField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Posting {
+    project_id: String,
+    field: IndexField,
+    tf: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchIndex {
+    docs: HashMap<String, IndexedDoc>,
+    postings: BTreeMap<String, Vec<Posting>>,
+}
+
+pub struct IndexState {
+    index: Mutex<SearchIndex>,
+    running: AtomicBool,
+    /// 运行期间又有内容变化，当前任务结束后需要再运行一次
+    pending: AtomicBool,
+}
+
+impl IndexState {
+    pub fn load(store_file_path: &Path) -> Self {
+        let index = fs::read_to_string(index_path(store_file_path))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            index: Mutex::new(index),
+            running: AtomicBool::new(false),
+            pending: AtomicBool::new(false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexProgress {
+    done: usize,
+    total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullTextHit {
+    project_id: String,
+    name: String,
+    score: f64,
+    field: IndexField,
+    snippet: String,
+}
+
+fn index_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("search-index.json")
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}')
+}
+
+/// 英文等按单词切分并转小写；中日韩文字没有空格分词，按相邻二字切分
+fn tokenize(text: &str) -> Vec<String> {
+    fn flush_cjk(run: &mut Vec<char>, tokens: &mut Vec<String>) {
+        if run.len() == 1 {
+            tokens.push(run[0].to_string());
+        } else {
+            tokens.extend(run.windows(2).map(|w| w.iter().collect::<String>()));
+        }
+        run.clear();
+    }
+
+    let mut tokens = vec![];
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = vec![];
+    for c in text.chars() {
+        if is_cjk(c) {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() {
+            if !cjk_run.is_empty() {
+                flush_cjk(&mut cjk_run, &mut tokens);
+            }
+            word.extend(c.to_lowercase());
+        } else {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            if !cjk_run.is_empty() {
+                flush_cjk(&mut cjk_run, &mut tokens);
+            }
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    if !cjk_run.is_empty() {
+        flush_cjk(&mut cjk_run, &mut tokens);
+    }
+    tokens
+}
+
+fn doc_fingerprint(project: &Project, readme_path: Option<&Path>) -> String {
+    let readme_mtime = readme_path
+        .and_then(|p| file_mtime_iso(&p.to_string_lossy()))
+        .unwrap_or_default();
+    stable_hash(&format!(
+        "{}\u{0}{}\u{0}{}\u{0}{}\u{0}{}\u{0}{}",
+        project.name,
+        project.tags.join("\u{1}"),
+        project.metadata.description.as_deref().unwrap_or(""),
+        project.notes.as_deref().unwrap_or(""),
+        readme_path
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        readme_mtime
+    ))
+}
+
+fn build_doc(project: &Project, fingerprint: String) -> IndexedDoc {
+    let mut fields = vec![IndexedField {
+        field: IndexField::Name,
+        text: project.name.clone(),
+    }];
+    let mut push = |field: IndexField, text: Option<String>| {
+        if let Some(text) = text.filter(|t| !t.trim().is_empty()) {
+            fields.push(IndexedField { field, text });
+        }
+    };
+    push(IndexField::Tags, Some(project.tags.join(" ")));
+    push(
+        IndexField::Description,
+        project.metadata.description.clone(),
+    );
+    push(IndexField::Notes, project.notes.clone());
+    push(
+        IndexField::Readme,
+        readme::read_readme(Path::new(&project.path))
+            .map(|r| r.content.chars().take(MAX_INDEXED_README_CHARS).collect()),
+    );
+    IndexedDoc {
+        fingerprint,
+        fields,
+    }
+}
+
+impl SearchIndex {
+    fn rebuild_postings(&mut self) {
+        let mut postings: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+        for (project_id, doc) in &self.docs {
+            for field in &doc.fields {
+                let mut counts: HashMap<String, u32> = HashMap::new();
+                for token in tokenize(&field.text) {
+                    *counts.entry(token).or_default() += 1;
+                }
+                for (token, tf) in counts {
+                    postings.entry(token).or_default().push(Posting {
+                        project_id: project_id.clone(),
+                        field: field.field,
+                        tf,
+                    });
+                }
+            }
+        }
+        self.postings = postings;
+    }
+
+    /// 查询词之间为「与」关系；最后一个词按前缀匹配，便于边输入边搜索
+    fn search(&self, query: &str, live_ids: &HashSet<String>, limit: usize) -> Vec<FullTextHit> {
+        let terms = tokenize(query);
+        if terms.is_empty() {
+            return vec![];
+        }
+        let total_docs = self.docs.len().max(1) as f64;
+
+        // project_id → (得分, 命中的最佳字段及其权重)
+        let mut matched: Option<HashMap<String, (f64, IndexField, f64)>> = None;
+        for (idx, term) in terms.iter().enumerate() {
+            let is_last = idx + 1 == terms.len();
+            let lists: Vec<&Vec<Posting>> = if is_last {
+                self.postings
+                    .range(term.clone()..)
+                    .take_while(|(token, _)| token.starts_with(term.as_str()))
+                    .map(|(_, list)| list)
+                    .collect()
+            } else {
+                self.postings.get(term).into_iter().collect()
+            };
+
+            let mut term_scores: HashMap<String, (f64, IndexField, f64)> = HashMap::new();
+            for list in lists {
+                let df = list
+                    .iter()
+                    .map(|p| p.project_id.as_str())
+                    .collect::<HashSet<_>>()
+                    .len() as f64;
+                let idf = (1.0 + total_docs / df.max(1.0)).ln();
+                for posting in list {
+                    let weight = posting.field.weight();
+                    let score = (1.0 + (posting.tf as f64).ln()) * weight * idf;
+                    let entry = term_scores.entry(posting.project_id.clone()).or_insert((
+                        0.0,
+                        posting.field,
+                        0.0,
+                    ));
+                    entry.0 += score;
+                    if weight > entry.2 {
+                        entry.1 = posting.field;
+                        entry.2 = weight;
+                    }
+                }
+            }
+
+            matched = Some(match matched {
+                None => term_scores,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(id, (score, field, weight))| {
+                        let (term_score, term_field, term_weight) = term_scores.get(&id)?;
+                        let (field, weight) = if *term_weight > weight {
+                            (*term_field, *term_weight)
+                        } else {
+                            (field, weight)
+                        };
+                        Some((id, (score + term_score, field, weight)))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<FullTextHit> = matched
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(id, _)| live_ids.contains(id))
+            .filter_map(|(id, (score, field, _))| {
+                let doc = self.docs.get(&id)?;
+                let name = doc
+                    .fields
+                    .iter()
+                    .find(|f| f.field == IndexField::Name)
+                    .map(|f| f.text.clone())
+                    .unwrap_or_default();
+                let text = doc
+                    .fields
+                    .iter()
+                    .find(|f| f.field == field)
+                    .map(|f| f.text.as_str())
+                    .unwrap_or("");
+                Some(FullTextHit {
+                    project_id: id,
+                    name,
+                    score,
+                    field,
+                    snippet: make_snippet(text, &terms),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// 在原文中定位第一个命中的查询词，截取前后若干字符作为摘要
+fn make_snippet(text: &str, terms: &[String]) -> String {
+    let chars: Vec<char> = text
+        .chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    let position = terms.iter().find_map(|term| {
+        let needle: Vec<char> = term.chars().collect();
+        lower
+            .windows(needle.len().max(1))
+            .position(|window| window == needle.as_slice())
+    });
+    let center = position.unwrap_or(0);
+    let start = center.saturating_sub(SNIPPET_BEFORE_CHARS);
+    let end = (center + SNIPPET_AFTER_CHARS).min(chars.len());
+
+    let mut snippet: String = chars[start..end].iter().collect();
+    snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+fn run_indexing(app: &AppHandle, task: &TaskContext) {
+    let state = app.state::<AppState>();
+    let projects = state.store.projects().clone();
+    let mut docs = state
+        .search_index
+        .index
+        .lock()
+        .expect("search index lock poisoned")
+        .docs
+        .clone();
+
+    let total = projects.len();
+    let live_ids: HashSet<String> = projects.iter().map(|p| p.id.clone()).collect();
+    docs.retain(|id, _| live_ids.contains(id));
+
+    for (done, project) in projects.iter().enumerate() {
+        // 取消时丢弃本次结果，保留原索引
+        if task.is_cancelled() {
+            return;
+        }
+        task.progress(done, total);
+        let readme_path = readme::find_readme(Path::new(&project.path)).map(|(path, _)| path);
+        let fingerprint = doc_fingerprint(project, readme_path.as_deref());
+        let unchanged = docs
+            .get(&project.id)
+            .map(|doc| doc.fingerprint == fingerprint)
+            .unwrap_or(false);
+        if !unchanged {
+            docs.insert(project.id.clone(), build_doc(project, fingerprint));
+        }
+        let _ = app.emit(
+            "index://progress",
+            IndexProgress {
+                done: done + 1,
+                total,
+            },
+        );
+    }
+
+    let mut index = state
+        .search_index
+        .index
+        .lock()
+        .expect("search index lock poisoned");
+    index.docs = docs;
+    index.rebuild_postings();
+    if let Ok(content) = serde_json::to_string(&*index) {
+        if let Err(err) = fs::write(index_path(&state.file_path), content) {
+            tracing::warn!(error = %err, "保存全文索引失败");
+        }
+    }
+    tracing::info!(projects = total, "全文索引已更新");
+}
+
+/// 在后台线程中增量重建索引；已有任务在运行时返回 false，并在该任务结束后再运行一次，
+/// 运行期间修改的内容不会漏掉
+pub fn start_indexing(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    if state.search_index.running.swap(true, Ordering::SeqCst) {
+        state.search_index.pending.store(true, Ordering::SeqCst);
+        return false;
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        loop {
+            let _ = state
+                .background
+                .run(TaskKind::Indexing, "全文索引", |task| {
+                    run_indexing(&app, task);
+                    Ok(())
+                });
+            let total = state.store.projects().len();
+            let _ = app.emit("index://done", IndexProgress { done: total, total });
+            state.search_index.running.store(false, Ordering::SeqCst);
+            // 没有待处理的变化，或已有新任务开始（会包含这些变化）时结束
+            if !state.search_index.pending.swap(false, Ordering::SeqCst)
+                || state.search_index.running.swap(true, Ordering::SeqCst)
+            {
+                break;
+            }
+        }
+    });
+    true
+}
+
+/// 进度通过 `index://progress` 事件推送，完成后发送 `index://done`
+#[tauri::command]
+pub fn index_projects(app: AppHandle) -> bool {
+    start_indexing(&app)
+}
+
+#[tauri::command]
+pub fn full_text_search(
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Vec<FullTextHit> {
+    let live_ids: HashSet<String> = state
+        .store
+        .projects()
+        .iter()
+        .map(|p| p.id.clone())
+        .collect();
+    state
+        .search_index
+        .index
+        .lock()
+        .expect("search index lock poisoned")
+        .search(
+            &query,
+            &live_ids,
+            limit.filter(|v| *v > 0).unwrap_or(DEFAULT_FULL_TEXT_LIMIT),
+        )
+}
@@ -0,0 +1,232 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{git, AppState};
+
+/// 展示的作者数量
+const TOP_AUTHORS: usize = 10;
+/// HEAD 未变化时复用统计结果；时间范围随日期推移，所以也不能一直复用
+const CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// 缓存键为 (仓库路径, 时间范围)，值为 (计算时间, HEAD, 结果)
+type CacheEntry = (Instant, String, GitStats);
+
+static CACHE: Mutex<Option<HashMap<(String, StatsRange), CacheEntry>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum StatsRange {
+    Month,
+    #[default]
+    Quarter,
+    Year,
+    All,
+}
+
+impl StatsRange {
+    fn days(self) -> Option<u64> {
+        match self {
+            StatsRange::Month => Some(30),
+            StatsRange::Quarter => Some(90),
+            StatsRange::Year => Some(365),
+            StatsRange::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekCommits {
+    /// 周一的日期，如 `2024-05-06`
+    week_start: String,
+    commits: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorStats {
+    name: String,
+    email: String,
+    commits: usize,
+    lines_added: u64,
+    lines_removed: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStats {
+    range: StatsRange,
+    total_commits: usize,
+    lines_added: u64,
+    lines_removed: u64,
+    /// 按时间顺序，没有提交的周也会列出
+    weeks: Vec<WeekCommits>,
+    /// 按提交数排序的前几位作者
+    authors: Vec<AuthorStats>,
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(u64::from(date.weekday().num_days_from_monday()))
+}
+
+struct Commit {
+    date: NaiveDate,
+    author: (String, String),
+    added: u64,
+    removed: u64,
+}
+
+/// 每个提交以 `\x1e` 开头，随后是 `作者\0邮箱\0时间`，再跟 `--numstat` 的行；二进制文件的增删为 `-`
+fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut header = lines.next()?.split('\0');
+            let name = header.next()?.to_string();
+            let email = header.next()?.to_lowercase();
+            let date = DateTime::parse_from_rfc3339(header.next()?.trim()).ok()?;
+            let (added, removed) = lines
+                .filter_map(|line| {
+                    let mut fields = line.split('\t');
+                    let added = fields.next()?.parse::<u64>().ok()?;
+                    let removed = fields.next()?.parse::<u64>().ok()?;
+                    Some((added, removed))
+                })
+                .fold((0, 0), |(a, r), (added, removed)| (a + added, r + removed));
+            Some(Commit {
+                date: date.with_timezone(&Utc).date_naive(),
+                author: (name, email),
+                added,
+                removed,
+            })
+        })
+        .collect()
+}
+
+fn compute(dir: &Path, range: StatsRange) -> Result<GitStats, String> {
+    let today = Utc::now().date_naive();
+    let since = range.days().map(|days| today - Days::new(days));
+    let mut args = vec![
+        "log".to_string(),
+        "--no-merges".to_string(),
+        "--numstat".to_string(),
+        // 使用 .mailmap 合并同一作者的不同身份
+        "--format=%x1e%aN%x00%aE%x00%aI".to_string(),
+    ];
+    if let Some(since) = since {
+        args.push(format!("--since={since}"));
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let commits = parse_log(&git(dir, &args)?);
+
+    let mut weeks: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut authors: HashMap<(String, String), AuthorStats> = HashMap::new();
+    for commit in &commits {
+        *weeks.entry(week_start(commit.date)).or_default() += 1;
+        let author = authors
+            .entry(commit.author.clone())
+            .or_insert_with(|| AuthorStats {
+                name: commit.author.0.clone(),
+                email: commit.author.1.clone(),
+                commits: 0,
+                lines_added: 0,
+                lines_removed: 0,
+            });
+        author.commits += 1;
+        author.lines_added += commit.added;
+        author.lines_removed += commit.removed;
+    }
+
+    // 补齐没有提交的周，图表的横轴才是连续的
+    let first = since
+        .or_else(|| weeks.keys().next().copied())
+        .map(week_start);
+    if let Some(first) = first {
+        let mut week = first;
+        while week <= today {
+            weeks.entry(week).or_default();
+            week = week + Days::new(7);
+        }
+    }
+
+    let mut authors: Vec<AuthorStats> = authors.into_values().collect();
+    authors.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then(b.lines_added.cmp(&a.lines_added))
+    });
+    authors.truncate(TOP_AUTHORS);
+
+    Ok(GitStats {
+        range,
+        total_commits: commits.len(),
+        lines_added: commits.iter().map(|c| c.added).sum(),
+        lines_removed: commits.iter().map(|c| c.removed).sum(),
+        weeks: weeks
+            .into_iter()
+            .map(|(week, commits)| WeekCommits {
+                week_start: week.to_string(),
+                commits,
+            })
+            .collect(),
+        authors,
+    })
+}
+
+/// 项目详情中的活跃度图表：每周提交数、主要作者与增删行数，不含合并提交
+#[tauri::command(async)]
+pub fn get_git_stats(
+    project_id: String,
+    range: Option<StatsRange>,
+    state: State<'_, AppState>,
+) -> Result<GitStats, String> {
+    let range = range.unwrap_or_default();
+    let dir = {
+        let projects = state.store.projects();
+        let project = projects
+            .iter()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| "项目不存在".to_string())?;
+        PathBuf::from(&project.path)
+    };
+    if !dir.join(".git").exists() {
+        return Err("项目不是 git 仓库".to_string());
+    }
+    // 空仓库还没有 HEAD
+    let Ok(head) = git(&dir, &["rev-parse", "HEAD"]) else {
+        return Ok(GitStats {
+            range,
+            total_commits: 0,
+            lines_added: 0,
+            lines_removed: 0,
+            weeks: vec![],
+            authors: vec![],
+        });
+    };
+    let key = (dir.to_string_lossy().to_string(), range);
+    if let Some((at, cached_head, stats)) = CACHE
+        .lock()
+        .expect("git stats cache lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .get(&key)
+    {
+        if at.elapsed() < CACHE_TTL && *cached_head == head {
+            return Ok(stats.clone());
+        }
+    }
+    let stats = compute(&dir, range)?;
+    CACHE
+        .lock()
+        .expect("git stats cache lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .insert(key, (Instant::now(), head, stats.clone()));
+    Ok(stats)
+}
@@ -0,0 +1,342 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    find_executable_in_path, find_project, git_output, network, process, tasks, AppState,
+    ProjectType,
+};
+
+const GIT_REMOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum HealthLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthIssue {
+    /// 稳定的机器可读标识，如 `toolchain-missing`，供前端选择徽标
+    code: &'static str,
+    level: HealthLevel,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    project_id: String,
+    checked_at: String,
+    /// 没有问题时为空
+    issues: Vec<HealthIssue>,
+}
+
+fn issue(code: &'static str, level: HealthLevel, message: impl Into<String>) -> HealthIssue {
+    HealthIssue {
+        code,
+        level,
+        message: message.into(),
+    }
+}
+
+/// 超时后结束进程，返回是否在限定时间内成功退出
+fn run_with_timeout(mut command: Command, timeout: Duration) -> bool {
+    process::hide_console_window(&mut command);
+    let Ok(mut child) = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status.success(),
+            Ok(None) if started.elapsed() < timeout => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return false;
+            }
+        }
+    }
+}
+
+fn check_git(root: &Path, issues: &mut Vec<HealthIssue>) {
+    if !root.join(".git").exists() {
+        return;
+    }
+    let Some(remote) = git_output(root, &["config", "--get", "remote.origin.url"]) else {
+        issues.push(issue(
+            "git-no-remote",
+            HealthLevel::Info,
+            "Git 仓库没有配置 origin 远端",
+        ));
+        return;
+    };
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(root)
+        .args(["ls-remote", "--heads", "origin"])
+        .env("GIT_TERMINAL_PROMPT", "0");
+    // 离线模式下不检查远端
+    if network::prepare_git(&mut command).is_err() {
+        return;
+    }
+    if !run_with_timeout(command, GIT_REMOTE_TIMEOUT) {
+        issues.push(issue(
+            "git-remote-unreachable",
+            HealthLevel::Warning,
+            format!("无法访问远端仓库 {remote}"),
+        ));
+    }
+}
+
+/// 按项目类型与目录中的构建文件列出需要的命令行工具
+fn required_tools(root: &Path, project_type: &ProjectType) -> Vec<&'static str> {
+    match project_type {
+        ProjectType::Rust => vec!["cargo"],
+        ProjectType::Nodejs => {
+            let manager = tasks::node_package_manager(root);
+            if manager == "bun" {
+                vec![manager]
+            } else {
+                vec!["node", manager]
+            }
+        }
+        ProjectType::Python => {
+            let mut tools = vec!["python"];
+            if root.join("poetry.lock").exists() {
+                tools.push("poetry");
+            }
+            if root.join("uv.lock").exists() {
+                tools.push("uv");
+            }
+            tools
+        }
+        ProjectType::Java => {
+            let mut tools = vec!["java"];
+            if root.join("pom.xml").exists() && !root.join("mvnw").exists() {
+                tools.push("mvn");
+            }
+            let gradle =
+                root.join("build.gradle").exists() || root.join("build.gradle.kts").exists();
+            if gradle && !root.join("gradlew").exists() {
+                tools.push("gradle");
+            }
+            tools
+        }
+        ProjectType::Go => vec!["go"],
+        ProjectType::Dotnet => vec!["dotnet"],
+        ProjectType::Flutter => vec!["flutter"],
+        ProjectType::Dart => vec!["dart"],
+        ProjectType::Php => {
+            let mut tools = vec!["php"];
+            if !root.join("composer.phar").exists() {
+                tools.push("composer");
+            }
+            tools
+        }
+        ProjectType::Ruby => vec!["ruby", "bundle"],
+        ProjectType::Elixir => vec!["elixir", "mix"],
+        ProjectType::Cpp => vec!["cmake"],
+        ProjectType::Zig => vec!["zig"],
+        ProjectType::Terraform => vec!["terraform"],
+        // Unity 项目通过 Unity Hub 打开，不检查命令行工具
+        ProjectType::Unity | ProjectType::Generic => vec![],
+    }
+}
+
+fn tool_installed(tool: &str) -> bool {
+    if find_executable_in_path(tool).is_some() {
+        return true;
+    }
+    // 部分系统只提供 python3
+    tool == "python" && find_executable_in_path("python3").is_some()
+}
+
+fn check_toolchain(root: &Path, project_type: &ProjectType, issues: &mut Vec<HealthIssue>) {
+    for tool in required_tools(root, project_type) {
+        if !tool_installed(tool) {
+            issues.push(issue(
+                "toolchain-missing",
+                HealthLevel::Error,
+                format!("未找到 {tool}，请安装后加入 PATH"),
+            ));
+        }
+    }
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
+/// package-lock.json 中缺少 package.json 声明的依赖时说明没有重新安装
+fn npm_lock_missing(root: &Path) -> Vec<String> {
+    let read = |name: &str| -> Option<serde_json::Value> {
+        serde_json::from_str(&fs::read_to_string(root.join(name)).ok()?).ok()
+    };
+    let (Some(manifest), Some(lock)) = (read("package.json"), read("package-lock.json")) else {
+        return vec![];
+    };
+    let mut missing = vec![];
+    for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+        let Some(deps) = manifest.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for name in deps.keys() {
+            let in_packages = lock
+                .get("packages")
+                .and_then(|p| p.get(format!("node_modules/{name}")))
+                .is_some();
+            let in_dependencies = lock.get("dependencies").and_then(|d| d.get(name)).is_some();
+            if !in_packages && !in_dependencies {
+                missing.push(name.clone());
+            }
+        }
+    }
+    missing
+}
+
+/// Cargo.lock 以 `name = "xxx"` 记录每个包，重命名的依赖以 package 字段为准
+fn cargo_lock_missing(root: &Path) -> Vec<String> {
+    let (Ok(manifest), Ok(lock)) = (
+        fs::read_to_string(root.join("Cargo.toml")),
+        fs::read_to_string(root.join("Cargo.lock")),
+    ) else {
+        return vec![];
+    };
+    let Ok(manifest) = manifest.parse::<toml::Table>() else {
+        return vec![];
+    };
+    let locked: HashSet<&str> = lock
+        .lines()
+        .filter_map(|line| line.strip_prefix("name = "))
+        .map(|name| name.trim_matches('"'))
+        .collect();
+    let mut missing = vec![];
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        let Some(deps) = manifest.get(section).and_then(|v| v.as_table()) else {
+            continue;
+        };
+        for (key, value) in deps {
+            let name = value.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+            if !locked.contains(name) {
+                missing.push(name.to_string());
+            }
+        }
+    }
+    missing
+}
+
+fn check_lockfiles(root: &Path, issues: &mut Vec<HealthIssue>) {
+    if root.join("package.json").exists() {
+        let lockfiles: Vec<&str> = [
+            "package-lock.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "bun.lockb",
+            "bun.lock",
+        ]
+        .into_iter()
+        .filter(|name| root.join(name).exists())
+        .collect();
+        if lockfiles.is_empty() {
+            issues.push(issue(
+                "lockfile-missing",
+                HealthLevel::Warning,
+                "package.json 没有对应的锁文件",
+            ));
+        } else if lockfiles.len() > 1 {
+            issues.push(issue(
+                "lockfile-conflict",
+                HealthLevel::Warning,
+                format!("存在多个包管理器的锁文件: {}", lockfiles.join(", ")),
+            ));
+        }
+    }
+
+    let missing: Vec<String> = npm_lock_missing(root)
+        .into_iter()
+        .chain(cargo_lock_missing(root))
+        .collect();
+    if !missing.is_empty() {
+        issues.push(issue(
+            "lockfile-out-of-sync",
+            HealthLevel::Warning,
+            format!("锁文件中缺少依赖: {}", missing.join(", ")),
+        ));
+    }
+
+    // 其余生态只能比较修改时间：清单比锁文件新时可能忘了重新锁定
+    let pairs = [
+        ("pyproject.toml", "poetry.lock"),
+        ("pyproject.toml", "uv.lock"),
+        ("Pipfile", "Pipfile.lock"),
+        ("go.mod", "go.sum"),
+        ("composer.json", "composer.lock"),
+        ("Gemfile", "Gemfile.lock"),
+    ];
+    for (manifest, lockfile) in pairs {
+        let (Some(manifest_time), Some(lock_time)) =
+            (mtime(&root.join(manifest)), mtime(&root.join(lockfile)))
+        else {
+            continue;
+        };
+        if manifest_time > lock_time {
+            issues.push(issue(
+                "lockfile-stale",
+                HealthLevel::Warning,
+                format!("{manifest} 在 {lockfile} 之后修改过，锁文件可能未更新"),
+            ));
+        }
+    }
+}
+
+/// 依次检查路径、Git 远端、工具链与锁文件，返回问题列表供前端渲染徽标。
+/// 访问远端可能较慢，在后台线程执行
+#[tauri::command]
+pub async fn check_project_health(
+    project_id: String,
+    app: AppHandle,
+) -> Result<HealthReport, String> {
+    let project = find_project(&app.state::<AppState>(), &project_id)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = Path::new(&project.path);
+        let mut issues = vec![];
+        if !root.is_dir() {
+            issues.push(issue(
+                "path-missing",
+                HealthLevel::Error,
+                format!("项目路径不存在: {}", project.path),
+            ));
+        } else {
+            check_git(root, &mut issues);
+            check_toolchain(root, &project.project_type, &mut issues);
+            check_lockfiles(root, &mut issues);
+        }
+        HealthReport {
+            project_id: project.id,
+            checked_at: crate::now_iso(),
+            issues,
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
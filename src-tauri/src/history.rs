@@ -0,0 +1,129 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::AppState;
+
+/// 只保留最近的启动记录，避免文件无限增长
+const MAX_LAUNCH_RECORDS: usize = 10_000;
+const MAX_FAILURE_RECORDS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchRecord {
+    pub project_id: String,
+    pub ide_id: Option<String>,
+    pub launched_at: String,
+}
+
+/// 启动失败的记录，error 中包含进程退出前的输出
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchFailure {
+    pub project_id: String,
+    pub ide_id: String,
+    pub failed_at: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchHistory {
+    pub records: Vec<LaunchRecord>,
+    #[serde(default)]
+    pub failures: Vec<LaunchFailure>,
+}
+
+impl LaunchHistory {
+    pub fn record(&mut self, project_id: &str, ide_id: Option<&str>) {
+        self.records.push(LaunchRecord {
+            project_id: project_id.to_string(),
+            ide_id: ide_id.map(str::to_string),
+            launched_at: Utc::now().to_rfc3339(),
+        });
+        if self.records.len() > MAX_LAUNCH_RECORDS {
+            let overflow = self.records.len() - MAX_LAUNCH_RECORDS;
+            self.records.drain(..overflow);
+        }
+    }
+
+    pub fn record_failure(&mut self, project_id: &str, ide_id: &str, error: &str) {
+        self.failures.push(LaunchFailure {
+            project_id: project_id.to_string(),
+            ide_id: ide_id.to_string(),
+            failed_at: Utc::now().to_rfc3339(),
+            error: error.to_string(),
+        });
+        if self.failures.len() > MAX_FAILURE_RECORDS {
+            let overflow = self.failures.len() - MAX_FAILURE_RECORDS;
+            self.failures.drain(..overflow);
+        }
+    }
+
+    pub fn remove_project(&mut self, project_id: &str) {
+        self.records.retain(|r| r.project_id != project_id);
+        self.failures.retain(|f| f.project_id != project_id);
+    }
+
+    /// 类 Firefox 的 frecency：每次打开按距今时间衰减后累加
+    pub fn frecency(&self) -> HashMap<String, f64> {
+        let now = Utc::now();
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for record in &self.records {
+            let Ok(at) = DateTime::parse_from_rfc3339(&record.launched_at) else {
+                continue;
+            };
+            let age_days = (now - at.with_timezone(&Utc)).num_days();
+            let weight = match age_days {
+                ..=4 => 100.0,
+                5..=14 => 70.0,
+                15..=31 => 50.0,
+                32..=90 => 30.0,
+                _ => 10.0,
+            };
+            *scores.entry(record.project_id.clone()).or_default() += weight;
+        }
+        scores
+    }
+}
+
+pub fn history_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("launch-history.json")
+}
+
+pub fn load_history(path: &Path) -> LaunchHistory {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_history(path: &Path, history: &LaunchHistory) -> Result<(), String> {
+    let content = serde_json::to_string(history).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// 最近的启动失败记录，新的在前
+#[tauri::command]
+pub fn get_launch_failures(
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Vec<LaunchFailure> {
+    let history = state.history.lock().expect("history lock poisoned");
+    history
+        .failures
+        .iter()
+        .rev()
+        .filter(|f| project_id.as_deref().is_none_or(|id| id == f.project_id))
+        .cloned()
+        .collect()
+}
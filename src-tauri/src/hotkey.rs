@@ -0,0 +1,170 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::{settings, window_state, AppState};
+
+pub const DEFAULT_HOTKEY: &str = "Ctrl+Alt+Space";
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyStatus {
+    /// 当前实际生效的快捷键，未注册时为空
+    active: Option<String>,
+    /// 最近一次注册失败的原因
+    error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct HotkeyState {
+    status: Mutex<HotkeyStatus>,
+}
+
+impl HotkeyState {
+    fn status(&self) -> HotkeyStatus {
+        self.status.lock().expect("hotkey lock poisoned").clone()
+    }
+
+    fn set(&self, active: Option<String>, error: Option<String>) {
+        *self.status.lock().expect("hotkey lock poisoned") = HotkeyStatus { active, error };
+    }
+}
+
+fn toggle_mini_window(app: &AppHandle) {
+    let Some(mini_win) = app.get_webview_window("mini") else {
+        return;
+    };
+    let visible = mini_win.is_visible().unwrap_or(false);
+    let focused = mini_win.is_focused().unwrap_or(false);
+    if visible && focused {
+        let _ = mini_win.hide();
+        return;
+    }
+    if let Some(main_win) = app.get_webview_window("main") {
+        let _ = main_win.hide();
+    }
+    window_state::restore_mini_window_position(&mini_win);
+    let _ = mini_win.show();
+    let _ = mini_win.set_focus();
+}
+
+fn parse_hotkey(hotkey: &str) -> Result<Shortcut, String> {
+    hotkey
+        .parse::<Shortcut>()
+        .map_err(|e| format!("快捷键格式无效: {hotkey} ({e})"))
+}
+
+/// `ctrl+alt+space` 与 `Ctrl+Alt+Space` 视为同一个快捷键
+fn same_hotkey(a: &str, b: &str) -> bool {
+    match (parse_hotkey(a), parse_hotkey(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn register_hotkey(app: &AppHandle, hotkey: &str) -> Result<(), String> {
+    let shortcut = parse_hotkey(hotkey)?;
+    app.global_shortcut()
+        .on_shortcut(shortcut, |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                toggle_mini_window(app);
+            }
+        })
+        .map_err(|e| format!("快捷键 {hotkey} 注册失败，可能已被其他程序占用: {e}"))
+}
+
+fn unregister_hotkey(app: &AppHandle, hotkey: &str) {
+    if let Ok(shortcut) = parse_hotkey(hotkey) {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+}
+
+fn emit_error(app: &AppHandle, status: &HotkeyStatus) {
+    if let Some(error) = &status.error {
+        tracing::warn!(active = ?status.active, "{error}");
+        let _ = app.emit("hotkey://error", status.clone());
+    }
+}
+
+/// 启动时注册设置中的快捷键；失败时回退到默认快捷键，并通过 `hotkey://error` 事件通知前端
+pub fn init_hotkey(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let configured = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .global_hotkey
+        .clone();
+    if configured.is_empty() {
+        return;
+    }
+
+    let status = match register_hotkey(app, &configured) {
+        Ok(()) => HotkeyStatus {
+            active: Some(configured),
+            error: None,
+        },
+        Err(err) if !configured.eq_ignore_ascii_case(DEFAULT_HOTKEY) => {
+            let active = register_hotkey(app, DEFAULT_HOTKEY)
+                .ok()
+                .map(|_| DEFAULT_HOTKEY.to_string());
+            HotkeyStatus {
+                active,
+                error: Some(err),
+            }
+        }
+        Err(err) => HotkeyStatus {
+            active: None,
+            error: Some(err),
+        },
+    };
+    state
+        .hotkey
+        .set(status.active.clone(), status.error.clone());
+    emit_error(app, &status);
+}
+
+#[tauri::command]
+pub fn get_global_hotkey(state: State<'_, AppState>) -> HotkeyStatus {
+    state.hotkey.status()
+}
+
+/// 运行时重新绑定快捷键；新快捷键注册失败时保留原有绑定。传入空字符串表示禁用
+#[tauri::command]
+pub fn set_global_hotkey(
+    hotkey: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<HotkeyStatus, String> {
+    let hotkey = hotkey.trim().to_string();
+    let current = state.hotkey.status().active;
+
+    let unchanged = current
+        .as_deref()
+        .map(|c| same_hotkey(c, &hotkey))
+        .unwrap_or(false);
+    if !unchanged {
+        if !hotkey.is_empty() {
+            if let Err(err) = register_hotkey(&app, &hotkey) {
+                state.hotkey.set(current, Some(err.clone()));
+                emit_error(&app, &state.hotkey.status());
+                return Err(err);
+            }
+        }
+        if let Some(current) = current.as_deref() {
+            unregister_hotkey(&app, current);
+        }
+    }
+
+    let mut settings = state.settings.lock().expect("settings lock poisoned");
+    settings.global_hotkey = hotkey.clone();
+    settings::save_settings(&settings::settings_path(&state.file_path), &settings)?;
+    drop(settings);
+
+    state
+        .hotkey
+        .set((!hotkey.is_empty()).then_some(hotkey), None);
+    Ok(state.hotkey.status())
+}
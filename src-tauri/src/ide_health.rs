@@ -0,0 +1,195 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{
+    find_executable_in_path, forget_ide, get_known_ides, normalize_windows_path_for_ui, path_key,
+    persist, resolve_ide_executable, tray, AppState, IdeConfig,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum IdeStatus {
+    Ok,
+    /// 原路径已失效，找到了新的可执行文件
+    Relocated,
+    /// 找不到可执行文件
+    Broken,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdeCheck {
+    ide_id: String,
+    name: String,
+    executable: String,
+    status: IdeStatus,
+    /// 重新查找到的可执行文件路径
+    resolved: Option<String>,
+    /// 已把 executable 更新为 resolved
+    fixed: bool,
+    auto_detected: bool,
+}
+
+/// 可执行文件的实际路径：带目录的按路径检查，只有命令名的从 PATH 中查找
+pub fn resolve_executable(executable: &str) -> Option<PathBuf> {
+    let path = Path::new(executable.trim());
+    if path.components().count() > 1 || path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    find_executable_in_path(executable.trim())
+}
+
+/// VS Code 系列的命令行 shim 位于安装目录的 bin 下（bin/code.cmd → Code.exe），视为主程序
+fn shim_target(path: &Path) -> Option<PathBuf> {
+    let parent = path.parent()?;
+    if !parent
+        .file_name()?
+        .to_string_lossy()
+        .eq_ignore_ascii_case("bin")
+    {
+        return None;
+    }
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    fs::read_dir(parent.parent()?)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|candidate| {
+            candidate.is_file()
+                && candidate
+                    .file_stem()
+                    .is_some_and(|s| s.to_string_lossy().eq_ignore_ascii_case(&stem))
+                && candidate
+                    .extension()
+                    .is_none_or(|ext| ext.eq_ignore_ascii_case("exe"))
+        })
+}
+
+/// 判断 IDE 是否重复时使用的键：解析并规范化后的可执行文件路径，Windows 上不区分大小写
+pub fn executable_key(executable: &str) -> Option<String> {
+    let path = resolve_executable(executable)?.canonicalize().ok()?;
+    let path = shim_target(&path).unwrap_or(path);
+    Some(path_key(&normalize_windows_path_for_ui(
+        &path.to_string_lossy(),
+    )))
+}
+
+/// 自动检测的 IDE 按内置列表重新查找，其它按可执行文件名从 PATH 查找
+fn relocate(ide: &IdeConfig) -> Option<PathBuf> {
+    if ide.auto_detected {
+        if let Some(found) = get_known_ides()
+            .iter()
+            .find(|def| def.id == ide.id)
+            .and_then(resolve_ide_executable)
+        {
+            return Some(found);
+        }
+    }
+    let file_name = Path::new(&ide.executable).file_name()?.to_string_lossy();
+    find_executable_in_path(&file_name)
+}
+
+fn check(ide: &IdeConfig) -> IdeCheck {
+    let (status, resolved) = if resolve_executable(&ide.executable).is_some() {
+        (IdeStatus::Ok, None)
+    } else {
+        match relocate(ide) {
+            Some(found) => (
+                IdeStatus::Relocated,
+                Some(normalize_windows_path_for_ui(&found.to_string_lossy())),
+            ),
+            None => (IdeStatus::Broken, None),
+        }
+    };
+    IdeCheck {
+        ide_id: ide.id.clone(),
+        name: ide.name.clone(),
+        executable: ide.executable.clone(),
+        status,
+        resolved,
+        fixed: false,
+        auto_detected: ide.auto_detected,
+    }
+}
+
+/// 检查每个 IDE 的可执行文件是否还在，IDE 升级或卸载后路径会失效。
+/// fix 为 true 时把找到新路径的 IDE 更新为新路径；查找在锁外进行
+#[tauri::command(async)]
+pub fn check_ides(fix: Option<bool>, state: State<'_, AppState>) -> Vec<IdeCheck> {
+    let ides = state.store.ides().clone();
+    let mut checks: Vec<IdeCheck> = ides.iter().map(check).collect();
+    if !fix.unwrap_or(false) {
+        return checks;
+    }
+
+    let mut ides = state.store.ides_mut();
+    for item in checks.iter_mut() {
+        let Some(resolved) = item.resolved.as_ref() else {
+            continue;
+        };
+        // 检查期间被修改过的 IDE 不覆盖
+        if let Some(ide) = ides
+            .iter_mut()
+            .find(|i| i.id == item.ide_id && i.executable == item.executable)
+        {
+            tracing::info!(ide = %ide.name, from = %ide.executable, to = %resolved, "已更新 IDE 路径");
+            ide.executable = resolved.clone();
+            item.fixed = true;
+        }
+    }
+    if checks.iter().any(|c| c.fixed) {
+        persist::mark_dirty();
+    }
+    checks
+}
+
+/// 删除找不到可执行文件的自动检测 IDE，手动添加的 IDE 不会删除，返回被删除的 IDE
+#[tauri::command(async)]
+pub fn remove_stale_ides(app: AppHandle, state: State<'_, AppState>) -> Vec<IdeConfig> {
+    let stale: Vec<(String, String)> = state
+        .store
+        .ides()
+        .clone()
+        .iter()
+        .filter(|ide| ide.auto_detected)
+        .map(check)
+        .filter(|c| c.status == IdeStatus::Broken)
+        .map(|c| (c.ide_id, c.executable))
+        .collect();
+    if stale.is_empty() {
+        return vec![];
+    }
+
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let mut ides = state.store.ides_mut();
+    let mut removed = vec![];
+    ides.retain(|ide| {
+        let is_stale = stale
+            .iter()
+            .any(|(id, executable)| ide.id == *id && ide.executable == *executable);
+        if is_stale {
+            removed.push(ide.clone());
+        }
+        !is_stale
+    });
+    for ide in &removed {
+        forget_ide(&mut projects, &ide.id);
+    }
+    if !removed.is_empty() {
+        persist::mark_dirty();
+    }
+    drop(ides);
+    drop(projects);
+    if !removed.is_empty() {
+        state.undo.push("删除失效的 IDE", snapshot, &state.store);
+        tray::refresh_tray_menu(&app);
+    }
+    tracing::info!(count = removed.len(), "已删除失效的自动检测 IDE");
+    removed
+}
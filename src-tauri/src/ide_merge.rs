@@ -0,0 +1,117 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{ide_health::executable_key, persist, tray, AppState, IdeConfig, Project};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateIdes {
+    /// 解析后的可执行文件路径
+    executable: String,
+    ides: Vec<IdeConfig>,
+}
+
+/// 按解析后的可执行文件分组，只返回包含多个 IDE 的组
+#[tauri::command(async)]
+pub fn find_duplicate_ides(state: State<'_, AppState>) -> Vec<DuplicateIdes> {
+    let mut groups: Vec<DuplicateIdes> = vec![];
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for ide in state.store.ides().clone() {
+        let Some(key) = executable_key(&ide.executable) else {
+            continue;
+        };
+        match index.get(&key) {
+            Some(&idx) => groups[idx].ides.push(ide),
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push(DuplicateIdes {
+                    executable: key,
+                    ides: vec![ide],
+                });
+            }
+        }
+    }
+    groups.retain(|g| g.ides.len() > 1);
+    groups
+}
+
+fn replace_id(ids: &mut Vec<String>, from: &str, to: &str) {
+    for id in ids.iter_mut().filter(|id| *id == from) {
+        *id = to.to_string();
+    }
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+}
+
+/// 把项目中对 from 的引用改为 to，已引用 to 的项目保留原有设置
+fn carry_over(projects: &mut [Project], from: &str, to: &str) {
+    for project in projects.iter_mut() {
+        let metadata = &mut project.metadata;
+        replace_id(&mut metadata.ide_preferences, from, to);
+        replace_id(&mut metadata.elevated_ides, from, to);
+        if metadata.launch_steps.iter().any(|s| s.ide_id == to) {
+            metadata.launch_steps.retain(|s| s.ide_id != from);
+        } else {
+            for step in metadata
+                .launch_steps
+                .iter_mut()
+                .filter(|s| s.ide_id == from)
+            {
+                step.ide_id = to.to_string();
+            }
+        }
+        if let Some(preset) = metadata.ide_arg_presets.remove(from) {
+            metadata
+                .ide_arg_presets
+                .entry(to.to_string())
+                .or_insert(preset);
+        }
+        if metadata.last_used_ide.as_deref() == Some(from) {
+            metadata.last_used_ide = Some(to.to_string());
+        }
+    }
+}
+
+/// 把 merge_ids 合并到 keep_id：项目的首选 IDE、启动步骤等改为引用保留的 IDE，然后删除其余 IDE
+#[tauri::command]
+pub fn merge_ides(
+    keep_id: String,
+    merge_ids: Vec<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<IdeConfig, String> {
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let mut ides = state.store.ides_mut();
+    let kept = ides
+        .iter()
+        .find(|i| i.id == keep_id)
+        .cloned()
+        .ok_or_else(|| "IDE 不存在".to_string())?;
+    let merge_ids: Vec<String> = merge_ids.into_iter().filter(|id| *id != keep_id).collect();
+    if let Some(missing) = merge_ids
+        .iter()
+        .find(|id| !ides.iter().any(|i| i.id == **id))
+    {
+        return Err(format!("IDE 不存在: {missing}"));
+    }
+    if merge_ids.is_empty() {
+        return Ok(kept);
+    }
+
+    for id in &merge_ids {
+        carry_over(&mut projects, id, &keep_id);
+    }
+    ides.retain(|i| !merge_ids.contains(&i.id));
+    persist::mark_dirty();
+    drop(ides);
+    drop(projects);
+    state
+        .undo
+        .push(&format!("合并 IDE {}", kept.name), snapshot, &state.store);
+    tray::refresh_tray_menu(&app);
+    tracing::info!(ide = %kept.name, merged = merge_ids.len(), "已合并重复的 IDE");
+    Ok(kept)
+}
@@ -0,0 +1,271 @@
+use std::path::PathBuf;
+
+/// 在 Scoop、Chocolatey、winget 的安装位置以及注册表 App Paths、Uninstall 键中查找可执行文件，
+/// 这些位置不在内置的固定路径里，也不一定在 PATH 中
+#[cfg(target_os = "windows")]
+pub fn find_installed(executable_name: &str) -> Option<PathBuf> {
+    let exe = if std::path::Path::new(executable_name).extension().is_some() {
+        executable_name.to_string()
+    } else {
+        format!("{executable_name}.exe")
+    };
+    find_in_scoop(&exe)
+        .or_else(|| find_in_winget(&exe))
+        .or_else(|| find_in_app_paths(&exe))
+        .or_else(|| find_in_uninstall(&exe))
+        .or_else(|| find_in_chocolatey(&exe))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn find_installed(_executable_name: &str) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn env_dir(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 目录下的子目录，读取失败时为空
+#[cfg(target_os = "windows")]
+fn subdirs(dir: &std::path::Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 优先取 `apps\<app>\current` 下的真实程序，其次是 `shims` 中的启动器
+#[cfg(target_os = "windows")]
+fn find_in_scoop(exe: &str) -> Option<PathBuf> {
+    let roots = [
+        env_dir("SCOOP").or_else(|| env_dir("USERPROFILE").map(|home| home.join("scoop"))),
+        env_dir("SCOOP_GLOBAL").or_else(|| env_dir("ProgramData").map(|data| data.join("scoop"))),
+    ];
+    let roots: Vec<PathBuf> = roots.into_iter().flatten().collect();
+    roots
+        .iter()
+        .flat_map(|root| subdirs(&root.join("apps")))
+        .map(|app| app.join("current"))
+        .flat_map(|current| [current.join(exe), current.join("bin").join(exe)])
+        .find(|path| path.is_file())
+        .or_else(|| {
+            roots
+                .iter()
+                .map(|root| root.join("shims").join(exe))
+                .find(|path| path.is_file())
+        })
+}
+
+#[cfg(target_os = "windows")]
+fn find_in_chocolatey(exe: &str) -> Option<PathBuf> {
+    let root = env_dir("ChocolateyInstall")
+        .or_else(|| env_dir("ProgramData").map(|data| data.join("chocolatey")))?;
+    Some(root.join("bin").join(exe)).filter(|path| path.is_file())
+}
+
+/// 便携版软件包的链接目录与解压目录
+#[cfg(target_os = "windows")]
+fn find_in_winget(exe: &str) -> Option<PathBuf> {
+    let user = env_dir("LOCALAPPDATA").map(|dir| dir.join("Microsoft").join("WinGet"));
+    let machine = env_dir("ProgramFiles").map(|dir| dir.join("WinGet"));
+    let roots: Vec<PathBuf> = [user, machine].into_iter().flatten().collect();
+    roots
+        .iter()
+        .map(|root| root.join("Links").join(exe))
+        .find(|path| path.is_file())
+        .or_else(|| {
+            roots
+                .iter()
+                .flat_map(|root| subdirs(&root.join("Packages")))
+                .flat_map(|package| {
+                    let mut dirs = subdirs(&package);
+                    dirs.push(package);
+                    dirs
+                })
+                .map(|dir| dir.join(exe))
+                .find(|path| path.is_file())
+        })
+}
+
+#[cfg(target_os = "windows")]
+mod registry {
+    use windows::{
+        core::{PCWSTR, PWSTR},
+        Win32::System::Registry::{
+            RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY, KEY_READ, RRF_RT_REG_SZ,
+        },
+    };
+
+    pub use windows::Win32::System::Registry::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    fn wide(value: &str) -> Vec<u16> {
+        value.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    /// 读取字符串值，name 为 None 时读取默认值；REG_EXPAND_SZ 会展开环境变量
+    pub fn read_string(hive: HKEY, subkey: &str, name: Option<&str>) -> Option<String> {
+        let subkey = wide(subkey);
+        let name = name.map(wide);
+        let mut buf = vec![0u16; 2048];
+        let mut size = (buf.len() * 2) as u32;
+        let status = unsafe {
+            RegGetValueW(
+                hive,
+                PCWSTR(subkey.as_ptr()),
+                name.as_ref()
+                    .map(|n| PCWSTR(n.as_ptr()))
+                    .unwrap_or(PCWSTR::null()),
+                RRF_RT_REG_SZ,
+                None,
+                Some(buf.as_mut_ptr().cast()),
+                Some(&mut size),
+            )
+        };
+        if status.is_err() {
+            return None;
+        }
+        let len = (size as usize / 2).saturating_sub(1);
+        let value = String::from_utf16_lossy(&buf[..len]).trim().to_string();
+        (!value.is_empty()).then_some(value)
+    }
+
+    pub fn subkeys(hive: HKEY, path: &str) -> Vec<String> {
+        let path = wide(path);
+        let mut key = HKEY::default();
+        if unsafe { RegOpenKeyExW(hive, PCWSTR(path.as_ptr()), 0, KEY_READ, &mut key) }.is_err() {
+            return vec![];
+        }
+        let mut names = vec![];
+        let mut buf = vec![0u16; 256];
+        for index in 0.. {
+            let mut len = buf.len() as u32;
+            let status = unsafe {
+                RegEnumKeyExW(
+                    key,
+                    index,
+                    PWSTR(buf.as_mut_ptr()),
+                    &mut len,
+                    None,
+                    PWSTR::null(),
+                    None,
+                    None,
+                )
+            };
+            if status.is_err() {
+                break;
+            }
+            names.push(String::from_utf16_lossy(&buf[..len as usize]));
+        }
+        unsafe {
+            let _ = RegCloseKey(key);
+        }
+        names
+    }
+}
+
+/// 注册表中的路径值可能带引号或 `,0` 形式的图标索引
+#[cfg(target_os = "windows")]
+fn registry_path(value: &str) -> PathBuf {
+    let value = value.trim().trim_matches('"');
+    let value = match value.rsplit_once(',') {
+        Some((path, index)) if index.trim().parse::<i32>().is_ok() => path,
+        _ => value,
+    };
+    PathBuf::from(value.trim().trim_matches('"'))
+}
+
+/// `HKCU`/`HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\<exe>` 的默认值
+#[cfg(target_os = "windows")]
+fn find_in_app_paths(exe: &str) -> Option<PathBuf> {
+    let key = format!(r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\{exe}");
+    [registry::HKEY_CURRENT_USER, registry::HKEY_LOCAL_MACHINE]
+        .into_iter()
+        .filter_map(|hive| registry::read_string(hive, &key, None))
+        .map(|value| registry_path(&value))
+        .find(|path| path.is_file())
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Clone)]
+struct UninstallEntry {
+    install_location: Option<PathBuf>,
+    display_icon: Option<PathBuf>,
+}
+
+/// 卸载信息在一次 IDE 扫描中会被多次查询，短时间内复用
+#[cfg(target_os = "windows")]
+const UNINSTALL_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+#[cfg(target_os = "windows")]
+type UninstallCache = Option<(std::time::Instant, Vec<UninstallEntry>)>;
+
+#[cfg(target_os = "windows")]
+static UNINSTALL_CACHE: std::sync::Mutex<UninstallCache> = std::sync::Mutex::new(None);
+
+/// 读取 Uninstall 键下各软件的安装目录与图标路径，包括 32 位程序的 WOW6432Node
+#[cfg(target_os = "windows")]
+fn uninstall_entries() -> Vec<UninstallEntry> {
+    let mut cache = UNINSTALL_CACHE
+        .lock()
+        .expect("uninstall cache lock poisoned");
+    if let Some((at, entries)) = cache.as_ref() {
+        if at.elapsed() < UNINSTALL_CACHE_TTL {
+            return entries.clone();
+        }
+    }
+    let roots = [
+        (
+            registry::HKEY_CURRENT_USER,
+            r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+        ),
+        (
+            registry::HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+        ),
+        (
+            registry::HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+        ),
+    ];
+    let entries: Vec<UninstallEntry> = roots
+        .into_iter()
+        .flat_map(|(hive, root)| {
+            registry::subkeys(hive, root)
+                .into_iter()
+                .map(move |name| (hive, format!(r"{root}\{name}")))
+        })
+        .map(|(hive, key)| UninstallEntry {
+            install_location: registry::read_string(hive, &key, Some("InstallLocation"))
+                .map(|v| registry_path(&v)),
+            display_icon: registry::read_string(hive, &key, Some("DisplayIcon"))
+                .map(|v| registry_path(&v)),
+        })
+        .collect();
+    *cache = Some((std::time::Instant::now(), entries.clone()));
+    entries
+}
+
+/// 安装目录或其 bin 子目录下有该程序，或图标就是该程序（JetBrains、Sublime Text、Notepad++ 等）
+#[cfg(target_os = "windows")]
+fn find_in_uninstall(exe: &str) -> Option<PathBuf> {
+    uninstall_entries().into_iter().find_map(|entry| {
+        let icon = entry.display_icon.filter(|icon| {
+            icon.file_name()
+                .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case(exe))
+        });
+        entry
+            .install_location
+            .into_iter()
+            .flat_map(|dir| [dir.join(exe), dir.join("bin").join(exe)])
+            .chain(icon)
+            .find(|path| path.is_file())
+    })
+}
@@ -0,0 +1,271 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::DateTime;
+use serde::Serialize;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::{now_iso, path_key, persist, trash, tray, AppState, IdeConfig, Project};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum IssueKind {
+    DuplicateId,
+    DuplicatePath,
+    /// 引用了已不存在的 IDE（首选、管理员启动、启动步骤、参数预设或上次使用）
+    DanglingIde,
+    InvalidDisplayOrder,
+    InvalidTimestamp,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreIssue {
+    kind: IssueKind,
+    project_id: String,
+    project_name: String,
+    detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreReport {
+    /// 修复时为修复后仍然存在的问题
+    issues: Vec<StoreIssue>,
+    /// 已修复的问题数
+    repaired: usize,
+}
+
+fn issue(kind: IssueKind, project: &Project, detail: String) -> StoreIssue {
+    StoreIssue {
+        kind,
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        detail,
+    }
+}
+
+fn valid_timestamp(value: &str) -> bool {
+    DateTime::parse_from_rfc3339(value).is_ok()
+}
+
+/// 项目中引用的 IDE id，包括首选、管理员启动、启动步骤、参数预设与上次使用的 IDE
+fn referenced_ides(project: &Project) -> Vec<&str> {
+    let metadata = &project.metadata;
+    let mut ids: Vec<&str> = metadata
+        .ide_preferences
+        .iter()
+        .chain(&metadata.elevated_ides)
+        .chain(metadata.launch_steps.iter().map(|s| &s.ide_id))
+        .chain(metadata.ide_arg_presets.keys())
+        .chain(&metadata.last_used_ide)
+        .map(String::as_str)
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+fn check(projects: &[Project], ides: &[IdeConfig]) -> Vec<StoreIssue> {
+    let ide_ids: HashSet<&str> = ides.iter().map(|i| i.id.as_str()).collect();
+    let mut issues = vec![];
+    let mut ids = HashSet::new();
+    let mut paths: HashMap<String, &Project> = HashMap::new();
+    let mut orders = HashSet::new();
+
+    for project in projects {
+        if !ids.insert(project.id.as_str()) {
+            issues.push(issue(
+                IssueKind::DuplicateId,
+                project,
+                format!("id 重复: {}", project.id),
+            ));
+        }
+        match paths.get(&path_key(&project.path)) {
+            Some(first) => issues.push(issue(
+                IssueKind::DuplicatePath,
+                project,
+                format!("与项目「{}」的路径相同: {}", first.name, project.path),
+            )),
+            None => {
+                paths.insert(path_key(&project.path), project);
+            }
+        }
+        for ide in referenced_ides(project) {
+            if !ide_ids.contains(ide) {
+                issues.push(issue(
+                    IssueKind::DanglingIde,
+                    project,
+                    format!("引用了不存在的 IDE: {ide}"),
+                ));
+            }
+        }
+        if project.display_order <= 0 || !orders.insert(project.display_order) {
+            issues.push(issue(
+                IssueKind::InvalidDisplayOrder,
+                project,
+                format!("排序值无效或重复: {}", project.display_order),
+            ));
+        }
+        let timestamps = [
+            ("createdAt", Some(&project.created_at)),
+            ("lastOpened", project.last_opened.as_ref()),
+            ("lastModified", project.last_modified.as_ref()),
+        ];
+        for (field, value) in timestamps {
+            if let Some(value) = value.filter(|v| !valid_timestamp(v)) {
+                issues.push(issue(
+                    IssueKind::InvalidTimestamp,
+                    project,
+                    format!("{field} 不是有效的时间: {value}"),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// 路径重复的项目合并到第一个：标签、别名与收藏并入，其余移入回收站以便恢复
+fn merge_duplicate_paths(state: &AppState, projects: &mut Vec<Project>) {
+    let mut first_index: HashMap<String, usize> = HashMap::new();
+    let mut duplicates = vec![];
+    for (idx, project) in projects.iter().enumerate() {
+        let key = path_key(&project.path);
+        match first_index.get(&key) {
+            Some(first) => duplicates.push((*first, idx)),
+            None => {
+                first_index.insert(key, idx);
+            }
+        }
+    }
+    for (first, duplicate) in &duplicates {
+        let (tags, aliases, favorite) = {
+            let dup = &projects[*duplicate];
+            (dup.tags.clone(), dup.aliases.clone(), dup.favorite)
+        };
+        let kept = &mut projects[*first];
+        for tag in tags {
+            if !kept.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                kept.tags.push(tag);
+            }
+        }
+        for alias in aliases {
+            if !kept.aliases.iter().any(|a| a.eq_ignore_ascii_case(&alias)) {
+                kept.aliases.push(alias);
+            }
+        }
+        kept.favorite |= favorite;
+    }
+    let mut removed: Vec<usize> = duplicates.into_iter().map(|(_, dup)| dup).collect();
+    removed.sort_unstable();
+    for idx in removed.into_iter().rev() {
+        trash::move_to_trash(state, projects.remove(idx));
+    }
+}
+
+fn repair(state: &AppState, projects: &mut Vec<Project>, ides: &[IdeConfig]) {
+    let ide_ids: HashSet<&str> = ides.iter().map(|i| i.id.as_str()).collect();
+    let mut ids = HashSet::new();
+    for project in projects.iter_mut() {
+        if !ids.insert(project.id.clone()) {
+            project.id = Uuid::new_v4().to_string();
+            ids.insert(project.id.clone());
+        }
+        let metadata = &mut project.metadata;
+        metadata
+            .ide_preferences
+            .retain(|id| ide_ids.contains(id.as_str()));
+        metadata
+            .elevated_ides
+            .retain(|id| ide_ids.contains(id.as_str()));
+        metadata
+            .launch_steps
+            .retain(|s| ide_ids.contains(s.ide_id.as_str()));
+        metadata
+            .ide_arg_presets
+            .retain(|id, _| ide_ids.contains(id.as_str()));
+        if metadata
+            .last_used_ide
+            .as_deref()
+            .is_some_and(|id| !ide_ids.contains(id))
+        {
+            metadata.last_used_ide = None;
+        }
+
+        if !valid_timestamp(&project.created_at) {
+            project.created_at = project
+                .last_opened
+                .clone()
+                .filter(|v| valid_timestamp(v))
+                .unwrap_or_else(now_iso);
+        }
+        if project
+            .last_opened
+            .as_deref()
+            .is_some_and(|v| !valid_timestamp(v))
+        {
+            project.last_opened = None;
+        }
+        if project
+            .last_modified
+            .as_deref()
+            .is_some_and(|v| !valid_timestamp(v))
+        {
+            project.last_modified = None;
+        }
+    }
+    merge_duplicate_paths(state, projects);
+
+    // 保持现有的相对顺序重新编号，无效的排在最后
+    let mut order: Vec<(bool, i64, usize)> = projects
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| (p.display_order <= 0, p.display_order, idx))
+        .collect();
+    order.sort_unstable();
+    let valid = order.windows(2).all(|w| w[0].1 != w[1].1) && order.iter().all(|o| !o.0);
+    if !valid {
+        for (rank, (_, _, idx)) in order.into_iter().enumerate() {
+            projects[idx].display_order = rank as i64 + 1;
+        }
+    }
+}
+
+/// 检查项目数据的一致性；repair 为 true 时自动修复，修复前的数据可以撤销恢复
+#[tauri::command]
+pub fn verify_store(
+    repair: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> StoreReport {
+    let issues = {
+        let projects = state.store.projects();
+        let ides = state.store.ides();
+        check(&projects, &ides)
+    };
+    if issues.is_empty() || !repair.unwrap_or(false) {
+        return StoreReport {
+            issues,
+            repaired: 0,
+        };
+    }
+
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let ides = state.store.ides();
+    self::repair(&state, &mut projects, &ides);
+    let remaining = check(&projects, &ides);
+    persist::mark_dirty();
+    drop(ides);
+    drop(projects);
+    state.undo.push("修复项目数据", snapshot, &state.store);
+    tray::refresh_tray_menu(&app);
+    tracing::info!(
+        found = issues.len(),
+        remaining = remaining.len(),
+        "已修复项目数据"
+    );
+    StoreReport {
+        repaired: issues.len().saturating_sub(remaining.len()),
+        issues: remaining,
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{AppState, Project};
+
+/// 每个项目保留的快照数量，每天最多一条
+const MAX_SNAPSHOTS: usize = 180;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageSnapshot {
+    /// 统计日期，如 `2024-05-06`
+    date: String,
+    total_lines: u64,
+    /// 语言 → 代码行数
+    languages: BTreeMap<String, u64>,
+}
+
+/// 各项目的语言统计历史，单独保存在 language-history.json 中，不随项目列表加载与同步
+pub struct LanguageHistory {
+    projects: Mutex<HashMap<String, Vec<LanguageSnapshot>>>,
+    path: PathBuf,
+}
+
+pub fn history_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("language-history.json")
+}
+
+impl LanguageHistory {
+    pub fn load(store_file_path: &Path) -> Self {
+        let path = history_path(store_file_path);
+        let projects = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            projects: Mutex::new(projects),
+            path,
+        }
+    }
+
+    /// 把项目当前的语言统计追加到历史中；同一天多次统计只保留最后一次。
+    /// 只修改内存，调用方释放 store 锁后再调用 save 写入文件
+    pub fn record(&self, project: &Project) {
+        let Some(stats) = &project.metadata.language_stats else {
+            return;
+        };
+        let date = stats
+            .scanned_at
+            .get(..10)
+            .unwrap_or(&stats.scanned_at)
+            .to_string();
+        let snapshot = LanguageSnapshot {
+            date,
+            total_lines: stats.total_lines,
+            languages: stats
+                .languages
+                .iter()
+                .map(|entry| (entry.language.clone(), entry.lines))
+                .collect(),
+        };
+        let mut projects = self
+            .projects
+            .lock()
+            .expect("language history lock poisoned");
+        let history = projects.entry(project.id.clone()).or_default();
+        match history.last_mut() {
+            Some(last) if last.date == snapshot.date => *last = snapshot,
+            _ => history.push(snapshot),
+        }
+        if history.len() > MAX_SNAPSHOTS {
+            history.drain(..history.len() - MAX_SNAPSHOTS);
+        }
+    }
+
+    pub fn remove_project(&self, project_id: &str) {
+        let mut projects = self
+            .projects
+            .lock()
+            .expect("language history lock poisoned");
+        if projects.remove(project_id).is_some() {
+            write_history(&self.path, &projects);
+        }
+    }
+
+    pub fn save(&self) {
+        let projects = self
+            .projects
+            .lock()
+            .expect("language history lock poisoned");
+        write_history(&self.path, &projects);
+    }
+}
+
+fn write_history(path: &Path, projects: &HashMap<String, Vec<LanguageSnapshot>>) {
+    let result = serde_json::to_string(projects)
+        .map_err(|e| e.to_string())
+        .and_then(|content| fs::write(path, content).map_err(|e| e.to_string()));
+    if let Err(err) = result {
+        tracing::warn!(error = %err, "保存语言统计历史失败");
+    }
+}
+
+/// 按日期排列的语言统计快照，用于绘制代码量的变化
+#[tauri::command]
+pub fn get_language_stats_history(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<LanguageSnapshot>, String> {
+    if !state.store.projects().iter().any(|p| p.id == project_id) {
+        return Err("项目不存在".to_string());
+    }
+    Ok(state
+        .language_history
+        .projects
+        .lock()
+        .expect("language history lock poisoned")
+        .get(&project_id)
+        .cloned()
+        .unwrap_or_default())
+}
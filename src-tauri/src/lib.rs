@@ -1,14 +1,84 @@
+mod analysis;
+mod arg_presets;
+mod autostart;
+mod background;
+mod branches;
+mod ci;
+mod cleanup;
+mod clipboard;
+mod compose;
+mod content_search;
+mod data_location;
+mod deeplink;
+mod description;
+mod detection;
+mod dev_server;
+mod diagnostics;
+mod elevate;
+mod envfile;
+mod favicon;
+mod file_search;
+mod file_tree;
+mod fulltext;
+mod git_stats;
+mod health;
+mod history;
+mod hotkey;
+mod ide_health;
+mod ide_merge;
+mod installers;
+mod integrity;
+mod language_history;
+mod logging;
+mod manifest;
+mod modules;
+mod mtime;
+mod multiplexer;
+mod multiroot;
+mod network;
+mod persist;
+mod portable;
+mod process;
+mod project_icon;
+mod prune;
+mod readme;
+mod relocate;
+mod report;
+mod rescan;
+mod search;
+mod secrets;
+mod settings;
+mod smart_tags;
+mod store_events;
+mod store_lock;
+mod store_watch;
+mod svg_icon;
+mod sync;
+mod tags;
+mod tasks;
+mod templates;
+mod terminal;
+mod timetrack;
+mod todos;
+mod trash;
 mod tray;
+mod undo;
+mod updater;
+mod version_manager;
+mod vscode;
+mod window_state;
+mod workspace;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env, fs,
     path::{Path, PathBuf},
-    process::Command,
-    sync::Mutex,
-    time::Duration,
+    process::{Child, Command},
+    sync::{Mutex, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::{Duration, SystemTime},
 };
 
+use background::{TaskContext, TaskKind};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tauri::{Manager, State};
@@ -26,6 +96,7 @@ use windows::{
     Win32::UI::WindowsAndMessaging::{DestroyIcon, HICON},
 };
 
+/// 较新版本新增的类型（例如同步过来的数据）读取为 Generic
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "PascalCase")]
 enum ProjectType {
@@ -35,6 +106,16 @@ enum ProjectType {
     Java,
     Go,
     Dotnet,
+    Flutter,
+    Dart,
+    Php,
+    Ruby,
+    Elixir,
+    Cpp,
+    Zig,
+    Unity,
+    Terraform,
+    #[serde(other)]
     Generic,
 }
 
@@ -44,23 +125,106 @@ struct ProjectMetadata {
     ide_preferences: Vec<String>,
     git_url: Option<String>,
     description: Option<String>,
+    /// 自动填写的描述的来源文件，如 `Cargo.toml`、`README.md`；手动填写的描述为空
+    #[serde(default)]
+    description_source: Option<String>,
     #[serde(default)]
     language_stats: Option<LanguageStats>,
+    #[serde(default)]
+    compose_file: Option<String>,
+    /// 扫描时自动识别的标签（如 `has-docker`），与用户标签分开保存
+    #[serde(default)]
+    system_tags: Vec<String>,
+    /// 启动命令行工具与终端时激活项目声明的 node/python 版本
+    #[serde(default)]
+    activate_version_managers: bool,
+    /// 启动 IDE、终端与任务时加载的环境变量文件，相对路径相对于项目根目录
+    #[serde(default)]
+    env_file: Option<String>,
+    /// 项目根目录下的 `.code-workspace` 文件名
+    #[serde(default)]
+    workspace_file: Option<String>,
+    /// 用 VS Code 系列 IDE 打开时传入工作区文件而不是目录
+    #[serde(default)]
+    open_workspace_file: bool,
+    /// 同时启动多个首选 IDE 时各自的延迟与等待设置，按 ide_preferences 的顺序依次启动
+    #[serde(default)]
+    launch_steps: Vec<LaunchStep>,
+    /// 在该项目中以管理员身份启动的 IDE
+    #[serde(default)]
+    elevated_ides: Vec<String>,
+    /// 用终端类 IDE 打开时创建或连接的 tmux/zellij 会话
+    #[serde(default)]
+    multiplexer: Option<multiplexer::MultiplexerSettings>,
+    /// 最近一次指定 IDE 打开该项目时使用的 IDE
+    #[serde(default)]
+    last_used_ide: Option<String>,
+    /// IDE id → 用该 IDE 打开时使用的参数预设
+    #[serde(default)]
+    ide_arg_presets: BTreeMap<String, String>,
+    /// 自定义识别规则给出的类型名（内置类型中没有的，如 `Deno`），此时 project_type 为 Generic
+    #[serde(default)]
+    custom_type: Option<String>,
+    /// 检测不到正在运行的开发服务器时在浏览器中打开的地址
+    #[serde(default)]
+    dev_url: Option<String>,
+    /// 受管开发服务器的启动命令，如 `npm run dev`
+    #[serde(default)]
+    dev_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchStep {
+    ide_id: String,
+    /// 启动前等待的毫秒数
+    #[serde(default)]
+    delay_ms: u64,
+    /// 等上一个 IDE 就绪（启动器进程退出或超时）后再启动
+    #[serde(default)]
+    wait_for_previous: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct LanguageStats {
+    /// 代码行数（不含注释与空行）
     total_lines: u64,
+    #[serde(default)]
+    total_comments: u64,
+    #[serde(default)]
+    total_blanks: u64,
     languages: Vec<LanguageEntry>,
     scanned_at: String,
+    /// 按目录分组的统计，未开启分组时为空
+    #[serde(default)]
+    directories: Option<Vec<DirectoryStats>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DirectoryStats {
+    /// 相对于项目根目录，使用 `/` 分隔；项目根目录下的文件为空字符串
+    path: String,
+    lines: u64,
+    comments: u64,
+    blanks: u64,
+    files: u32,
+    /// 占整个项目代码行数的百分比
+    percentage: f64,
+    languages: Vec<LanguageEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct LanguageEntry {
     language: String,
+    /// 代码行数（不含注释与空行）
     lines: u64,
+    #[serde(default)]
+    comments: u64,
+    #[serde(default)]
+    blanks: u64,
     files: u32,
     percentage: f64,
 }
@@ -71,15 +235,29 @@ struct Project {
     id: String,
     name: String,
     path: String,
+    /// 多根项目中 path 之外的其它根目录，如前后端分离的两个仓库
+    #[serde(default)]
+    paths: Vec<String>,
     project_type: ProjectType,
     favorite: bool,
     tags: Vec<String>,
+    /// 快速启动用的简短别名，如 `api`，搜索与链接中可代替名称
+    #[serde(default)]
+    aliases: Vec<String>,
     last_opened: Option<String>,
     #[serde(default)]
     last_modified: Option<String>,
     created_at: String,
     #[serde(default)]
     display_order: i64,
+    /// 用户自定义图标（data URL），为空时由 get_project_icon 自动识别
+    #[serde(default)]
+    icon: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    /// 项目目录已不存在
+    #[serde(default)]
+    missing: bool,
     metadata: ProjectMetadata,
 }
 
@@ -103,6 +281,14 @@ struct IdeConfig {
     category: IdeCategory,
     priority: i32,
     auto_detected: bool,
+    #[serde(default)]
+    multi_root: multiroot::MultiRootMode,
+    /// 始终以管理员身份启动
+    #[serde(default)]
+    elevated: bool,
+    /// 官网地址，内置列表中没有的 IDE 从该站点获取图标
+    #[serde(default)]
+    website: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -110,14 +296,150 @@ struct IdeConfig {
 struct AppStore {
     projects: Vec<Project>,
     ides: Vec<IdeConfig>,
+    /// 软删除的项目，可恢复
+    #[serde(default)]
+    trash: Vec<trash::TrashedProject>,
+    /// 标签的颜色、图标等定义，项目仍按名称引用标签
+    #[serde(default)]
+    tags: Vec<tags::TagDefinition>,
+    /// 每次写入加一，多个实例同时写入时用于识别并发修改
+    #[serde(default)]
+    revision: u64,
+}
+
+/// 项目、IDE、回收站与标签定义分别加读写锁，长时间的读取不再阻塞无关的命令。
+/// 需要同时持有多个锁时按 projects → ides → trash → tags 的顺序获取；修改后调用 persist::mark_dirty
+struct StoreState {
+    projects: RwLock<Vec<Project>>,
+    ides: RwLock<Vec<IdeConfig>>,
+    trash: RwLock<Vec<trash::TrashedProject>>,
+    tags: RwLock<Vec<tags::TagDefinition>>,
+}
+
+impl StoreState {
+    fn new(store: AppStore) -> Self {
+        Self {
+            projects: RwLock::new(store.projects),
+            ides: RwLock::new(store.ides),
+            trash: RwLock::new(store.trash),
+            tags: RwLock::new(store.tags),
+        }
+    }
+
+    fn projects(&self) -> RwLockReadGuard<'_, Vec<Project>> {
+        self.projects.read().expect("projects lock poisoned")
+    }
+
+    fn projects_mut(&self) -> RwLockWriteGuard<'_, Vec<Project>> {
+        self.projects.write().expect("projects lock poisoned")
+    }
+
+    fn ides(&self) -> RwLockReadGuard<'_, Vec<IdeConfig>> {
+        self.ides.read().expect("ides lock poisoned")
+    }
+
+    fn ides_mut(&self) -> RwLockWriteGuard<'_, Vec<IdeConfig>> {
+        self.ides.write().expect("ides lock poisoned")
+    }
+
+    fn trash(&self) -> RwLockReadGuard<'_, Vec<trash::TrashedProject>> {
+        self.trash.read().expect("trash lock poisoned")
+    }
+
+    fn trash_mut(&self) -> RwLockWriteGuard<'_, Vec<trash::TrashedProject>> {
+        self.trash.write().expect("trash lock poisoned")
+    }
+
+    fn tags(&self) -> RwLockReadGuard<'_, Vec<tags::TagDefinition>> {
+        self.tags.read().expect("tags lock poisoned")
+    }
+
+    fn tags_mut(&self) -> RwLockWriteGuard<'_, Vec<tags::TagDefinition>> {
+        self.tags.write().expect("tags lock poisoned")
+    }
+
+    /// 完整数据的副本，用于保存与撤销。调用方不能持有任何写锁
+    fn snapshot(&self) -> AppStore {
+        let projects = self.projects().clone();
+        let ides = self.ides().clone();
+        let trash = self.trash().clone();
+        let tags = self.tags().clone();
+        AppStore {
+            projects,
+            ides,
+            trash,
+            tags,
+            revision: 0,
+        }
+    }
+
+    /// 同时持有全部写锁修改完整数据，保存时合并其它实例的修改用
+    fn update_all<R>(&self, f: impl FnOnce(&mut AppStore) -> R) -> R {
+        let mut projects = self.projects_mut();
+        let mut ides = self.ides_mut();
+        let mut trash = self.trash_mut();
+        let mut tags = self.tags_mut();
+        let mut store = AppStore {
+            projects: std::mem::take(&mut *projects),
+            ides: std::mem::take(&mut *ides),
+            trash: std::mem::take(&mut *trash),
+            tags: std::mem::take(&mut *tags),
+            revision: 0,
+        };
+        let result = f(&mut store);
+        *projects = store.projects;
+        *ides = store.ides;
+        *trash = store.trash;
+        *tags = store.tags;
+        result
+    }
+
+    /// 外部修改后重新加载时整体替换，返回是否替换。持有全部写锁时才检查未保存的修改，
+    /// 修改在持有写锁时调用 persist::mark_dirty，检查与替换之间不会插入新的修改
+    fn replace_if_clean(&self, store: AppStore) -> bool {
+        let mut projects = self.projects_mut();
+        let mut ides = self.ides_mut();
+        let mut trash = self.trash_mut();
+        let mut tags = self.tags_mut();
+        if persist::is_dirty() {
+            return false;
+        }
+        *projects = store.projects;
+        *ides = store.ides;
+        *trash = store.trash;
+        *tags = store.tags;
+        true
+    }
 }
 
 struct AppState {
     file_path: PathBuf,
-    store: Mutex<AppStore>,
+    store: StoreState,
     last_active_window: Mutex<Option<String>>,
+    processes: process::ProcessRegistry,
+    settings: Mutex<settings::AppSettings>,
+    history: Mutex<history::LaunchHistory>,
+    search_index: fulltext::IndexState,
+    hotkey: hotkey::HotkeyState,
+    sessions: timetrack::SessionTracker,
+    language_history: language_history::LanguageHistory,
+    undo: undo::UndoStack,
+    background: background::TaskQueue,
 }
 
+const DEEP_LINK_SCHEME: &str = "dev-boom";
+/// 图标提取算法变更时递增，旧版本提取的图标会在 get_ides 时重新提取
+const ICON_EXTRACTION_VERSION: &str = "v4";
+/// 下载的图标缓存过期时间，过期后重新下载
+const ICON_CACHE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const ICON_CACHE_EXTENSIONS: [&str; 5] = ["svg", "png", "ico", "webp", "jpg"];
+/// 启动 IDE 后观察的时长，期间以非零码退出视为启动失败
+const LAUNCH_CHECK_WINDOW: Duration = Duration::from_secs(2);
+/// 等待上一个 IDE 就绪的最长时间
+const LAUNCH_READY_TIMEOUT: Duration = Duration::from_secs(15);
+/// 单步启动延迟上限
+const MAX_LAUNCH_DELAY_MS: u64 = 60_000;
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct NewProjectInput {
@@ -139,6 +461,8 @@ struct NewIdeInput {
     icon: Option<String>,
     category: IdeCategory,
     priority: Option<i32>,
+    multi_root: Option<multiroot::MultiRootMode>,
+    website: Option<String>,
 }
 
 fn now_iso() -> String {
@@ -168,202 +492,52 @@ fn normalize_windows_path_for_ui(path: &str) -> String {
     path.to_string()
 }
 
-#[derive(Debug, Clone)]
+/// 把 8.3 短文件名（如 `PROGRA~1`）展开为长文件名，路径不存在时返回 None
+#[cfg(target_os = "windows")]
+fn long_path_name(path: &str) -> Option<String> {
+    use windows::Win32::Storage::FileSystem::GetLongPathNameW;
+    let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buf = vec![0u16; 32768];
+    let len = unsafe { GetLongPathNameW(PCWSTR(wide.as_ptr()), Some(&mut buf)) } as usize;
+    (len > 0 && len < buf.len()).then(|| String::from_utf16_lossy(&buf[..len]))
+}
+
+/// 比较路径是否相同时使用的键：Windows 上不区分大小写与分隔符，并展开短文件名
+fn path_key(path: &str) -> String {
+    #[cfg(target_os = "windows")]
+    let long = path.contains('~').then(|| long_path_name(path)).flatten();
+    #[cfg(not(target_os = "windows"))]
+    let long: Option<String> = None;
+    let path = long.as_deref().unwrap_or(path);
+    let key = path.replace('\\', "/").trim_end_matches('/').to_string();
+    if cfg!(target_os = "windows") {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// 内置 IDE 列表来自 known_ides.json，新增 IDE 只需修改该文件
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct IdeDefinition {
-    id: &'static str,
-    name: &'static str,
-    executable_name: &'static str,
-    paths: Vec<&'static str>,
-    args_template: &'static str,
+    id: String,
+    name: String,
+    executable_name: String,
+    /// Windows 上的常见安装路径，支持 `%LOCALAPPDATA%` 等环境变量
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    args_template: String,
     category: IdeCategory,
     priority: i32,
 }
 
-fn get_known_ides() -> Vec<IdeDefinition> {
-    vec![
-        IdeDefinition {
-            id: "vscode",
-            name: "VSCode",
-            executable_name: "Code.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\Microsoft VS Code\\Code.exe",
-                "%USERPROFILE%\\AppData\\Local\\Programs\\Microsoft VS Code\\Code.exe",
-                "C:\\Program Files\\Microsoft VS Code\\Code.exe",
-                "C:\\Program Files (x86)\\Microsoft VS Code\\Code.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 100,
-        },
-        IdeDefinition {
-            id: "cursor",
-            name: "Cursor",
-            executable_name: "cursor.exe",
-            paths: vec![
-                "%USERPROFILE%\\AppData\\Local\\cursor\\cursor.exe",
-                "%LOCALAPPDATA%\\Programs\\cursor\\cursor.exe",
-                "C:\\Program Files\\cursor\\cursor.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 110,
-        },
-        IdeDefinition {
-            id: "webstorm",
-            name: "WebStorm",
-            executable_name: "webstorm64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\WebStorm\\bin\\webstorm64.exe",
-                "C:\\Program Files\\JetBrains\\WebStorm\\bin\\webstorm64.exe",
-                "C:\\Program Files (x86)\\JetBrains\\WebStorm\\bin\\webstorm64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 120,
-        },
-        IdeDefinition {
-            id: "intellij",
-            name: "IntelliJ IDEA",
-            executable_name: "idea64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\IntelliJ IDEA\\bin\\idea64.exe",
-                "C:\\Program Files\\JetBrains\\IntelliJ IDEA\\bin\\idea64.exe",
-                "C:\\Program Files (x86)\\JetBrains\\IntelliJ IDEA\\bin\\idea64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 121,
-        },
-        IdeDefinition {
-            id: "pycharm",
-            name: "PyCharm",
-            executable_name: "pycharm64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\PyCharm\\bin\\pycharm64.exe",
-                "C:\\Program Files\\JetBrains\\PyCharm\\bin\\pycharm64.exe",
-                "C:\\Program Files (x86)\\JetBrains\\PyCharm\\bin\\pycharm64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 122,
-        },
-        IdeDefinition {
-            id: "clion",
-            name: "CLion",
-            executable_name: "clion64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\CLion\\bin\\clion64.exe",
-                "C:\\Program Files\\JetBrains\\CLion\\bin\\clion64.exe",
-                "C:\\Program Files (x86)\\JetBrains\\CLion\\bin\\clion64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 123,
-        },
-        IdeDefinition {
-            id: "goland",
-            name: "GoLand",
-            executable_name: "goland64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\GoLand\\bin\\goland64.exe",
-                "C:\\Program Files\\JetBrains\\GoLand\\bin\\goland64.exe",
-                "C:\\Program Files (x86)\\JetBrains\\GoLand\\bin\\goland64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 124,
-        },
-        IdeDefinition {
-            id: "rider",
-            name: "Rider",
-            executable_name: "rider64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\JetBrains\\Rider\\bin\\rider64.exe",
-                "C:\\Program Files\\JetBrains\\Rider\\bin\\rider64.exe",
-                "C:\\Program Files (x86)\\JetBrains\\Rider\\bin\\rider64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 125,
-        },
-        IdeDefinition {
-            id: "fleet",
-            name: "Fleet",
-            executable_name: "fleet.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Programs\\Fleet\\bin\\fleet.exe",
-                "C:\\Program Files\\JetBrains\\Fleet\\bin\\fleet.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 126,
-        },
-        IdeDefinition {
-            id: "android-studio",
-            name: "Android Studio",
-            executable_name: "studio64.exe",
-            paths: vec![
-                "%LOCALAPPDATA%\\Android\\android-studio\\bin\\studio64.exe",
-                "C:\\Program Files\\Android\\Android Studio\\bin\\studio64.exe",
-                "C:\\Program Files (x86)\\Android\\Android Studio\\bin\\studio64.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Gui,
-            priority: 127,
-        },
-        IdeDefinition {
-            id: "neovim",
-            name: "Neovim",
-            executable_name: "nvim",
-            paths: vec![
-                "%LOCALAPPDATA%\\nvim\\bin\\nvim.exe",
-                "C:\\Program Files\\Neovim\\bin\\nvim.exe",
-                "C:\\tools\\neovim\\bin\\nvim.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Cli,
-            priority: 200,
-        },
-        IdeDefinition {
-            id: "vim",
-            name: "Vim",
-            executable_name: "vim",
-            paths: vec![
-                "C:\\Program Files\\Vim\\vim90\\vim.exe",
-                "C:\\Program Files (x86)\\Vim\\vim90\\vim.exe",
-            ],
-            args_template: "{projectPath}",
-            category: IdeCategory::Cli,
-            priority: 201,
-        },
-        IdeDefinition {
-            id: "claude",
-            name: "Claude CLI",
-            executable_name: "claude",
-            paths: vec![],
-            args_template: "",
-            category: IdeCategory::Cli,
-            priority: 210,
-        },
-        IdeDefinition {
-            id: "codex",
-            name: "Codex CLI",
-            executable_name: "codex",
-            paths: vec![],
-            args_template: "",
-            category: IdeCategory::Cli,
-            priority: 211,
-        },
-        IdeDefinition {
-            id: "opencode",
-            name: "OpenCode CLI",
-            executable_name: "opencode",
-            paths: vec![],
-            args_template: "",
-            category: IdeCategory::Cli,
-            priority: 212,
-        },
-    ]
+fn get_known_ides() -> &'static [IdeDefinition] {
+    static KNOWN_IDES: OnceLock<Vec<IdeDefinition>> = OnceLock::new();
+    KNOWN_IDES.get_or_init(|| {
+        serde_json::from_str(include_str!("known_ides.json")).expect("known_ides.json 格式错误")
+    })
 }
 
 fn expand_env_path(path: &str) -> Option<String> {
@@ -389,7 +563,7 @@ fn expand_env_path(path: &str) -> Option<String> {
     Some(result)
 }
 
-fn find_executable_in_known_paths(paths: &[&str]) -> Option<PathBuf> {
+fn find_executable_in_known_paths(paths: &[String]) -> Option<PathBuf> {
     paths
         .iter()
         .filter_map(|p| expand_env_path(p))
@@ -447,9 +621,11 @@ fn find_executable_in_path(command_name: &str) -> Option<PathBuf> {
         .map(PathBuf::from)
 }
 
+/// 查找顺序：固定路径、包管理器安装位置与 App Paths、PATH
 fn resolve_ide_executable(ide_def: &IdeDefinition) -> Option<PathBuf> {
     find_executable_in_known_paths(&ide_def.paths)
-        .or_else(|| find_executable_in_path(ide_def.executable_name))
+        .or_else(|| installers::find_installed(&ide_def.executable_name))
+        .or_else(|| find_executable_in_path(&ide_def.executable_name))
 }
 
 #[cfg(target_os = "windows")]
@@ -518,7 +694,9 @@ fn extract_icon_from_exe(exe_path: &Path) -> Option<String> {
 
         let icon = extract_hicon_to_png(hicon)?;
         let _ = DestroyIcon(hicon);
-        Some(format!("data:image/png;extraction=v3;base64,{}", icon))
+        Some(format!(
+            "data:image/png;extraction={ICON_EXTRACTION_VERSION};base64,{icon}"
+        ))
     }
 }
 
@@ -806,12 +984,14 @@ fn build_alpha_from_icon_mask(hicon: HICON, width: i32, height: i32) -> Option<V
 }
 
 #[cfg(target_os = "windows")]
-fn is_cached_v3_icon(icon: &str) -> bool {
-    icon.starts_with("data:image/png;extraction=v3;base64,")
+fn is_current_extraction_icon(icon: &str) -> bool {
+    icon.starts_with(&format!(
+        "data:image/png;extraction={ICON_EXTRACTION_VERSION};base64,"
+    ))
 }
 
 #[cfg(not(target_os = "windows"))]
-fn is_cached_v3_icon(_icon: &str) -> bool {
+fn is_current_extraction_icon(_icon: &str) -> bool {
     true
 }
 
@@ -831,6 +1011,9 @@ fn default_ides() -> Vec<IdeConfig> {
             category: IdeCategory::Gui,
             priority: 100,
             auto_detected: false,
+            multi_root: multiroot::MultiRootMode::Auto,
+            elevated: false,
+            website: None,
         },
         IdeConfig {
             id: "cursor".to_string(),
@@ -841,6 +1024,9 @@ fn default_ides() -> Vec<IdeConfig> {
             category: IdeCategory::Gui,
             priority: 110,
             auto_detected: false,
+            multi_root: multiroot::MultiRootMode::Auto,
+            elevated: false,
+            website: None,
         },
     ]
 }
@@ -918,16 +1104,46 @@ fn icon_data_url_from_user_file(path: &Path) -> Result<String, String> {
 }
 
 fn load_cached_ide_icon(store_file_path: &Path, ide_id: &str) -> Option<String> {
-    let cache_dir = ide_icon_cache_dir(store_file_path);
-    let candidates = [
-        cache_dir.join(format!("{ide_id}.svg")),
-        cache_dir.join(format!("{ide_id}.png")),
-        cache_dir.join(format!("{ide_id}.ico")),
-        cache_dir.join(format!("{ide_id}.webp")),
-        cache_dir.join(format!("{ide_id}.jpg")),
-    ];
+    load_cached_icon(&ide_icon_cache_dir(store_file_path), ide_id)
+}
+
+fn remove_cached_icon(cache_dir: &Path, cache_key: &str) {
+    for ext in ICON_CACHE_EXTENSIONS {
+        let _ = fs::remove_file(cache_dir.join(format!("{cache_key}.{ext}")));
+    }
+    let svg = cache_dir.join(format!("{cache_key}.svg"));
+    for size in svg_icon::SIZES {
+        let _ = fs::remove_file(svg_icon::rendition_path(&svg, size));
+    }
+}
+
+/// 未过期的缓存图标
+fn load_cached_icon(cache_dir: &Path, cache_key: &str) -> Option<String> {
+    load_cached_icon_within(cache_dir, cache_key, Some(ICON_CACHE_TTL))
+}
+
+/// 忽略过期时间，重新下载失败时作为兜底
+fn load_stale_cached_icon(cache_dir: &Path, cache_key: &str) -> Option<String> {
+    load_cached_icon_within(cache_dir, cache_key, None)
+}
 
-    for path in candidates {
+fn load_cached_icon_within(
+    cache_dir: &Path,
+    cache_key: &str,
+    max_age: Option<Duration>,
+) -> Option<String> {
+    for ext in ICON_CACHE_EXTENSIONS {
+        let path = cache_dir.join(format!("{cache_key}.{ext}"));
+        let expired = max_age.is_some_and(|max_age| {
+            fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| SystemTime::now().duration_since(t).ok())
+                .is_some_and(|age| age > max_age)
+        });
+        if expired {
+            continue;
+        }
         let bytes = match fs::read(&path) {
             Ok(v) => v,
             Err(_) => continue,
@@ -935,6 +1151,11 @@ fn load_cached_ide_icon(store_file_path: &Path, ide_id: &str) -> Option<String>
         if bytes.is_empty() {
             continue;
         }
+        if ext == "svg" && svg_icon::enabled() {
+            if let Some(icon) = svg_icon::cached_display_icon(&path, &bytes) {
+                return Some(icon);
+            }
+        }
         use base64::Engine;
         let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
         let mime = decode_ide_icon_cache_mime(&path);
@@ -1003,21 +1224,33 @@ fn guess_icon_ext_by_content_type(content_type: &str) -> &'static str {
 }
 
 fn download_and_cache_ide_icon(store_file_path: &Path, ide: &IdeConfig) -> Option<String> {
-    let urls = online_icon_urls_for_ide(ide);
+    let mut urls: Vec<String> = online_icon_urls_for_ide(ide)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if urls.is_empty() {
+        if let Some(website) = &ide.website {
+            urls = favicon::site_icon_urls(website);
+        }
+    }
+    fetch_icon_to_cache(&urls, &ide_icon_cache_dir(store_file_path), &ide.id)
+}
+
+/// 依次尝试下载图标，成功后写入 `cache_dir/{cache_key}.{ext}` 并返回 data URL
+fn fetch_icon_to_cache<S: AsRef<str>>(
+    urls: &[S],
+    cache_dir: &Path,
+    cache_key: &str,
+) -> Option<String> {
     if urls.is_empty() {
         return None;
     }
 
-    let cache_dir = ide_icon_cache_dir(store_file_path);
-    let _ = fs::create_dir_all(&cache_dir);
-    let client = reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(6))
-        .user_agent("dev-boom/0.1 ide-icon-fetch")
-        .build()
-        .ok()?;
+    let _ = fs::create_dir_all(cache_dir);
+    let client = network::client(Duration::from_secs(6), "dev-boom/0.1 ide-icon-fetch").ok()?;
 
     for url in urls {
-        let response = match client.get(url).send() {
+        let response = match client.get(url.as_ref()).send() {
             Ok(v) => v,
             Err(_) => continue,
         };
@@ -1042,8 +1275,13 @@ fn download_and_cache_ide_icon(store_file_path: &Path, ide: &IdeConfig) -> Optio
         }
 
         let ext = guess_icon_ext_by_content_type(&content_type);
-        let cache_path = cache_dir.join(format!("{}.{}", ide.id, ext));
+        let cache_path = cache_dir.join(format!("{}.{}", cache_key, ext));
         let _ = fs::write(&cache_path, &bytes);
+        if ext == "svg" && svg_icon::enabled() {
+            if let Some(icon) = svg_icon::cached_display_icon(&cache_path, &bytes) {
+                return Some(icon);
+            }
+        }
 
         use base64::Engine;
         let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
@@ -1054,6 +1292,7 @@ fn download_and_cache_ide_icon(store_file_path: &Path, ide: &IdeConfig) -> Optio
         ));
     }
 
+    tracing::warn!(cache_key, "所有图标地址均下载失败");
     None
 }
 
@@ -1073,6 +1312,7 @@ fn resolve_ide_icon(store_file_path: &Path, ide: &IdeConfig) -> Option<String> {
 
     load_cached_ide_icon(store_file_path, &ide.id)
         .or_else(|| download_and_cache_ide_icon(store_file_path, ide))
+        .or_else(|| load_stale_cached_icon(&ide_icon_cache_dir(store_file_path), &ide.id))
 }
 
 fn load_store(path: &Path) -> AppStore {
@@ -1080,6 +1320,9 @@ fn load_store(path: &Path) -> AppStore {
         return AppStore {
             projects: vec![],
             ides: default_ides(),
+            trash: vec![],
+            tags: vec![],
+            revision: 0,
         };
     }
 
@@ -1089,39 +1332,65 @@ fn load_store(path: &Path) -> AppStore {
             return AppStore {
                 projects: vec![],
                 ides: default_ides(),
+                trash: vec![],
+                tags: vec![],
+                revision: 0,
             };
         }
     };
 
-    match serde_json::from_str::<AppStore>(&content) {
-        Ok(mut store) => {
-            if store.ides.is_empty() {
-                store.ides = default_ides();
-            }
-            for (idx, project) in store.projects.iter_mut().enumerate() {
-                project.path = normalize_windows_path_for_ui(&project.path);
-                if project.display_order == 0 {
-                    project.display_order = idx as i64 + 1;
-                }
-            }
-            for ide in &mut store.ides {
-                ide.executable = normalize_windows_path_for_ui(&ide.executable);
-            }
-            store
-        }
-        Err(_) => AppStore {
+    match parse_store(&content) {
+        Some(store) => store,
+        None => AppStore {
             projects: vec![],
             ides: default_ides(),
+            trash: vec![],
+            tags: vec![],
+            revision: 0,
         },
     }
 }
 
+/// 解析 store.json 的内容并补全旧版本缺少的字段
+fn parse_store(content: &str) -> Option<AppStore> {
+    let mut store = serde_json::from_str::<AppStore>(content).ok()?;
+    if store.ides.is_empty() {
+        store.ides = default_ides();
+    }
+    for (idx, project) in store.projects.iter_mut().enumerate() {
+        project.path = normalize_windows_path_for_ui(&project.path);
+        if project.display_order == 0 {
+            project.display_order = idx as i64 + 1;
+        }
+    }
+    for ide in &mut store.ides {
+        ide.executable = normalize_windows_path_for_ui(&ide.executable);
+    }
+    tags::ensure_definitions(&store.projects, &mut store.tags);
+    Some(store)
+}
+
+/// 写入数据文件。主数据文件的修改通过 persist::mark_dirty 延迟批量写入
 fn save_store(path: &Path, store: &AppStore) -> Result<(), String> {
-    let content = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
-    fs::write(path, content).map_err(|e| e.to_string())
+    let content = persist::to_json(store).map_err(|e| e.to_string())?;
+    // 先写临时文件再替换，其它实例不会读到写了一半的文件
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, &content)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| {
+            tracing::error!(path = %path.display(), error = %e, "保存数据失败");
+            e.to_string()
+        })?;
+    store_watch::note_written(path, &content);
+    Ok(())
 }
 
 fn detect_project_type(path: &Path) -> ProjectType {
+    match detection::rule_type(path) {
+        Some(detection::RuleType::Builtin(project_type)) => return project_type,
+        Some(detection::RuleType::Custom(_)) => return ProjectType::Generic,
+        None => {}
+    }
     if path.join("Cargo.toml").exists() {
         return ProjectType::Rust;
     }
@@ -1137,7 +1406,47 @@ fn detect_project_type(path: &Path) -> ProjectType {
     if path.join("go.mod").exists() {
         return ProjectType::Go;
     }
-    let has_dotnet_project = fs::read_dir(path)
+    if path.join("pubspec.yaml").exists() {
+        // Flutter 项目在 pubspec 中声明 flutter SDK 依赖
+        let is_flutter = fs::read_to_string(path.join("pubspec.yaml"))
+            .is_ok_and(|content| content.lines().any(|line| line.trim() == "flutter:"));
+        return if is_flutter {
+            ProjectType::Flutter
+        } else {
+            ProjectType::Dart
+        };
+    }
+    if path.join("composer.json").exists() {
+        return ProjectType::Php;
+    }
+    if path.join("Gemfile").exists() {
+        return ProjectType::Ruby;
+    }
+    if path.join("mix.exs").exists() {
+        return ProjectType::Elixir;
+    }
+    if path.join("build.zig").exists() {
+        return ProjectType::Zig;
+    }
+    // Unity 会生成 .sln/.csproj，需先于 .NET 判断
+    if is_unity_project(path) {
+        return ProjectType::Unity;
+    }
+    if has_file_with_extension(path, &["sln", "csproj"]) {
+        return ProjectType::Dotnet;
+    }
+    if path.join("CMakeLists.txt").exists() {
+        return ProjectType::Cpp;
+    }
+    if has_file_with_extension(path, &["tf"]) {
+        return ProjectType::Terraform;
+    }
+
+    ProjectType::Generic
+}
+
+fn has_file_with_extension(path: &Path, extensions: &[&str]) -> bool {
+    fs::read_dir(path)
         .ok()
         .into_iter()
         .flatten()
@@ -1147,14 +1456,13 @@ fn detect_project_type(path: &Path) -> ProjectType {
                 .path()
                 .extension()
                 .and_then(|v| v.to_str())
-                .map(|ext| ext.eq_ignore_ascii_case("sln") || ext.eq_ignore_ascii_case("csproj"))
+                .map(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
                 .unwrap_or(false)
-        });
-    if has_dotnet_project {
-        return ProjectType::Dotnet;
-    }
+        })
+}
 
-    ProjectType::Generic
+fn is_unity_project(path: &Path) -> bool {
+    path.join("Assets").is_dir() && path.join("ProjectSettings/ProjectVersion.txt").exists()
 }
 
 fn is_project_root(path: &Path) -> bool {
@@ -1165,7 +1473,16 @@ fn is_project_root(path: &Path) -> bool {
         || path.join("go.mod").exists()
         || path.join("pom.xml").exists()
         || path.join("build.gradle").exists()
+        || path.join("pubspec.yaml").exists()
+        || path.join("composer.json").exists()
+        || path.join("Gemfile").exists()
+        || path.join("mix.exs").exists()
+        || path.join("CMakeLists.txt").exists()
+        || path.join("build.zig").exists()
+        || is_unity_project(path)
+        || has_file_with_extension(path, &["tf"])
         || path.join(".git").exists()
+        || detection::is_marked(path)
 }
 
 fn should_skip_dir(path: &Path) -> bool {
@@ -1184,13 +1501,47 @@ fn should_skip_dir(path: &Path) -> bool {
     }
 }
 
-fn scan_projects_rec(path: &Path, current_depth: u8, max_depth: u8, out: &mut Vec<PathBuf>) {
-    if current_depth > max_depth || should_skip_dir(path) {
+/// 只支持 `*` 与 `?` 通配符
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| wildcard_match(rest, &text[i..])),
+        Some((&c, rest)) => text.split_first().is_some_and(|(&t, text_rest)| {
+            (c == '?' || c.eq_ignore_ascii_case(&t)) && wildcard_match(rest, text_rest)
+        }),
+    }
+}
+
+/// 排除规则匹配目录名或完整路径（统一使用 `/` 分隔）
+fn is_excluded(path: &Path, exclude: &[String]) -> bool {
+    if exclude.is_empty() {
+        return false;
+    }
+    let name: Vec<char> = path
+        .file_name()
+        .map(|n| n.to_string_lossy().chars().collect())
+        .unwrap_or_default();
+    let full: Vec<char> = path.to_string_lossy().replace('\\', "/").chars().collect();
+    exclude.iter().any(|pattern| {
+        let pattern: Vec<char> = pattern.replace('\\', "/").chars().collect();
+        wildcard_match(&pattern, &name) || wildcard_match(&pattern, &full)
+    })
+}
+
+/// 每找到一个项目目录就调用 on_found，不再深入该目录
+fn scan_projects_rec(
+    path: &Path,
+    current_depth: u8,
+    max_depth: u8,
+    exclude: &[String],
+    on_found: &mut impl FnMut(&Path),
+) {
+    if current_depth > max_depth || should_skip_dir(path) || is_excluded(path, exclude) {
         return;
     }
 
     if is_project_root(path) {
-        out.push(path.to_path_buf());
+        on_found(path);
         return;
     }
 
@@ -1202,11 +1553,72 @@ fn scan_projects_rec(path: &Path, current_depth: u8, max_depth: u8, out: &mut Ve
     for entry in entries.flatten() {
         let child = entry.path();
         if child.is_dir() {
-            scan_projects_rec(&child, current_depth + 1, max_depth, out);
+            scan_projects_rec(&child, current_depth + 1, max_depth, exclude, on_found);
         }
     }
 }
 
+fn compose_file_name(project_path: &Path) -> Option<String> {
+    compose::find_compose_file(project_path)
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+}
+
+/// 在 dir 中执行 git 的命令，调用方可以再设置环境变量等
+fn git_command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(dir).args(args);
+    process::hide_console_window(&mut command);
+    command
+}
+
+/// 执行 git 命令并返回去掉首尾空白的 stdout，失败时错误中带上 stderr
+fn run_git(mut command: Command, args: &[&str]) -> Result<String, String> {
+    let output = command.output().map_err(|e| format!("无法执行 git: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!("git {} 失败: {stderr}", args.join(" ")));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    run_git(git_command(dir, args), args)
+}
+
+/// 在项目目录执行 git 并返回去掉首尾空白的 stdout，失败返回 None
+fn git_output(project_path: &Path, args: &[&str]) -> Option<String> {
+    git(project_path, args).ok()
+}
+
+/// 调用方可能持有 store 锁，history 锁总是在 store 之后获取
+fn record_launches(state: &AppState, project_id: &str, ide_ids: &[&str]) {
+    let mut history = state.history.lock().expect("history lock poisoned");
+    for ide_id in ide_ids {
+        history.record(project_id, Some(ide_id));
+    }
+    if let Err(err) = history::save_history(&history::history_path(&state.file_path), &history) {
+        tracing::warn!(error = %err, "保存启动记录失败");
+    }
+}
+
+fn record_launch_failure(state: &AppState, project_id: &str, ide_id: &str, error: &str) {
+    let mut history = state.history.lock().expect("history lock poisoned");
+    history.record_failure(project_id, ide_id, error);
+    if let Err(err) = history::save_history(&history::history_path(&state.file_path), &history) {
+        tracing::warn!(error = %err, "保存启动记录失败");
+    }
+}
+
+fn find_project(state: &AppState, project_id: &str) -> Result<Project, String> {
+    state
+        .store
+        .projects()
+        .iter()
+        .find(|p| p.id == project_id)
+        .cloned()
+        .ok_or_else(|| "项目不存在".to_string())
+}
+
 fn split_args_template(args_template: &str) -> Vec<String> {
     let trimmed = args_template.trim();
     if trimmed.is_empty() {
@@ -1220,61 +1632,390 @@ fn split_args_template(args_template: &str) -> Vec<String> {
     vec![trimmed.to_string()]
 }
 
-fn expand_args(args_template: &str, project: &Project) -> Vec<String> {
+/// `C:\code\foo` 转为 `/mnt/c/code/foo`，`\\wsl$\Ubuntu\home` 转为 `/home`，其它路径原样返回
+fn to_wsl_path(path: &str) -> String {
+    let unix = |rest: &str| format!("/{}", rest.replace('\\', "/").trim_start_matches('/'));
+    for prefix in [r"\\wsl$\", r"\\wsl.localhost\"] {
+        if let Some(rest) = path.strip_prefix(prefix) {
+            // 跳过发行版名称
+            return unix(rest.split_once('\\').map(|(_, p)| p).unwrap_or(""));
+        }
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        let drive = (bytes[0] as char).to_ascii_lowercase();
+        return format!("/mnt/{drive}{}", unix(&path[2..]).trim_end_matches('/'));
+    }
+    path.to_string()
+}
+
+/// 展开 `{env:NAME}`，未设置的变量替换为空字符串
+fn expand_env_placeholders(arg: &str) -> String {
+    let mut result = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find("{env:") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = &rest[start + "{env:".len()..start + len];
+        result.push_str(&env::var(name).unwrap_or_default());
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 支持的占位符：`{projectPath}` `{projectName}` `{projectDir}` `{branch}` `{relativeFile}`
+/// `{ideName}` `{wslPath}` 以及环境变量 `{env:NAME}`；没有指定文件时整个 `{relativeFile}` 参数被省略
+fn expand_args(
+    args_template: &str,
+    project: &Project,
+    ide: &IdeConfig,
+    relative_file: Option<&str>,
+) -> Vec<String> {
+    let project_dir = Path::new(&project.path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut branch = None;
     // 先拆模板、后替换占位符，避免 Windows 路径中的反斜杠被 shell 解析破坏。
     split_args_template(args_template)
         .into_iter()
+        .filter(|arg| relative_file.is_some() || arg != "{relativeFile}")
         .map(|arg| {
-            arg.replace("{projectPath}", &project.path)
+            let mut arg = expand_env_placeholders(&arg)
+                .replace("{projectPath}", &project.path)
                 .replace("{projectName}", &project.name)
+                .replace("{projectDir}", &project_dir)
+                .replace("{relativeFile}", relative_file.unwrap_or(""))
+                .replace("{ideName}", &ide.name)
+                .replace("{wslPath}", &to_wsl_path(&project.path));
+            if arg.contains("{branch}") {
+                let branch = branch.get_or_insert_with(|| {
+                    git_output(
+                        Path::new(&project.path),
+                        &["rev-parse", "--abbrev-ref", "HEAD"],
+                    )
+                    .unwrap_or_default()
+                });
+                arg = arg.replace("{branch}", branch);
+            }
+            arg
         })
         .collect()
 }
 
-#[tauri::command]
-fn get_projects(state: State<'_, AppState>) -> Vec<Project> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    for project in &mut store.projects {
-        project.last_modified = file_mtime_iso(&project.path);
-    }
-    let mut projects = store.projects.clone();
-    projects.sort_by(|a, b| {
-        b.last_modified
-            .cmp(&a.last_modified)
-            .then_with(|| a.name.cmp(&b.name))
-    });
-    projects
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchPreview {
+    executable: String,
+    args: Vec<String>,
+    working_dir: String,
 }
 
+/// 返回启动主窗口时实际使用的命令行，用于调试参数模板
 #[tauri::command]
-fn get_ides(state: State<'_, AppState>) -> Vec<IdeConfig> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let mut dirty = false;
-    for ide in &mut store.ides {
-        let should_refresh_icon = match ide.icon.as_deref() {
-            None => true,
-            Some(icon) => icon.starts_with("data:image/png") && !is_cached_v3_icon(icon),
-        };
-        if !should_refresh_icon {
-            continue;
-        }
-        let icon = resolve_ide_icon(&state.file_path, ide);
-        if icon.is_some() {
-            ide.icon = icon;
-            dirty = true;
-        }
-    }
-    if dirty {
-        let _ = save_store(&state.file_path, &store);
-    }
-    let mut ides = store.ides.clone();
-    ides.sort_by_key(|x| x.priority);
-    ides
+fn preview_launch_command(
+    project_id: String,
+    ide_id: String,
+    relative_file: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<LaunchPreview, String> {
+    let project = find_project(&state, &project_id)?;
+    let ide = state
+        .store
+        .ides()
+        .iter()
+        .find(|i| i.id == ide_id)
+        .cloned()
+        .ok_or_else(|| "IDE 不存在".to_string())?;
+    let ide = arg_presets::effective_ide(&project, &ide);
+    let mut args = expand_args(&ide.args_template, &project, &ide, relative_file.as_deref());
+    workspace::apply_workspace(&project, &ide, &mut args);
+    multiroot::append_single_window_roots(&project, &ide, &mut args);
+    multiplexer::apply(&project, &ide, &mut args)?;
+    Ok(LaunchPreview {
+        executable: ide.executable,
+        args,
+        working_dir: project.path,
+    })
 }
 
-#[tauri::command]
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+enum ProjectSort {
+    /// 最近修改在前（默认）
+    #[default]
+    LastModified,
+    LastOpened,
+    Name,
+    CreatedAt,
+    DisplayOrder,
+}
+
+/// get_projects 的附加筛选与分页参数，均可省略
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ProjectQuery {
+    project_type: Option<ProjectType>,
+    favorite: Option<bool>,
+    /// 名称、路径、描述、备注或标签中包含该文本（不区分大小写）
+    text: Option<String>,
+    sort: ProjectSort,
+    offset: usize,
+    limit: Option<usize>,
+    /// 为 true 时不返回语言统计，列表页无需完整元数据
+    summary: bool,
+}
+
+impl ProjectQuery {
+    fn matches(&self, project: &Project) -> bool {
+        if self
+            .project_type
+            .as_ref()
+            .is_some_and(|t| *t != project.project_type)
+        {
+            return false;
+        }
+        if self.favorite.is_some_and(|f| f != project.favorite) {
+            return false;
+        }
+        let Some(text) = self
+            .text
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        else {
+            return true;
+        };
+        let text = text.to_lowercase();
+        let contains = |value: &str| value.to_lowercase().contains(&text);
+        contains(&project.name)
+            || contains(&project.path)
+            || project
+                .metadata
+                .description
+                .as_deref()
+                .is_some_and(contains)
+            || project.notes.as_deref().is_some_and(contains)
+            || project
+                .tags
+                .iter()
+                .chain(&project.metadata.system_tags)
+                .any(|t| contains(t))
+    }
+
+    fn sort(&self, projects: &mut [Project]) {
+        match self.sort {
+            // list_projects 已按修改时间排序
+            ProjectSort::LastModified => {}
+            ProjectSort::LastOpened => projects.sort_by(|a, b| {
+                b.last_opened
+                    .cmp(&a.last_opened)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+            ProjectSort::Name => projects.sort_by(|a, b| {
+                a.name
+                    .to_lowercase()
+                    .cmp(&b.name.to_lowercase())
+                    .then_with(|| a.path.cmp(&b.path))
+            }),
+            ProjectSort::CreatedAt => projects.sort_by(|a, b| {
+                b.created_at
+                    .cmp(&a.created_at)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+            ProjectSort::DisplayOrder => projects.sort_by(|a, b| {
+                a.display_order
+                    .cmp(&b.display_order)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+        }
+    }
+}
+
+/// hide_missing 为 true 时不返回目录已不存在的项目；
+/// query 可按类型、收藏、文本筛选并排序分页，summary 模式下省略语言统计
+#[tauri::command(async)]
+fn get_projects(
+    tags: Option<Vec<String>>,
+    hide_missing: Option<bool>,
+    query: Option<ProjectQuery>,
+    state: State<'_, AppState>,
+) -> Vec<Project> {
+    let query = query.unwrap_or_default();
+    let mut projects = list_projects(&state, tags);
+    if hide_missing.unwrap_or(false) {
+        projects.retain(|p| !p.missing);
+    }
+    projects.retain(|p| query.matches(p));
+    query.sort(&mut projects);
+    let mut projects: Vec<Project> = projects
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+    if query.summary {
+        for project in &mut projects {
+            project.metadata.language_stats = None;
+        }
+    }
+    projects
+}
+
+/// 单个项目的完整信息，配合 summary 模式的列表使用
+#[tauri::command]
+fn get_project(project_id: String, state: State<'_, AppState>) -> Result<Project, String> {
+    let mut project = find_project(&state, &project_id)?;
+    project.last_modified = mtime::project_mtime_iso(&project.path);
+    project.missing = !Path::new(&project.path).is_dir();
+    Ok(project)
+}
+
+/// 检查所有项目路径并保存 missing 标记，返回目录已不存在的项目。
+/// 检查路径与读取远端地址时不持有锁
+#[tauri::command(async)]
+fn validate_projects(state: State<'_, AppState>) -> Result<Vec<Project>, String> {
+    let checked: Vec<(String, bool, Option<String>)> = state
+        .store
+        .projects()
+        .clone()
+        .into_iter()
+        .map(|project| {
+            let missing = !Path::new(&project.path).is_dir();
+            // 记录远端地址，目录移动后据此重新定位
+            let git_url = (!missing && project.metadata.git_url.is_none())
+                .then(|| relocate::remote_url(Path::new(&project.path)))
+                .flatten();
+            (project.id, missing, git_url)
+        })
+        .collect();
+
+    let mut projects = state.store.projects_mut();
+    let mut changed = false;
+    for (id, missing, git_url) in checked {
+        let Some(project) = projects.iter_mut().find(|p| p.id == id) else {
+            continue;
+        };
+        if project.missing != missing {
+            project.missing = missing;
+            changed = true;
+        }
+        if git_url.is_some() {
+            project.metadata.git_url = git_url;
+            changed = true;
+        }
+    }
+    if changed {
+        persist::mark_dirty();
+    }
+    let missing: Vec<Project> = projects.iter().filter(|p| p.missing).cloned().collect();
+    tracing::info!(missing = missing.len(), "已检查项目路径");
+    Ok(missing)
+}
+
+/// list_projects 每次按磁盘状态重新计算的字段，各实例与各台机器上天然不同，合并时不算作改动
+const DERIVED_PROJECT_FIELDS: &[&str] = &["lastModified", "missing"];
+
+/// 合并时比较的项目内容，不含按磁盘状态计算的字段
+fn project_merge_value(project: &Project) -> serde_json::Value {
+    let mut value = serde_json::to_value(project).unwrap_or_default();
+    if let Some(fields) = value.as_object_mut() {
+        for field in DERIVED_PROJECT_FIELDS {
+            fields.remove(*field);
+        }
+    }
+    value
+}
+
+/// get_projects 与导出报告共用的筛选与排序
+/// 读取文件状态时不持有锁，结果写回供托盘菜单等使用
+fn list_projects(state: &AppState, tags: Option<Vec<String>>) -> Vec<Project> {
+    let mut all = state.store.projects().clone();
+    for project in &mut all {
+        project.last_modified = mtime::project_mtime_iso(&project.path);
+        project.missing = !Path::new(&project.path).is_dir();
+    }
+    {
+        let mut projects = state.store.projects_mut();
+        for project in projects.iter_mut() {
+            if let Some(current) = all.iter().find(|p| p.id == project.id) {
+                project.last_modified = current.last_modified.clone();
+                project.missing = current.missing;
+            }
+        }
+    }
+    // 按标签筛选时需同时满足所有标签，用户标签与系统标签均可匹配
+    let tags = tags.unwrap_or_default();
+    let mut projects: Vec<Project> = all
+        .into_iter()
+        .filter(|p| {
+            tags.iter().all(|tag| {
+                p.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+                    || p.metadata
+                        .system_tags
+                        .iter()
+                        .any(|t| t.eq_ignore_ascii_case(tag))
+            })
+        })
+        .collect();
+    projects.sort_by(|a, b| {
+        b.last_modified
+            .cmp(&a.last_modified)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    projects
+}
+
+/// 缺少图标或图标为旧版本提取结果时重新获取，提取与下载在锁外进行
+#[tauri::command(async)]
+fn get_ides(state: State<'_, AppState>) -> Vec<IdeConfig> {
+    let stale: Vec<IdeConfig> = state
+        .store
+        .ides()
+        .iter()
+        .filter(|ide| match ide.icon.as_deref() {
+            None => true,
+            Some(icon) => icon.starts_with("data:image/png") && !is_current_extraction_icon(icon),
+        })
+        .cloned()
+        .collect();
+    let resolved: Vec<(String, String)> = stale
+        .iter()
+        .filter_map(|ide| {
+            resolve_ide_icon(&state.file_path, ide).map(|icon| (ide.id.clone(), icon))
+        })
+        .collect();
+
+    let mut stored = state.store.ides_mut();
+    for (id, icon) in resolved {
+        if let Some(ide) = stored.iter_mut().find(|i| i.id == id) {
+            ide.icon = Some(icon);
+            persist::mark_dirty();
+        }
+    }
+    let mut ides: Vec<IdeConfig> = stored.iter().cloned().map(ide_for_display).collect();
+    ides.sort_by_key(|x| x.priority);
+    ides
+}
+
+/// 返回给界面的 IDE 配置，开启栅格化时 SVG 图标转为 PNG
+fn ide_for_display(mut ide: IdeConfig) -> IdeConfig {
+    ide.icon = ide.icon.map(svg_icon::display_icon);
+    ide
+}
+
+/// 语言统计等文件读取在锁外进行
+#[tauri::command(async)]
 fn add_project(input: NewProjectInput, state: State<'_, AppState>) -> Result<Project, String> {
-    let path = PathBuf::from(&input.path);
+    let mut path = PathBuf::from(&input.path);
+    // 直接登记 .code-workspace 文件时，以其所在目录作为项目路径
+    let mut workspace_file = None;
+    if path.is_file() && workspace::is_workspace_file(&path) {
+        workspace_file = path.file_name().map(|n| n.to_string_lossy().to_string());
+        path = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    }
     if !path.exists() || !path.is_dir() {
         return Err("项目路径不存在或不是目录".to_string());
     }
@@ -1286,42 +2027,32 @@ fn add_project(input: NewProjectInput, state: State<'_, AppState>) -> Result<Pro
         .to_string();
     let normalized_path = normalize_windows_path_for_ui(&normalized_path);
 
-    let mut store = state.store.lock().expect("store lock poisoned");
-    if store.projects.iter().any(|p| p.path == normalized_path) {
+    let language_settings = language_stats_settings(&state);
+    let key = path_key(&normalized_path);
+    if state
+        .store
+        .projects()
+        .iter()
+        .any(|p| path_key(&p.path) == key)
+    {
         return Err("该项目路径已存在".to_string());
     }
 
     // 自动统计语言分布
-    let language_stats = scan_project_languages(&path).ok().map(|lang_data| {
-        let total_lines: u64 = lang_data.values().map(|(lines, _)| *lines).sum();
-        let mut languages: Vec<LanguageEntry> = lang_data
-            .into_iter()
-            .map(|(language, (lines, files))| {
-                let percentage = if total_lines > 0 {
-                    (lines as f64 / total_lines as f64) * 100.0
-                } else {
-                    0.0
-                };
-                LanguageEntry {
-                    language,
-                    lines,
-                    files,
-                    percentage,
-                }
-            })
-            .collect();
-        languages.sort_by(|a, b| b.lines.cmp(&a.lines));
-        LanguageStats {
-            total_lines,
-            languages,
-            scanned_at: now_iso(),
-        }
-    });
-
-    let created = Project {
+    let language_stats = compute_language_stats(&path, &state.file_path, &language_settings).ok();
+
+    let custom_type = input
+        .project_type
+        .is_none()
+        .then(|| detection::custom_type(&path))
+        .flatten();
+    let mut created = Project {
         id: Uuid::new_v4().to_string(),
         name: if input.name.trim().is_empty() {
-            path.file_name()
+            workspace_file
+                .as_deref()
+                .and_then(|n| Path::new(n).file_stem())
+                .or_else(|| path.file_name())
                 .and_then(|n| n.to_str())
                 .unwrap_or("untitled")
                 .to_string()
@@ -1329,131 +2060,293 @@ fn add_project(input: NewProjectInput, state: State<'_, AppState>) -> Result<Pro
             input.name.trim().to_string()
         },
         path: normalized_path.clone(),
+        paths: vec![],
         project_type: input
             .project_type
             .unwrap_or_else(|| detect_project_type(&path)),
         favorite: input.favorite.unwrap_or(false),
         tags: input.tags.unwrap_or_default(),
+        aliases: vec![],
         last_opened: None,
-        last_modified: file_mtime_iso(&normalized_path),
+        last_modified: mtime::project_mtime_iso(&normalized_path),
         created_at: now_iso(),
-        display_order: store
-            .projects
-            .iter()
-            .map(|p| p.display_order)
-            .max()
-            .unwrap_or(0)
-            + 1,
+        display_order: 0,
+        icon: None,
+        notes: None,
+        missing: false,
         metadata: ProjectMetadata {
             ide_preferences: input.ide_preferences.unwrap_or_default(),
-            git_url: None,
+            git_url: relocate::remote_url(&path),
             description: input.description,
+            description_source: None,
             language_stats,
+            compose_file: compose_file_name(&path),
+            system_tags: smart_tags::detect_system_tags(&path),
+            activate_version_managers: false,
+            env_file: None,
+            open_workspace_file: workspace_file.is_some(),
+            launch_steps: vec![],
+            elevated_ides: vec![],
+            multiplexer: None,
+            last_used_ide: None,
+            ide_arg_presets: BTreeMap::new(),
+            custom_type,
+            dev_url: None,
+            dev_command: None,
+            workspace_file: workspace_file.or_else(|| workspace::detect_workspace_file(&path)),
         },
     };
 
-    store.projects.push(created.clone());
-    save_store(&state.file_path, &store)?;
+    // 统计期间可能已添加了同一路径，持有写锁后再检查一次
+    let mut projects = state.store.projects_mut();
+    if projects.iter().any(|p| path_key(&p.path) == key) {
+        return Err("该项目路径已存在".to_string());
+    }
+    created.display_order = projects.iter().map(|p| p.display_order).max().unwrap_or(0) + 1;
+    state.language_history.record(&created);
+    projects.push(created.clone());
+    tags::ensure_definitions(&projects, &mut state.store.tags_mut());
+    persist::mark_dirty();
+    drop(projects);
+    state.language_history.save();
     Ok(created)
 }
 
+/// 默认移入回收站，permanent 为 true 时彻底删除
 #[tauri::command]
-fn remove_project(project_id: String, state: State<'_, AppState>) -> Result<(), String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let before = store.projects.len();
-    store.projects.retain(|p| p.id != project_id);
-    if store.projects.len() == before {
-        return Err("项目不存在".to_string());
+fn remove_project(
+    project_id: String,
+    permanent: Option<bool>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let removed = projects
+        .iter()
+        .position(|p| p.id == project_id)
+        .map(|idx| projects.remove(idx))
+        .ok_or_else(|| "项目不存在".to_string())?;
+    let label = format!("删除项目 {}", removed.name);
+    if permanent.unwrap_or(false) {
+        trash::forget_project_data(&state, &removed);
+    } else {
+        trash::move_to_trash(&state, removed);
     }
-    save_store(&state.file_path, &store)
+    persist::mark_dirty();
+    drop(projects);
+    state.undo.push(&label, snapshot, &state.store);
+    tray::refresh_tray_menu(&app);
+    Ok(())
 }
 
 #[tauri::command]
 fn toggle_project_favorite(
     project_id: String,
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
 ) -> Result<Project, String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let project = store
-        .projects
+    let mut projects = state.store.projects_mut();
+    let project = projects
         .iter_mut()
         .find(|p| p.id == project_id)
         .ok_or_else(|| "项目不存在".to_string())?;
     project.favorite = !project.favorite;
     let result = project.clone();
-    save_store(&state.file_path, &store)?;
+    persist::mark_dirty();
+    drop(projects);
+    tray::refresh_tray_menu(&app);
     Ok(result)
 }
 
-#[tauri::command]
+/// 记录扫描过的根目录，供定期重新扫描与重新定位项目时使用。已记录时返回保存的配置
+fn remember_scan_root(
+    state: &AppState,
+    root: &Path,
+    max_depth: Option<u8>,
+) -> Option<settings::ScanRoot> {
+    let root = root.canonicalize().ok()?;
+    let root = normalize_windows_path_for_ui(&root.to_string_lossy());
+    let mut settings = state.settings.lock().expect("settings lock poisoned");
+    let key = path_key(&root);
+    if let Some(saved) = settings
+        .scan_roots
+        .iter()
+        .find(|r| path_key(&r.path) == key)
+    {
+        return Some(saved.clone());
+    }
+    let saved = settings::ScanRoot {
+        path: root,
+        max_depth: max_depth.unwrap_or(settings::DEFAULT_SCAN_DEPTH),
+        exclude: vec![],
+    };
+    settings.scan_roots.push(saved.clone());
+    if let Err(err) = settings::save_settings(&settings::settings_path(&state.file_path), &settings)
+    {
+        tracing::warn!("保存扫描根目录失败: {err}");
+    }
+    Some(saved)
+}
+
+/// 扫描中每找到一个项目目录发送一次，语言统计等耗时步骤完成前即可先显示
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanFound {
+    /// 所属后台任务的 id
+    task_id: String,
+    path: String,
+    name: String,
+    project_type: ProjectType,
+    custom_type: Option<String>,
+    /// 已在项目列表中
+    existing: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct ScanReport {
+    added: Vec<Project>,
+    /// 语言统计、系统标签等扫描字段有变化的已有项目
+    updated: Vec<Project>,
+    unchanged: Vec<Project>,
+    /// 位于扫描根目录下但目录已不存在的项目
+    missing: Vec<Project>,
+}
+
+/// 扫描会改写的字段，用于区分已有项目是否有变化（忽略统计时间）
+fn scanned_fields(project: &Project) -> serde_json::Value {
+    let mut stats = serde_json::to_value(&project.metadata.language_stats).unwrap_or_default();
+    if let Some(stats) = stats.as_object_mut() {
+        stats.remove("scannedAt");
+    }
+    serde_json::json!([
+        stats,
+        project.metadata.compose_file,
+        project.metadata.system_tags,
+        project.metadata.workspace_file,
+        project.metadata.description,
+    ])
+}
+
+/// 扫描在后台任务队列中执行，可通过 cancel_background_task 中途停止，已处理的项目会保留。
+/// mark_missing 为 true 时给根目录下已不存在的项目设置 missing 标记。
+/// 语言统计会让扫描慢很多，默认不统计，之后可用 refresh_language_stats 补上。
+/// auto_description 为 true 时为没有描述的项目从清单与 README 中提取描述
+#[tauri::command(async)]
 fn scan_projects(
     root_path: String,
     max_depth: Option<u8>,
+    mark_missing: Option<bool>,
+    compute_language_stats: Option<bool>,
+    auto_description: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<Vec<Project>, String> {
+) -> Result<ScanReport, String> {
     let root = PathBuf::from(root_path);
     if !root.exists() || !root.is_dir() {
         return Err("扫描路径不存在或不是目录".to_string());
     }
+    state
+        .background
+        .run(TaskKind::Scan, root.display().to_string(), |task| {
+            scan_root(
+                &root,
+                max_depth,
+                mark_missing.unwrap_or(false),
+                compute_language_stats.unwrap_or(false),
+                auto_description.unwrap_or(false),
+                &state,
+                task,
+            )
+        })
+}
 
+fn scan_root(
+    root: &Path,
+    max_depth: Option<u8>,
+    mark_missing: bool,
+    with_language_stats: bool,
+    with_descriptions: bool,
+    state: &AppState,
+    task: &TaskContext,
+) -> Result<ScanReport, String> {
+    let saved = remember_scan_root(state, root, max_depth);
+    let exclude = saved
+        .as_ref()
+        .map(|r| r.exclude.as_slice())
+        .unwrap_or_default();
+    let max_depth = max_depth
+        .or(saved.as_ref().map(|r| r.max_depth))
+        .unwrap_or(settings::DEFAULT_SCAN_DEPTH);
+    let known: HashSet<String> = state
+        .store
+        .projects()
+        .iter()
+        .map(|p| path_key(&p.path))
+        .collect();
     let mut found_paths = vec![];
-    scan_projects_rec(&root, 0, max_depth.unwrap_or(3), &mut found_paths);
-
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let mut existing_paths: HashSet<String> =
-        store.projects.iter().map(|p| p.path.clone()).collect();
-    let mut added = vec![];
+    scan_projects_rec(root, 0, max_depth, exclude, &mut |path| {
+        let display = path
+            .canonicalize()
+            .map(|p| normalize_windows_path_for_ui(&p.to_string_lossy()))
+            .unwrap_or_else(|_| path.to_string_lossy().to_string());
+        task.emit(
+            "scan://found",
+            ScanFound {
+                task_id: task.id().to_string(),
+                name: path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("untitled")
+                    .to_string(),
+                existing: known.contains(&path_key(&display)),
+                path: display,
+                project_type: detect_project_type(path),
+                custom_type: detection::custom_type(path),
+            },
+        );
+        found_paths.push(path.to_path_buf());
+    });
+    tracing::info!(root = %root.display(), found = found_paths.len(), "扫描项目目录");
+    if task.is_cancelled() {
+        return Err(background::CANCELLED.to_string());
+    }
 
-    let mut next_order = store
-        .projects
+    let language_settings = language_stats_settings(state);
+    let mut existing_paths: HashSet<String> = state
+        .store
+        .projects()
         .iter()
-        .map(|p| p.display_order)
-        .max()
-        .unwrap_or(0)
-        + 1;
+        .map(|p| path_key(&p.path))
+        .collect();
+    let mut scanned = vec![];
 
-    for item in found_paths {
+    // 统计与识别在锁外进行，最后一次性写入
+    let total = found_paths.len();
+    for (done, item) in found_paths.into_iter().enumerate() {
+        if task.is_cancelled() {
+            break;
+        }
+        task.progress(done, total);
         let canonical = match item.canonicalize() {
             Ok(v) => normalize_windows_path_for_ui(&v.to_string_lossy()),
             Err(_) => continue,
         };
 
         // 检查项目是否已存在
-        let is_new = !existing_paths.contains(&canonical);
-        if is_new {
-            existing_paths.insert(canonical.clone());
-        }
+        let key = path_key(&canonical);
+        let is_new = existing_paths.insert(key.clone());
 
-        // 自动统计语言分布（新项目和已有项目都更新）
-        let language_stats = scan_project_languages(&item).ok().map(|lang_data| {
-            let total_lines: u64 = lang_data.values().map(|(lines, _)| *lines).sum();
-            let mut languages: Vec<LanguageEntry> = lang_data
-                .into_iter()
-                .map(|(language, (lines, files))| {
-                    let percentage = if total_lines > 0 {
-                        (lines as f64 / total_lines as f64) * 100.0
-                    } else {
-                        0.0
-                    };
-                    LanguageEntry {
-                        language,
-                        lines,
-                        files,
-                        percentage,
-                    }
-                })
-                .collect();
-            languages.sort_by(|a, b| b.lines.cmp(&a.lines));
-            LanguageStats {
-                total_lines,
-                languages,
-                scanned_at: now_iso(),
-            }
-        });
+        // 统计语言分布（新项目和已有项目都更新），不统计时已有项目保留原来的结果
+        let language_stats = with_language_stats
+            .then(|| compute_language_stats(&item, &state.file_path, &language_settings).ok());
+        let auto_description = with_descriptions
+            .then(|| description::detect(&item))
+            .flatten();
 
         if is_new {
-            // 创建新项目
-            let project = Project {
+            // 创建新项目，排序位置在写入时确定
+            let mut project = Project {
                 id: Uuid::new_v4().to_string(),
                 name: item
                     .file_name()
@@ -1461,39 +2354,159 @@ fn scan_projects(
                     .unwrap_or("untitled")
                     .to_string(),
                 path: canonical.clone(),
+                paths: vec![],
                 project_type: detect_project_type(&item),
                 favorite: false,
                 tags: vec![],
+                aliases: vec![],
                 last_opened: None,
-                last_modified: file_mtime_iso(&canonical),
+                last_modified: mtime::project_mtime_iso(&canonical),
                 created_at: now_iso(),
-                display_order: next_order,
+                display_order: 0,
+                icon: None,
+                notes: None,
+                missing: false,
                 metadata: ProjectMetadata {
                     ide_preferences: vec![],
-                    git_url: None,
+                    git_url: relocate::remote_url(&item),
                     description: None,
-                    language_stats,
+                    description_source: None,
+                    language_stats: language_stats.flatten(),
+                    compose_file: compose_file_name(&item),
+                    system_tags: smart_tags::detect_system_tags(&item),
+                    activate_version_managers: false,
+                    env_file: None,
+                    workspace_file: workspace::detect_workspace_file(&item),
+                    open_workspace_file: false,
+                    launch_steps: vec![],
+                    elevated_ides: vec![],
+                    multiplexer: None,
+                    last_used_ide: None,
+                    ide_arg_presets: BTreeMap::new(),
+                    custom_type: detection::custom_type(&item),
+                    dev_url: None,
+                    dev_command: None,
                 },
             };
-            next_order += 1;
-            store.projects.push(project.clone());
-            added.push(project);
+            if let Some(auto) = auto_description {
+                description::apply(&mut project.metadata, auto);
+            }
+            scanned.push(ScannedProject::New(Box::new(project)));
         } else {
-            // 更新已有项目的语言统计
-            if let Some(project) = store.projects.iter_mut().find(|p| p.path == canonical) {
-                project.metadata.language_stats = language_stats;
-                added.push(project.clone());
+            scanned.push(ScannedProject::Existing {
+                key,
+                language_stats,
+                auto_description: auto_description.map(Box::new),
+                compose_file: compose_file_name(&item),
+                system_tags: smart_tags::detect_system_tags(&item),
+            });
+        }
+    }
+
+    let root_key = root
+        .canonicalize()
+        .map(|root| path_key(&normalize_windows_path_for_ui(&root.to_string_lossy())))
+        .unwrap_or_default();
+    // 撤销快照在写入前重新获取，撤销扫描不会还原扫描期间对其它项目的修改
+    let before = state.store.snapshot();
+    let missing_ids: HashSet<String> = before
+        .projects
+        .iter()
+        .filter(|p| path_key(&p.path).starts_with(&format!("{root_key}/")))
+        .filter(|p| !Path::new(&p.path).is_dir())
+        .map(|p| p.id.clone())
+        .collect();
+
+    let mut projects = state.store.projects_mut();
+    let mut next_order = projects.iter().map(|p| p.display_order).max().unwrap_or(0) + 1;
+    let mut report = ScanReport::default();
+    for item in scanned {
+        match item {
+            ScannedProject::New(mut project) => {
+                // 扫描期间可能已通过其它方式添加
+                let key = path_key(&project.path);
+                if projects.iter().any(|p| path_key(&p.path) == key) {
+                    continue;
+                }
+                project.display_order = next_order;
+                next_order += 1;
+                state.language_history.record(&project);
+                projects.push((*project).clone());
+                report.added.push(*project);
+            }
+            ScannedProject::Existing {
+                key,
+                language_stats,
+                auto_description,
+                compose_file,
+                system_tags,
+            } => {
+                // 更新已有项目的语言统计
+                if let Some(project) = projects.iter_mut().find(|p| path_key(&p.path) == key) {
+                    let before = scanned_fields(project);
+                    if let Some(language_stats) = language_stats {
+                        project.metadata.language_stats = language_stats;
+                        state.language_history.record(project);
+                    }
+                    if let Some(auto) = auto_description {
+                        description::apply(&mut project.metadata, *auto);
+                    }
+                    project.metadata.compose_file = compose_file;
+                    project.metadata.system_tags = system_tags;
+                    workspace::refresh_workspace_file(project);
+                    if scanned_fields(project) == before {
+                        report.unchanged.push(project.clone());
+                    } else {
+                        report.updated.push(project.clone());
+                    }
+                }
             }
         }
     }
 
-    if !added.is_empty() {
-        save_store(&state.file_path, &store)?;
+    let mut marked = false;
+    for project in projects.iter_mut() {
+        if !missing_ids.contains(&project.id) {
+            continue;
+        }
+        if mark_missing && !project.missing {
+            project.missing = true;
+            marked = true;
+        }
+        report.missing.push(project.clone());
     }
-    Ok(added)
+
+    drop(projects);
+    if !report.added.is_empty() || !report.updated.is_empty() || marked {
+        persist::mark_dirty();
+        state.undo.push("扫描添加项目", before, &state.store);
+        state.language_history.save();
+    }
+    tracing::info!(
+        added = report.added.len(),
+        updated = report.updated.len(),
+        unchanged = report.unchanged.len(),
+        missing = report.missing.len(),
+        "扫描完成"
+    );
+    Ok(report)
 }
 
-#[tauri::command]
+enum ScannedProject {
+    New(Box<Project>),
+    Existing {
+        key: String,
+        /// 本次扫描未统计语言时为 None
+        language_stats: Option<Option<LanguageStats>>,
+        /// 本次扫描未提取描述或没有可用的描述时为 None
+        auto_description: Option<Box<description::AutoDescription>>,
+        compose_file: Option<String>,
+        system_tags: Vec<String>,
+    },
+}
+
+/// 已有 IDE 使用相同的可执行文件与启动参数时拒绝添加，可执行文件的解析在锁外进行
+#[tauri::command(async)]
 fn add_ide(input: NewIdeInput, state: State<'_, AppState>) -> Result<IdeConfig, String> {
     if input.name.trim().is_empty() {
         return Err("IDE 名称不能为空".to_string());
@@ -1501,8 +2514,11 @@ fn add_ide(input: NewIdeInput, state: State<'_, AppState>) -> Result<IdeConfig,
     if input.executable.trim().is_empty() {
         return Err("可执行文件不能为空".to_string());
     }
+    let website = match input.website.as_deref() {
+        Some(website) => favicon::normalize_website(website)?,
+        None => None,
+    };
 
-    let mut store = state.store.lock().expect("store lock poisoned");
     let ide = IdeConfig {
         id: Uuid::new_v4().to_string(),
         name: input.name.trim().to_string(),
@@ -1514,26 +2530,67 @@ fn add_ide(input: NewIdeInput, state: State<'_, AppState>) -> Result<IdeConfig,
         category: input.category,
         priority: input.priority.unwrap_or(200),
         auto_detected: false,
+        multi_root: input.multi_root.unwrap_or_default(),
+        elevated: false,
+        website,
     };
-    store.ides.push(ide.clone());
-    save_store(&state.file_path, &store)?;
+    if let Some(key) = ide_health::executable_key(&ide.executable) {
+        let existing = state.store.ides().clone();
+        if let Some(duplicate) = existing.iter().find(|i| {
+            i.args_template == ide.args_template
+                && ide_health::executable_key(&i.executable).as_ref() == Some(&key)
+        }) {
+            return Err(format!("已存在相同的 IDE: {}", duplicate.name));
+        }
+    }
+    state.store.ides_mut().push(ide.clone());
+    persist::mark_dirty();
     Ok(ide)
 }
 
-#[tauri::command]
-fn remove_ide(ide_id: String, state: State<'_, AppState>) -> Result<(), String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let before = store.ides.len();
-    store.ides.retain(|x| x.id != ide_id);
-    if store.ides.len() == before {
-        return Err("IDE 不存在".to_string());
+/// 删除 IDE 后清理项目中对它的引用
+fn forget_ide(projects: &mut [Project], ide_id: &str) {
+    for project in projects.iter_mut() {
+        project.metadata.ide_preferences.retain(|x| x != ide_id);
+        project.metadata.launch_steps.retain(|x| x.ide_id != ide_id);
+        project.metadata.elevated_ides.retain(|x| x != ide_id);
+        project.metadata.ide_arg_presets.remove(ide_id);
+        if project.metadata.last_used_ide.as_deref() == Some(ide_id) {
+            project.metadata.last_used_ide = None;
+        }
     }
+}
 
-    for project in &mut store.projects {
-        project.metadata.ide_preferences.retain(|x| x != &ide_id);
+#[tauri::command]
+fn remove_ide(
+    ide_id: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let mut ides = state.store.ides_mut();
+    let before = ides.len();
+    ides.retain(|x| x.id != ide_id);
+    if ides.len() == before {
+        return Err("IDE 不存在".to_string());
     }
+    let name = snapshot
+        .ides
+        .iter()
+        .find(|x| x.id == ide_id)
+        .map(|x| x.name.clone())
+        .unwrap_or_default();
+    forget_ide(&mut projects, &ide_id);
 
-    save_store(&state.file_path, &store)
+    persist::mark_dirty();
+    drop(ides);
+    drop(projects);
+    state
+        .undo
+        .push(&format!("删除 IDE {name}"), snapshot, &state.store);
+    tray::refresh_tray_menu(&app);
+    Ok(())
 }
 
 #[tauri::command]
@@ -1548,63 +2605,198 @@ fn set_ide_icon_from_file(
     }
 
     let icon_data_url = icon_data_url_from_user_file(&path)?;
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let ide = store
-        .ides
+    let mut ides = state.store.ides_mut();
+    let ide = ides
         .iter_mut()
         .find(|x| x.id == ide_id)
         .ok_or_else(|| "IDE 不存在".to_string())?;
     ide.icon = Some(icon_data_url);
     let updated = ide.clone();
-    save_store(&state.file_path, &store)?;
-    Ok(updated)
+    persist::mark_dirty();
+    Ok(ide_for_display(updated))
 }
 
-#[cfg(target_os = "windows")]
+/// 删除 IDE 的下载缓存后重新提取/下载图标，失败时保留原图标
+fn refetch_ide_icon(store_file_path: &Path, ide: &IdeConfig) -> Option<String> {
+    remove_cached_icon(&ide_icon_cache_dir(store_file_path), &ide.id);
+    resolve_ide_icon(store_file_path, ide)
+}
+
+#[tauri::command(async)]
+fn refresh_ide_icon(ide_id: String, state: State<'_, AppState>) -> Result<IdeConfig, String> {
+    let ide = state
+        .store
+        .ides()
+        .iter()
+        .find(|x| x.id == ide_id)
+        .cloned()
+        .ok_or_else(|| "IDE 不存在".to_string())?;
+    // 下载可能较慢，不持有 store 锁
+    let icon = state
+        .background
+        .run(TaskKind::IconDownload, ide.name.clone(), |_| {
+            refetch_ide_icon(&state.file_path, &ide).ok_or_else(|| "未能获取 IDE 图标".to_string())
+        })?;
+
+    let mut ides = state.store.ides_mut();
+    let ide = ides
+        .iter_mut()
+        .find(|x| x.id == ide_id)
+        .ok_or_else(|| "IDE 不存在".to_string())?;
+    ide.icon = Some(icon);
+    let updated = ide.clone();
+    persist::mark_dirty();
+    Ok(ide_for_display(updated))
+}
+
+#[tauri::command(async)]
+fn refresh_all_ide_icons(state: State<'_, AppState>) -> Result<Vec<IdeConfig>, String> {
+    let ides = state.store.ides().clone();
+    let icons: HashMap<String, String> =
+        state
+            .background
+            .run(TaskKind::IconDownload, "全部 IDE 图标", |task| {
+                let mut icons = HashMap::new();
+                for (done, ide) in ides.iter().enumerate() {
+                    if task.is_cancelled() {
+                        break;
+                    }
+                    task.progress(done, ides.len());
+                    if let Some(icon) = refetch_ide_icon(&state.file_path, ide) {
+                        icons.insert(ide.id.clone(), icon);
+                    }
+                }
+                Ok(icons)
+            })?;
+
+    let mut stored = state.store.ides_mut();
+    for ide in stored.iter_mut() {
+        if let Some(icon) = icons.get(&ide.id) {
+            ide.icon = Some(icon.clone());
+        }
+    }
+    persist::mark_dirty();
+    let mut ides: Vec<IdeConfig> = stored.iter().cloned().map(ide_for_display).collect();
+    ides.sort_by_key(|x| x.priority);
+    Ok(ides)
+}
+
+/// 设置 IDE 官网地址，并按新地址重新获取图标
+#[tauri::command(async)]
+fn set_ide_website(
+    ide_id: String,
+    website: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<IdeConfig, String> {
+    let website = match website.as_deref() {
+        Some(website) => favicon::normalize_website(website)?,
+        None => None,
+    };
+    let mut ide = {
+        let mut ides = state.store.ides_mut();
+        let ide = ides
+            .iter_mut()
+            .find(|x| x.id == ide_id)
+            .ok_or_else(|| "IDE 不存在".to_string())?;
+        ide.website = website;
+        let updated = ide.clone();
+        persist::mark_dirty();
+        updated
+    };
+    if ide.website.is_none() {
+        return Ok(ide_for_display(ide));
+    }
+
+    let icon = state
+        .background
+        .run(TaskKind::IconDownload, ide.name.clone(), |_| {
+            Ok(refetch_ide_icon(&state.file_path, &ide))
+        })?;
+    let Some(icon) = icon else {
+        return Ok(ide_for_display(ide));
+    };
+    if let Some(stored) = state.store.ides_mut().iter_mut().find(|x| x.id == ide_id) {
+        stored.icon = Some(icon);
+        ide = stored.clone();
+        persist::mark_dirty();
+    }
+    Ok(ide_for_display(ide))
+}
+
+/// 清空下载的 IDE 图标与站点图标缓存，已保存到 IDE 配置中的图标不受影响
 #[tauri::command]
+fn clear_icon_cache(state: State<'_, AppState>) -> Result<(), String> {
+    for dir in [
+        ide_icon_cache_dir(&state.file_path),
+        project_icon::site_icon_cache_dir(&state.file_path),
+    ] {
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| format!("清除图标缓存失败: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+#[tauri::command(async)]
 fn scan_ides(state: State<'_, AppState>) -> Result<Vec<IdeConfig>, String> {
     let known_ides = get_known_ides();
     let mut detected = vec![];
+    // 手动添加的 IDE 可能与检测结果指向同一个可执行文件（如 `code` 与 Code.exe）
+    let existing_keys: HashSet<String> = state
+        .store
+        .ides()
+        .clone()
+        .iter()
+        .filter_map(|i| ide_health::executable_key(&i.executable))
+        .collect();
 
     for ide_def in known_ides {
         // 检查是否已存在
-        let store = state.store.lock().expect("store lock poisoned");
-        let already_exists = store.ides.iter().any(|i| i.id == ide_def.id);
-        drop(store);
+        let already_exists = state.store.ides().iter().any(|i| i.id == ide_def.id);
 
         if already_exists {
             continue;
         }
 
         // 查找可执行文件：先固定路径，再从 PATH 命令发现
-        let exe_path = resolve_ide_executable(&ide_def);
+        let exe_path = resolve_ide_executable(ide_def).filter(|path| {
+            ide_health::executable_key(&path.to_string_lossy())
+                .is_none_or(|key| !existing_keys.contains(&key))
+        });
 
         if let Some(path) = exe_path {
-            let icon_source = resolve_icon_source_path(&path, ide_def.executable_name);
+            let icon_source = resolve_icon_source_path(&path, &ide_def.executable_name);
             let icon = extract_icon_from_exe(&icon_source).or_else(|| {
                 let placeholder = IdeConfig {
-                    id: ide_def.id.to_string(),
-                    name: ide_def.name.to_string(),
+                    id: ide_def.id.clone(),
+                    name: ide_def.name.clone(),
                     executable: path.to_string_lossy().to_string(),
-                    args_template: ide_def.args_template.to_string(),
+                    args_template: ide_def.args_template.clone(),
                     icon: None,
                     category: ide_def.category.clone(),
                     priority: ide_def.priority,
                     auto_detected: true,
+                    multi_root: multiroot::MultiRootMode::Auto,
+                    elevated: false,
+                    website: None,
                 };
-                load_cached_ide_icon(&state.file_path, ide_def.id)
+                load_cached_ide_icon(&state.file_path, &ide_def.id)
                     .or_else(|| download_and_cache_ide_icon(&state.file_path, &placeholder))
             });
 
             detected.push(IdeConfig {
-                id: ide_def.id.to_string(),
-                name: ide_def.name.to_string(),
+                id: ide_def.id.clone(),
+                name: ide_def.name.clone(),
                 executable: path.to_string_lossy().to_string(),
-                args_template: ide_def.args_template.to_string(),
+                args_template: ide_def.args_template.clone(),
                 icon,
                 category: ide_def.category.clone(),
                 priority: ide_def.priority,
                 auto_detected: true,
+                multi_root: multiroot::MultiRootMode::Auto,
+                elevated: false,
+                website: None,
             });
         }
     }
@@ -1612,7 +2804,7 @@ fn scan_ides(state: State<'_, AppState>) -> Result<Vec<IdeConfig>, String> {
     Ok(detected)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 fn add_detected_ides(state: State<'_, AppState>) -> Result<Vec<IdeConfig>, String> {
     let detected_ides = scan_ides(state.clone())?;
 
@@ -1620,19 +2812,19 @@ fn add_detected_ides(state: State<'_, AppState>) -> Result<Vec<IdeConfig>, Strin
         return Ok(vec![]);
     }
 
-    let mut store = state.store.lock().expect("store lock poisoned");
+    let mut ides = state.store.ides_mut();
     let mut added = vec![];
 
     for ide in detected_ides {
         // 再次检查是否已存在（防止竞态条件）
-        if !store.ides.iter().any(|i| i.id == ide.id) {
-            store.ides.push(ide.clone());
+        if !ides.iter().any(|i| i.id == ide.id) {
+            ides.push(ide.clone());
             added.push(ide);
         }
     }
 
     if !added.is_empty() {
-        save_store(&state.file_path, &store)?;
+        persist::mark_dirty();
     }
 
     Ok(added)
@@ -1642,10 +2834,12 @@ fn add_detected_ides(state: State<'_, AppState>) -> Result<Vec<IdeConfig>, Strin
 fn set_project_ide_preferences(
     project_id: String,
     ide_ids: Vec<String>,
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
 ) -> Result<Project, String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let valid_ide_ids: HashSet<&str> = store.ides.iter().map(|i| i.id.as_str()).collect();
+    let mut projects = state.store.projects_mut();
+    let ides = state.store.ides();
+    let valid_ide_ids: HashSet<&str> = ides.iter().map(|i| i.id.as_str()).collect();
 
     let mut seen: HashSet<String> = HashSet::new();
     let mut normalized: Vec<String> = ide_ids
@@ -1655,23 +2849,83 @@ fn set_project_ide_preferences(
         .collect();
     normalized.truncate(3);
 
-    let project = store
-        .projects
+    let project = projects
         .iter_mut()
         .find(|p| p.id == project_id)
         .ok_or_else(|| "项目不存在".to_string())?;
 
     project.metadata.ide_preferences = normalized;
     let updated = project.clone();
-    save_store(&state.file_path, &store)?;
+    persist::mark_dirty();
+    drop(ides);
+    drop(projects);
+    tray::refresh_tray_menu(&app);
+    Ok(updated)
+}
+
+/// 设置首选 IDE 依次启动时的延迟与等待，只保留首选列表中的 IDE
+#[tauri::command]
+fn set_project_launch_steps(
+    project_id: String,
+    steps: Vec<LaunchStep>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    let mut seen: HashSet<String> = HashSet::new();
+    project.metadata.launch_steps = steps
+        .into_iter()
+        .filter(|step| project.metadata.ide_preferences.contains(&step.ide_id))
+        .filter(|step| seen.insert(step.ide_id.clone()))
+        .map(|step| LaunchStep {
+            delay_ms: step.delay_ms.min(MAX_LAUNCH_DELAY_MS),
+            ..step
+        })
+        .collect();
+    let updated = project.clone();
+    persist::mark_dirty();
     Ok(updated)
 }
 
-fn launch_with_ide(project: &Project, ide: &IdeConfig) -> Result<(), String> {
-    let args = expand_args(&ide.args_template, project);
-    let mut launched = false;
+fn launch_with_ide(
+    project: &Project,
+    ide: &IdeConfig,
+    relative_file: Option<&str>,
+) -> Result<Option<Child>, String> {
+    let ide = &arg_presets::effective_ide(project, ide);
+    let mut args = expand_args(&ide.args_template, project, ide, relative_file);
+    workspace::apply_workspace(project, ide, &mut args);
+    multiplexer::apply(project, ide, &mut args)?;
+    multiroot::launch(project, ide, args)
+}
+
+/// 返回启动的进程，用于统计使用时长；以管理员身份启动时可能拿不到进程
+fn spawn_ide(
+    project: &Project,
+    ide: &IdeConfig,
+    args: Vec<String>,
+) -> Result<Option<Child>, String> {
+    if elevate::should_elevate(project, ide) {
+        let child = elevate::spawn_elevated(project, ide, &args).map_err(|e| {
+            tracing::error!(
+                project = %project.name,
+                ide = %ide.name,
+                error = %e,
+                "提权启动 IDE 失败"
+            );
+            format!("启动 {} 失败: {e}", ide.name)
+        })?;
+        tracing::info!(project = %project.name, ide = %ide.name, "已以管理员身份启动 IDE");
+        return Ok(child);
+    }
+
+    let mut launched = None;
+    let is_cli = ide.category == IdeCategory::Cli || ide.category == IdeCategory::Terminal;
 
-    if ide.category == IdeCategory::Cli || ide.category == IdeCategory::Terminal {
+    if is_cli {
         #[cfg(target_os = "windows")]
         {
             let mut wt = Command::new("wt");
@@ -1679,29 +2933,312 @@ fn launch_with_ide(project: &Project, ide: &IdeConfig) -> Result<(), String> {
                 .arg(&project.path)
                 .arg(&ide.executable)
                 .args(&args);
-            if wt.spawn().is_ok() {
-                launched = true;
+            envfile::apply_env_file(&mut wt, project);
+            version_manager::apply_activation(&mut wt, project);
+            match wt.spawn() {
+                Ok(child) => launched = Some(child),
+                Err(err) => tracing::warn!(
+                    ide = %ide.name,
+                    error = %err,
+                    "通过 Windows Terminal 启动失败，改为直接启动"
+                ),
+            }
+        }
+    }
+
+    let child = match launched {
+        Some(child) => child,
+        None => {
+            let mut command = Command::new(&ide.executable);
+            command.current_dir(&project.path).args(args);
+            process::detach(&mut command);
+            envfile::apply_env_file(&mut command, project);
+            if is_cli {
+                version_manager::apply_activation(&mut command, project);
+            }
+            process::spawn_checked(command, LAUNCH_CHECK_WINDOW).map_err(|e| {
+                tracing::error!(
+                    project = %project.name,
+                    ide = %ide.name,
+                    executable = %ide.executable,
+                    error = %e,
+                    "启动 IDE 失败"
+                );
+                format!("启动 {} 失败: {e}", ide.name)
+            })?
+        }
+    };
+
+    tracing::info!(project = %project.name, ide = %ide.name, "已启动 IDE");
+    Ok(Some(child))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GotoStyle {
+    VsCode,
+    JetBrains,
+    Vim,
+    Plain,
+}
+
+fn goto_style_for_ide(ide: &IdeConfig) -> GotoStyle {
+    let id = ide.id.to_ascii_lowercase();
+    let stem = Path::new(&ide.executable)
+        .file_stem()
+        .and_then(|v| v.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    // JetBrains 在 Windows 上的可执行文件带 64 后缀，如 idea64.exe
+    let stem = stem.strip_suffix("64").unwrap_or(&stem);
+    // 只按 id 与可执行文件名精确匹配，避免 visual-studio 之类的名称被误判
+    let is = |names: &[&str]| names.contains(&id.as_str()) || names.contains(&stem);
+
+    if is(&["vscode", "code", "cursor", "windsurf", "codium", "positron"]) {
+        return GotoStyle::VsCode;
+    }
+    if is(&[
+        "intellij",
+        "idea",
+        "webstorm",
+        "pycharm",
+        "clion",
+        "goland",
+        "rider",
+        "fleet",
+        "android-studio",
+        "studio",
+        "rustrover",
+        "phpstorm",
+        "rubymine",
+        "datagrip",
+    ]) {
+        return GotoStyle::JetBrains;
+    }
+    if is(&["vim", "nvim", "neovim", "gvim"]) {
+        return GotoStyle::Vim;
+    }
+    GotoStyle::Plain
+}
+
+fn goto_args(
+    ide: &IdeConfig,
+    project: &Project,
+    file_path: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+) -> Vec<String> {
+    match goto_style_for_ide(ide) {
+        GotoStyle::VsCode => {
+            let mut target = file_path.to_string();
+            if let Some(line) = line {
+                target.push_str(&format!(":{line}"));
+                if let Some(column) = column {
+                    target.push_str(&format!(":{column}"));
+                }
+            }
+            let root =
+                workspace::launch_workspace(project, ide).unwrap_or_else(|| project.path.clone());
+            vec![root, "-g".to_string(), target]
+        }
+        GotoStyle::JetBrains => {
+            let mut args = vec![project.path.clone()];
+            if let Some(line) = line {
+                args.push("--line".to_string());
+                args.push(line.to_string());
+                if let Some(column) = column {
+                    args.push("--column".to_string());
+                    args.push(column.to_string());
+                }
             }
+            args.push(file_path.to_string());
+            args
         }
+        GotoStyle::Vim => {
+            let mut args = vec![];
+            if let Some(line) = line {
+                match column {
+                    Some(column) => args.push(format!("+call cursor({line}, {column})")),
+                    None => args.push(format!("+{line}")),
+                }
+            }
+            args.push(file_path.to_string());
+            args
+        }
+        GotoStyle::Plain => vec![file_path.to_string()],
     }
+}
 
-    if !launched {
-        Command::new(&ide.executable)
-            .current_dir(&project.path)
-            .args(args)
-            .spawn()
-            .map_err(|e| format!("启动 {} 失败: {e}", ide.name))?;
+fn resolve_file_in_project(project: &Project, relative_file: &str) -> Result<PathBuf, String> {
+    let relative = relative_file.trim();
+    if relative.is_empty() {
+        return Err("文件路径不能为空".to_string());
+    }
+    let root = PathBuf::from(&project.path)
+        .canonicalize()
+        .map_err(|e| format!("无法读取项目路径: {e}"))?;
+    let target = root
+        .join(relative)
+        .canonicalize()
+        .map_err(|_| "文件不存在".to_string())?;
+    if !target.starts_with(&root) {
+        return Err("文件不在项目目录内".to_string());
+    }
+    if !target.is_file() {
+        return Err("目标不是文件".to_string());
     }
+    Ok(PathBuf::from(normalize_windows_path_for_ui(
+        &target.to_string_lossy(),
+    )))
+}
 
-    Ok(())
+#[tauri::command(async)]
+fn launch_project_at(
+    project_id: String,
+    relative_file: String,
+    line: Option<u32>,
+    column: Option<u32>,
+    ide_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let project = find_project(&state, &project_id)?;
+    let ides = state.store.ides();
+    let file_path = resolve_file_in_project(&project, &relative_file)?;
+
+    // 未指定 IDE 时按启动方式取上次使用或首选的 IDE，再回退到优先级最高的 IDE
+    let explicit = ide_id.is_some();
+    let ide = match ide_id {
+        Some(requested) => ides.iter().find(|i| i.id == requested).cloned(),
+        None => default_launch_ides(&state, &project, &ides)
+            .into_iter()
+            .next()
+            .or_else(|| ides.iter().min_by_key(|i| i.priority).cloned()),
+    }
+    .ok_or_else(|| "IDE 不存在".to_string())?;
+    // 启动检查需要等待，期间不占用 store 锁
+    drop(ides);
+
+    // 参数模板中使用了 `{relativeFile}` 时按模板启动，由模板决定文件参数的位置
+    let uses_template = arg_presets::effective_ide(&project, &ide)
+        .args_template
+        .contains("{relativeFile}");
+    let child = if uses_template {
+        launch_with_ide(&project, &ide, Some(relative_file.trim()))
+    } else {
+        let args = goto_args(
+            &ide,
+            &project,
+            &file_path.to_string_lossy(),
+            line.filter(|v| *v > 0),
+            column.filter(|v| *v > 0),
+        );
+        spawn_ide(&project, &ide, args)
+    }
+    .inspect_err(|err| {
+        record_launch_failure(&state, &project.id, &ide.id, err);
+    })?;
+    if let Some(child) = child {
+        state.sessions.watch(&project, &ide, child);
+    }
+    record_launches(&state, &project.id, &[&ide.id]);
+    mark_opened(&state, &project.id, explicit.then_some(ide.id.as_str()))
+}
+
+/// 手动修改描述，之后不再被自动描述覆盖；清空后可重新自动填写
+#[tauri::command]
+fn update_project_description(
+    project_id: String,
+    description: Option<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.metadata.description = description
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    project.metadata.description_source = None;
+    let updated = project.clone();
+    persist::mark_dirty();
+    drop(projects);
+    fulltext::start_indexing(&app);
+    Ok(updated)
+}
+
+#[tauri::command]
+fn update_project_notes(
+    project_id: String,
+    notes: Option<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.notes = notes.filter(|v| !v.trim().is_empty());
+    let updated = project.clone();
+    persist::mark_dirty();
+    drop(projects);
+    fulltext::start_indexing(&app);
+    Ok(updated)
+}
+
+/// 别名不区分大小写，不能与其它项目的别名重复
+#[tauri::command]
+fn update_project_aliases(
+    project_id: String,
+    aliases: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut normalized: Vec<String> = vec![];
+    for alias in aliases {
+        let alias = alias.trim().to_string();
+        if alias.is_empty() || normalized.iter().any(|a| a.eq_ignore_ascii_case(&alias)) {
+            continue;
+        }
+        if alias.chars().any(char::is_whitespace) {
+            return Err(format!("别名不能包含空格: {alias}"));
+        }
+        normalized.push(alias);
+    }
+    let mut projects = state.store.projects_mut();
+    if let Some((other, alias)) = projects
+        .iter()
+        .filter(|p| p.id != project_id)
+        .find_map(|p| {
+            let alias = normalized
+                .iter()
+                .find(|a| p.aliases.iter().any(|b| b.eq_ignore_ascii_case(a)))?;
+            Some((p.name.clone(), alias.clone()))
+        })
+    {
+        return Err(format!("别名 {alias} 已被项目 {other} 使用"));
+    }
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.aliases = normalized;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
 }
 
 #[tauri::command]
-fn reorder_projects(project_ids: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
+fn reorder_projects(
+    project_ids: Vec<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
     if project_ids.is_empty() {
         return Ok(());
     }
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
 
     let mut rank = std::collections::HashMap::new();
     for (idx, id) in project_ids.iter().enumerate() {
@@ -1709,74 +3246,190 @@ fn reorder_projects(project_ids: Vec<String>, state: State<'_, AppState>) -> Res
     }
 
     let mut max_rank = rank.len() as i64 + 1;
-    for project in &mut store.projects {
+    for project in projects.iter_mut() {
         if let Some(order) = rank.get(&project.id) {
             project.display_order = *order;
         } else {
-            project.display_order = max_rank;
+            project.display_order = max_rank;
+            max_rank += 1;
+        }
+    }
+
+    persist::mark_dirty();
+    drop(projects);
+    state.undo.push("调整项目顺序", snapshot, &state.store);
+    tray::refresh_tray_menu(&app);
+    Ok(())
+}
+
+/// 按列表顺序重写 IDE 优先级，未列出的 IDE 排在后面并保持原有相对顺序
+#[tauri::command]
+fn reorder_ides(
+    ide_ids: Vec<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if ide_ids.is_empty() {
+        return Ok(());
+    }
+    let snapshot = state.store.snapshot();
+    let mut ides = state.store.ides_mut();
+
+    let mut rank = std::collections::HashMap::new();
+    for (idx, id) in ide_ids.iter().enumerate() {
+        rank.insert(id.clone(), idx as i32 + 1);
+    }
+
+    ides.sort_by_key(|ide| ide.priority);
+    let mut max_rank = rank.len() as i32 + 1;
+    for ide in ides.iter_mut() {
+        if let Some(priority) = rank.get(&ide.id) {
+            ide.priority = *priority;
+        } else {
+            ide.priority = max_rank;
             max_rank += 1;
         }
     }
+    ides.sort_by_key(|ide| ide.priority);
 
-    save_store(&state.file_path, &store)
+    persist::mark_dirty();
+    drop(ides);
+    state.undo.push("调整 IDE 顺序", snapshot, &state.store);
+    tray::refresh_tray_menu(&app);
+    Ok(())
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 fn launch_project(
     project_id: String,
     ide_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-    let project_idx = store
-        .projects
-        .iter()
-        .position(|p| p.id == project_id)
-        .ok_or_else(|| "项目不存在".to_string())?;
-    let project = store.projects[project_idx].clone();
+    let project = find_project(&state, &project_id)?;
+    let ides = state.store.ides();
 
+    let explicit = ide_id.is_some();
     let selected_ides: Vec<IdeConfig> = if let Some(requested) = ide_id {
-        vec![store
-            .ides
+        vec![ides
             .iter()
             .find(|i| i.id == requested)
             .cloned()
             .ok_or_else(|| "IDE 不存在".to_string())?]
     } else {
-        let preferred: Vec<IdeConfig> = project
-            .metadata
-            .ide_preferences
-            .iter()
-            .take(3)
-            .filter_map(|preferred_id| store.ides.iter().find(|i| i.id == *preferred_id).cloned())
-            .collect();
+        let preferred = default_launch_ides(&state, &project, &ides);
         if !preferred.is_empty() {
             preferred
         } else {
-            vec![store
-                .ides
+            vec![ides
                 .iter()
                 .min_by_key(|i| i.priority)
                 .cloned()
                 .ok_or_else(|| "没有可用 IDE，请先添加 IDE 配置".to_string())?]
         }
     };
+    // 启动检查需要等待，期间不占用 store 锁
+    drop(ides);
 
-    let mut launched_count = 0usize;
+    let mut launched: Vec<&str> = Vec::new();
     let mut errors: Vec<String> = Vec::new();
-    for ide in &selected_ides {
-        match launch_with_ide(&project, ide) {
-            Ok(()) => launched_count += 1,
-            Err(err) => errors.push(err),
+    let mut previous: Option<(Child, &IdeConfig)> = None;
+    for (idx, ide) in selected_ides.iter().enumerate() {
+        // 第一个 IDE 立即启动
+        if let Some(step) = project
+            .metadata
+            .launch_steps
+            .iter()
+            .find(|s| s.ide_id == ide.id)
+            .filter(|_| idx > 0)
+        {
+            if step.wait_for_previous {
+                if let Some((child, _)) = previous.as_mut() {
+                    wait_until_ready(child);
+                }
+            }
+            if step.delay_ms > 0 {
+                std::thread::sleep(Duration::from_millis(
+                    step.delay_ms.min(MAX_LAUNCH_DELAY_MS),
+                ));
+            }
+        }
+        if let Some((child, previous_ide)) = previous.take() {
+            state.sessions.watch(&project, previous_ide, child);
+        }
+        match launch_with_ide(&project, ide, None) {
+            Ok(child) => {
+                previous = child.map(|child| (child, ide));
+                launched.push(&ide.id);
+            }
+            Err(err) => {
+                record_launch_failure(&state, &project.id, &ide.id, &err);
+                errors.push(err);
+            }
         }
     }
 
-    if launched_count == 0 {
+    if let Some((child, ide)) = previous {
+        state.sessions.watch(&project, ide, child);
+    }
+
+    if launched.is_empty() {
         return Err(errors.join("；"));
     }
+    record_launches(&state, &project.id, &launched);
+    mark_opened(&state, &project.id, explicit.then(|| launched[0]))
+}
+
+/// 未指定 IDE 时启动的 IDE：LastUsed 模式下优先上次指定的 IDE，否则取最多 3 个首选 IDE
+fn default_launch_ides(state: &AppState, project: &Project, ides: &[IdeConfig]) -> Vec<IdeConfig> {
+    let mode = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .launch_mode;
+    if mode == settings::LaunchMode::LastUsed {
+        if let Some(ide) = project
+            .metadata
+            .last_used_ide
+            .as_ref()
+            .and_then(|last| ides.iter().find(|i| i.id == *last))
+        {
+            return vec![ide.clone()];
+        }
+    }
+    project
+        .metadata
+        .ide_preferences
+        .iter()
+        .take(3)
+        .filter_map(|preferred_id| ides.iter().find(|i| i.id == *preferred_id).cloned())
+        .collect()
+}
+
+/// 启动器（如 `code`、`wt`）把工作交给主进程后退出即视为就绪，超时后不再等待
+fn wait_until_ready(child: &mut Child) {
+    let started = std::time::Instant::now();
+    while started.elapsed() < LAUNCH_READY_TIMEOUT {
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
 
-    store.projects[project_idx].last_opened = Some(now_iso());
-    save_store(&state.file_path, &store)?;
+/// explicit_ide 为用户明确指定的 IDE，记为该项目上次使用的 IDE
+fn mark_opened(
+    state: &AppState,
+    project_id: &str,
+    explicit_ide: Option<&str>,
+) -> Result<(), String> {
+    let mut projects = state.store.projects_mut();
+    if let Some(project) = projects.iter_mut().find(|p| p.id == project_id) {
+        project.last_opened = Some(now_iso());
+        if let Some(ide_id) = explicit_ide {
+            project.metadata.last_used_ide = Some(ide_id.to_string());
+        }
+    }
+    persist::mark_dirty();
     Ok(())
 }
 
@@ -1784,150 +3437,36 @@ fn launch_project(
 fn open_in_file_manager(path: String) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
-        Command::new("explorer")
-            .arg(path)
-            .spawn()
-            .map_err(|e| format!("打开文件夹失败: {e}"))?;
+        let mut command = Command::new("explorer");
+        command.arg(path);
+        process::spawn_detached(command).map_err(|e| format!("打开文件夹失败: {e}"))?;
         return Ok(());
     }
     #[cfg(target_os = "macos")]
     {
-        Command::new("open")
-            .arg(path)
-            .spawn()
-            .map_err(|e| format!("打开文件夹失败: {e}"))?;
+        let mut command = Command::new("open");
+        command.arg(path);
+        process::spawn_detached(command).map_err(|e| format!("打开文件夹失败: {e}"))?;
         return Ok(());
     }
     #[cfg(target_os = "linux")]
     {
-        Command::new("xdg-open")
-            .arg(path)
-            .spawn()
-            .map_err(|e| format!("打开文件夹失败: {e}"))?;
+        let mut command = Command::new("xdg-open");
+        command.arg(path);
+        process::spawn_detached(command).map_err(|e| format!("打开文件夹失败: {e}"))?;
         return Ok(());
     }
     #[allow(unreachable_code)]
     Err("当前系统不支持打开文件管理器".to_string())
 }
 
-#[tauri::command]
-fn open_in_terminal(path: String) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-
-        // 方案1: 直接启动 PowerShell，使用 CREATE_NEW_CONSOLE 标志创建新窗口
-        let result = Command::new("powershell")
-            .args([
-                "-NoExit",
-                "-NoLogo",
-                "-Command",
-                &format!("Set-Location '{}'", &path),
-            ])
-            .creation_flags(0x00000010) // CREATE_NEW_CONSOLE
-            .spawn();
-
-        if result.is_ok() {
-            return Ok(());
-        }
-
-        // 方案2: Windows Terminal - 默认就是新窗口
-        let result = Command::new("wt")
-            .args([
-                "powershell",
-                "-NoExit",
-                "-NoLogo",
-                "-Command",
-                &format!("Set-Location '{}'", &path),
-            ])
-            .spawn();
-
-        if result.is_ok() {
-            return Ok(());
-        }
-
-        // 方案3: CMD with CREATE_NEW_CONSOLE
-        let result = Command::new("cmd")
-            .args(["/k", &format!("cd /d \"{}\"", &path)])
-            .creation_flags(0x00000010) // CREATE_NEW_CONSOLE
-            .spawn();
-
-        if result.is_ok() {
-            return Ok(());
-        }
-
-        return Err("无法打开终端".to_string());
-    }
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg("-a")
-            .arg("Terminal")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("打开终端失败: {e}"))?;
-        return Ok(());
-    }
-    #[cfg(target_os = "linux")]
-    {
-        // 尝试常见的 Linux 终端，使用 --new-window 或直接启动新实例
-        let terminals = [
-            ("gnome-terminal", vec!["--working-directory", &path]),
-            ("konsole", vec!["--new-window", "--workdir", &path]),
-            ("xfce4-terminal", vec!["--working-directory", &path]),
-            ("xterm", vec!["-e", &format!("cd '{}'", &path)]),
-        ];
-
-        for (term, args) in terminals {
-            let mut cmd = Command::new(term);
-            for arg in args {
-                cmd.arg(arg);
-            }
-            if cmd.spawn().is_ok() {
-                return Ok(());
-            }
-        }
-        return Err("未找到可用的终端".to_string());
-    }
-    #[allow(unreachable_code)]
-    Err("当前系统不支持打开终端".to_string())
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MiniWindowPosition {
-    x: i32,
-    y: i32,
-}
-
-#[tauri::command]
-fn save_mini_window_position(x: i32, y: i32, state: State<'_, AppState>) -> Result<(), String> {
-    let pos_path = state
-        .file_path
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .join("mini-window-position.json");
-    let pos = MiniWindowPosition { x, y };
-    let content = serde_json::to_string(&pos).map_err(|e| e.to_string())?;
-    fs::write(pos_path, content).map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn load_mini_window_position(state: State<'_, AppState>) -> Option<MiniWindowPosition> {
-    let pos_path = state
-        .file_path
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .join("mini-window-position.json");
-    let content = fs::read_to_string(pos_path).ok()?;
-    serde_json::from_str(&content).ok()
-}
-
 #[tauri::command]
 fn switch_to_mini_window(app: tauri::AppHandle) -> Result<(), String> {
     if let Some(main_win) = app.get_webview_window("main") {
         main_win.hide().map_err(|e| e.to_string())?;
     }
     if let Some(mini_win) = app.get_webview_window("mini") {
+        window_state::restore_mini_window_position(&mini_win);
         mini_win.show().map_err(|e| e.to_string())?;
         mini_win.set_focus().map_err(|e| e.to_string())?;
     }
@@ -1946,56 +3485,6 @@ fn switch_to_main_window(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-// 文件扩展名到语言的映射
-fn get_language_from_extension(ext: &str) -> Option<&'static str> {
-    let ext_lower = ext.to_ascii_lowercase();
-    match ext_lower.as_str() {
-        // Rust
-        "rs" => Some("Rust"),
-        // JavaScript/TypeScript
-        "js" | "jsx" | "mjs" => Some("JavaScript"),
-        "ts" | "tsx" => Some("TypeScript"),
-        // Python
-        "py" | "pyi" | "pyw" => Some("Python"),
-        // Java
-        "java" | "kt" | "kts" => Some("Java"),
-        // Go
-        "go" => Some("Go"),
-        // C/C++
-        "c" | "h" => Some("C"),
-        "cpp" | "cc" | "cxx" | "hpp" | "hxx" => Some("C++"),
-        // C#
-        "cs" => Some("C#"),
-        // Web
-        "html" | "htm" | "xhtml" => Some("HTML"),
-        "css" | "scss" | "sass" | "less" => Some("CSS"),
-        "vue" => Some("Vue"),
-        "svelte" => Some("Svelte"),
-        // Shell
-        "sh" | "bash" | "zsh" | "fish" => Some("Shell"),
-        "ps1" | "psm1" | "psd1" => Some("PowerShell"),
-        // Config/Data
-        "json" => Some("JSON"),
-        "yaml" | "yml" => Some("YAML"),
-        "toml" => Some("TOML"),
-        "xml" => Some("XML"),
-        "md" | "markdown" => Some("Markdown"),
-        // Other
-        "sql" => Some("SQL"),
-        "rb" => Some("Ruby"),
-        "php" => Some("PHP"),
-        "swift" => Some("Swift"),
-        "dart" => Some("Dart"),
-        "r" => Some("R"),
-        "scala" => Some("Scala"),
-        "lua" => Some("Lua"),
-        "ex" | "exs" => Some("Elixir"),
-        "erl" | "hrl" => Some("Erlang"),
-        "fs" | "fsi" | "fsx" => Some("F#"),
-        _ => None,
-    }
-}
-
 // 检查目录是否应该被跳过
 fn should_skip_dir_for_stats(path: &Path) -> bool {
     let skip = [
@@ -2034,25 +3523,213 @@ fn should_skip_dir_for_stats(path: &Path) -> bool {
     }
 }
 
-// 统计单个文件的语言信息
-fn count_file_lines(path: &Path) -> Option<(&'static str, u64)> {
-    let ext = path.extension()?.to_str()?;
-    let language = get_language_from_extension(ext)?;
+#[derive(Debug, Default, Clone, Copy)]
+struct LanguageTotals {
+    code: u64,
+    comments: u64,
+    blanks: u64,
+    files: u32,
+}
+
+/// 单个文件的统计结果，按 mtime + size 判断是否需要重新统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedFileStats {
+    mtime_ns: u64,
+    size: u64,
+    language: String,
+    code: u64,
+    comments: u64,
+    blanks: u64,
+}
+
+/// 某个项目的逐文件统计缓存，key 为相对项目根目录的路径
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct LanguageCache {
+    /// 统计相关设置的指纹，设置变化后整个缓存失效
+    #[serde(default)]
+    settings_fingerprint: String,
+    files: HashMap<String, CachedFileStats>,
+}
+
+fn language_cache_dir(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("language-cache")
+}
+
+// FNV-1a，保证同一路径在不同版本间得到相同的缓存文件名
+fn stable_hash(value: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in value.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn language_cache_path(store_file_path: &Path, project_path: &Path) -> PathBuf {
+    let canonical = project_path
+        .canonicalize()
+        .map(|p| normalize_windows_path_for_ui(&p.to_string_lossy()))
+        .unwrap_or_else(|_| project_path.to_string_lossy().to_string());
+    language_cache_dir(store_file_path).join(format!("{}.json", stable_hash(&canonical)))
+}
+
+fn load_language_cache(path: &Path) -> LanguageCache {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_language_cache(path: &Path, cache: &LanguageCache) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string(cache) {
+        let _ = fs::write(path, content);
+    }
+}
+
+fn file_mtime_ns(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+// 统计单个文件的语言信息：先查用户自定义映射，再交给 tokei 识别；
+// 自定义语言名 tokei 不认识时按非空行计为代码
+fn count_file_lines(
+    path: &Path,
+    config: &tokei::Config,
+    language_settings: &settings::LanguageStatsSettings,
+) -> Option<(String, tokei::CodeStats)> {
+    if let Some(custom) = language_settings.language_for(path) {
+        if looks_binary(path) {
+            return None;
+        }
+        if let Ok(language) = custom.parse::<tokei::LanguageType>() {
+            let content = fs::read(path).ok()?;
+            let stats = language.parse_from_slice(content, config).summarise();
+            return Some((custom.to_string(), stats));
+        }
+        return Some((custom.to_string(), count_plain_lines(path)?));
+    }
+
+    let language = tokei::LanguageType::from_path(path, config)?;
+    if looks_binary(path) {
+        return None;
+    }
+    let content = fs::read(path).ok()?;
+    let stats = language.parse_from_slice(content, config).summarise();
+    Some((language.name().to_string(), stats))
+}
+
+/// 逐行读取，不把整个文件载入内存
+fn count_plain_lines(path: &Path) -> Option<tokei::CodeStats> {
+    use std::io::BufRead;
+
+    let mut reader = std::io::BufReader::new(fs::File::open(path).ok()?);
+    let mut stats = tokei::CodeStats::new();
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                if line.iter().all(|b| b.is_ascii_whitespace()) {
+                    stats.blanks += 1;
+                } else {
+                    stats.code += 1;
+                }
+            }
+            Err(_) => return None,
+        }
+    }
+    Some(stats)
+}
+
+/// 开头 8KB 中含有 NUL 字节的视为二进制文件
+fn looks_binary(path: &Path) -> bool {
+    use std::io::Read;
 
-    // 读取文件内容并计算行数
-    let content = fs::read_to_string(path).ok()?;
-    let lines = content.lines().count() as u64;
+    let Ok(file) = fs::File::open(path) else {
+        return true;
+    };
+    let mut head = Vec::with_capacity(8192);
+    if file.take(8192).read_to_end(&mut head).is_err() {
+        return true;
+    }
+    head.contains(&0)
+}
 
-    Some((language, lines))
+/// 压缩后的前端产物与 source map 会严重夸大行数
+fn is_minified_bundle(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".map") || name.ends_with(".min.js") || name.ends_with(".min.css")
 }
 
-// 递归扫描项目目录统计语言
-fn scan_project_languages(path: &Path) -> Result<HashMap<String, (u64, u32)>, String> {
-    let mut language_data: HashMap<String, (u64, u32)> = HashMap::new();
+// 递归扫描项目目录统计语言；mtime/size 未变化的文件直接复用缓存结果，
+// 扫描结束后 cache 只保留本次仍存在的文件
+fn scan_project_languages(
+    path: &Path,
+    cache: &mut LanguageCache,
+    language_settings: &settings::LanguageStatsSettings,
+) -> Result<HashMap<String, LanguageTotals>, String> {
+    struct ScanContext<'a> {
+        root: &'a Path,
+        config: tokei::Config,
+        language_settings: &'a settings::LanguageStatsSettings,
+        previous: HashMap<String, CachedFileStats>,
+        current: HashMap<String, CachedFileStats>,
+        language_data: HashMap<String, LanguageTotals>,
+    }
+
+    fn file_stats(ctx: &mut ScanContext, path: &Path) -> Option<CachedFileStats> {
+        let metadata = fs::metadata(path).ok()?;
+        let mtime_ns = file_mtime_ns(&metadata);
+        let size = metadata.len();
+        let key = path
+            .strip_prefix(ctx.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+
+        if let Some(cached) = ctx.previous.remove(&key) {
+            if cached.mtime_ns == mtime_ns && cached.size == size {
+                ctx.current.insert(key, cached.clone());
+                return Some(cached);
+            }
+        }
+
+        if !ctx.language_settings.within_size_limit(path, size) || is_minified_bundle(path) {
+            return None;
+        }
+        let (language, stats) = count_file_lines(path, &ctx.config, ctx.language_settings)?;
+        let fresh = CachedFileStats {
+            mtime_ns,
+            size,
+            language,
+            code: stats.code as u64,
+            comments: stats.comments as u64,
+            blanks: stats.blanks as u64,
+        };
+        ctx.current.insert(key, fresh.clone());
+        Some(fresh)
+    }
 
     fn scan_dir(
+        ctx: &mut ScanContext,
         dir: &Path,
-        language_data: &mut HashMap<String, (u64, u32)>,
         depth: u32,
         max_depth: u32,
     ) -> Result<(), String> {
@@ -2068,12 +3745,14 @@ fn scan_project_languages(path: &Path) -> Result<HashMap<String, (u64, u32)>, St
             let path = entry.path();
 
             if path.is_dir() {
-                scan_dir(&path, language_data, depth + 1, max_depth)?;
+                scan_dir(ctx, &path, depth + 1, max_depth)?;
             } else if path.is_file() {
-                if let Some((language, lines)) = count_file_lines(&path) {
-                    let entry = language_data.entry(language.to_string()).or_insert((0, 0));
-                    entry.0 += lines;
-                    entry.1 += 1;
+                if let Some(stats) = file_stats(ctx, &path) {
+                    let totals = ctx.language_data.entry(stats.language).or_default();
+                    totals.code += stats.code;
+                    totals.comments += stats.comments;
+                    totals.blanks += stats.blanks;
+                    totals.files += 1;
                 }
             }
         }
@@ -2081,82 +3760,291 @@ fn scan_project_languages(path: &Path) -> Result<HashMap<String, (u64, u32)>, St
         Ok(())
     }
 
-    scan_dir(path, &mut language_data, 0, 50)?;
-    Ok(language_data)
+    let mut ctx = ScanContext {
+        root: path,
+        config: tokei::Config::default(),
+        language_settings,
+        previous: std::mem::take(&mut cache.files),
+        current: HashMap::new(),
+        language_data: HashMap::new(),
+    };
+    scan_dir(&mut ctx, path, 0, 50)?;
+    cache.files = ctx.current;
+    Ok(ctx.language_data)
 }
 
-#[tauri::command]
-fn scan_project_language_stats(
-    project_id: String,
-    state: State<'_, AppState>,
-) -> Result<LanguageStats, String> {
-    let mut store = state.store.lock().expect("store lock poisoned");
-
-    let project = store
-        .projects
-        .iter()
-        .find(|p| p.id == project_id)
-        .ok_or_else(|| "项目不存在".to_string())?;
-
-    let project_path = Path::new(&project.path);
-    if !project_path.exists() || !project_path.is_dir() {
-        return Err("项目路径不存在或不是目录".to_string());
+fn percentage_of(lines: u64, total_lines: u64) -> f64 {
+    if total_lines > 0 {
+        (lines as f64 / total_lines as f64) * 100.0
+    } else {
+        0.0
     }
+}
 
-    // 扫描语言统计
-    let language_data =
-        scan_project_languages(project_path).map_err(|e| format!("扫描语言统计失败: {}", e))?;
+fn language_entries(language_data: HashMap<String, LanguageTotals>) -> Vec<LanguageEntry> {
+    let total_lines: u64 = language_data.values().map(|t| t.code).sum();
+    let mut languages: Vec<LanguageEntry> = language_data
+        .into_iter()
+        .filter(|(_, totals)| totals.code > 0 || totals.comments > 0)
+        .map(|(language, totals)| LanguageEntry {
+            language,
+            lines: totals.code,
+            comments: totals.comments,
+            blanks: totals.blanks,
+            files: totals.files,
+            percentage: percentage_of(totals.code, total_lines),
+        })
+        .collect();
 
-    let total_lines: u64 = language_data.values().map(|(lines, _)| *lines).sum();
+    // 按代码行数降序排序
+    languages.sort_by_key(|entry| std::cmp::Reverse(entry.lines));
+    languages
+}
 
-    let mut languages: Vec<LanguageEntry> = language_data
+/// 按文件所在目录的前 depth 层分组，如 depth 为 2 时 `src/api/mod.rs` 归入 `src/api`
+fn directory_breakdown(
+    files: &HashMap<String, CachedFileStats>,
+    depth: u32,
+    total_lines: u64,
+) -> Vec<DirectoryStats> {
+    let mut groups: HashMap<String, HashMap<String, LanguageTotals>> = HashMap::new();
+    for (relative, stats) in files {
+        let relative = Path::new(relative);
+        let dirs: Vec<String> = relative
+            .parent()
+            .map(|parent| {
+                parent
+                    .components()
+                    .take(depth as usize)
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let totals = groups
+            .entry(dirs.join("/"))
+            .or_default()
+            .entry(stats.language.clone())
+            .or_default();
+        totals.code += stats.code;
+        totals.comments += stats.comments;
+        totals.blanks += stats.blanks;
+        totals.files += 1;
+    }
+
+    let mut directories: Vec<DirectoryStats> = groups
         .into_iter()
-        .map(|(language, (lines, files))| {
-            let percentage = if total_lines > 0 {
-                (lines as f64 / total_lines as f64) * 100.0
-            } else {
-                0.0
-            };
-            LanguageEntry {
-                language,
+        .map(|(path, language_data)| {
+            let lines = language_data.values().map(|t| t.code).sum();
+            DirectoryStats {
+                path,
                 lines,
-                files,
-                percentage,
+                comments: language_data.values().map(|t| t.comments).sum(),
+                blanks: language_data.values().map(|t| t.blanks).sum(),
+                files: language_data.values().map(|t| t.files).sum(),
+                percentage: percentage_of(lines, total_lines),
+                languages: language_entries(language_data),
             }
         })
+        .filter(|dir| dir.lines > 0 || dir.comments > 0)
         .collect();
+    directories.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.path.cmp(&b.path)));
+    directories
+}
 
-    // 按行数降序排序
-    languages.sort_by(|a, b| b.lines.cmp(&a.lines));
+fn build_language_stats(
+    language_data: HashMap<String, LanguageTotals>,
+    cache: &LanguageCache,
+    directory_depth: u32,
+) -> LanguageStats {
+    let total_lines: u64 = language_data.values().map(|t| t.code).sum();
+    let total_comments: u64 = language_data.values().map(|t| t.comments).sum();
+    let total_blanks: u64 = language_data.values().map(|t| t.blanks).sum();
 
-    let stats = LanguageStats {
+    LanguageStats {
         total_lines,
-        languages,
+        total_comments,
+        total_blanks,
+        languages: language_entries(language_data),
         scanned_at: now_iso(),
-    };
+        directories: (directory_depth > 0)
+            .then(|| directory_breakdown(&cache.files, directory_depth, total_lines)),
+    }
+}
 
-    // 更新项目的语言统计信息
-    let project_idx = store
-        .projects
-        .iter()
-        .position(|p| p.id == project_id)
-        .ok_or_else(|| "项目不存在".to_string())?;
+fn compute_language_stats(
+    path: &Path,
+    store_file_path: &Path,
+    language_settings: &settings::LanguageStatsSettings,
+) -> Result<LanguageStats, String> {
+    // 分组层数只影响汇总方式，不影响逐文件缓存
+    let fingerprint = stable_hash(
+        &serde_json::to_string(&settings::LanguageStatsSettings {
+            directory_depth: 0,
+            ..language_settings.clone()
+        })
+        .unwrap_or_default(),
+    );
+    let cache_path = language_cache_path(store_file_path, path);
+    let mut cache = load_language_cache(&cache_path);
+    if cache.settings_fingerprint != fingerprint {
+        cache = LanguageCache {
+            settings_fingerprint: fingerprint,
+            files: HashMap::new(),
+        };
+    }
+    let language_data = scan_project_languages(path, &mut cache, language_settings)?;
+    save_language_cache(&cache_path, &cache);
+    Ok(build_language_stats(
+        language_data,
+        &cache,
+        language_settings.directory_depth,
+    ))
+}
+
+fn language_stats_settings(state: &AppState) -> settings::LanguageStatsSettings {
+    state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .language_stats
+        .clone()
+}
+
+/// directory_depth 覆盖设置中的目录分组层数，0 表示不分组
+#[tauri::command(async)]
+fn scan_project_language_stats(
+    project_id: String,
+    directory_depth: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<LanguageStats, String> {
+    let mut language_settings = language_stats_settings(&state);
+    if let Some(depth) = directory_depth {
+        language_settings.directory_depth = depth.min(settings::MAX_DIRECTORY_DEPTH);
+    }
+    let project = find_project(&state, &project_id)?;
+    let project_path = Path::new(&project.path);
+    if !project_path.exists() || !project_path.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+
+    // 扫描期间不持有 store 锁
+    let stats = state
+        .background
+        .run(TaskKind::LanguageStats, project.name.clone(), |_| {
+            compute_language_stats(project_path, &state.file_path, &language_settings)
+        })
+        .map_err(|e| format!("扫描语言统计失败: {}", e))?;
 
-    store.projects[project_idx].metadata.language_stats = Some(stats.clone());
-    save_store(&state.file_path, &store)?;
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.metadata.language_stats = Some(stats.clone());
+    state.language_history.record(project);
+    persist::mark_dirty();
+    drop(projects);
+    state.language_history.save();
 
     Ok(stats)
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LanguageStatsUpdated {
+    project_id: String,
+    stats: LanguageStats,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct LanguageStatsRefresh {
+    updated: Vec<String>,
+    failed: Vec<LanguageStatsFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LanguageStatsFailure {
+    project_id: String,
+    error: String,
+}
+
+/// 在一个后台任务中依次统计多个项目，project_ids 为空时统计全部项目。
+/// 每完成一个项目就写入并发送 `language-stats://updated` 事件，取消后已完成的结果会保留
+#[tauri::command(async)]
+fn refresh_language_stats(
+    project_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<LanguageStatsRefresh, String> {
+    let language_settings = language_stats_settings(&state);
+    let targets: Vec<(String, String)> = state
+        .store
+        .projects()
+        .iter()
+        .filter(|p| project_ids.is_empty() || project_ids.contains(&p.id))
+        .map(|p| (p.id.clone(), p.path.clone()))
+        .collect();
+    let label = format!("{} 个项目", targets.len());
+
+    state
+        .background
+        .run(TaskKind::LanguageStats, label, |task| {
+            let mut report = LanguageStatsRefresh::default();
+            let total = targets.len();
+            for (done, (project_id, path)) in targets.iter().enumerate() {
+                if task.is_cancelled() {
+                    break;
+                }
+                task.progress(done, total);
+                let path = Path::new(path);
+                let stats = if path.is_dir() {
+                    compute_language_stats(path, &state.file_path, &language_settings)
+                } else {
+                    Err("项目路径不存在或不是目录".to_string())
+                };
+                let stats = match stats {
+                    Ok(stats) => stats,
+                    Err(error) => {
+                        report.failed.push(LanguageStatsFailure {
+                            project_id: project_id.clone(),
+                            error,
+                        });
+                        continue;
+                    }
+                };
+                // 统计期间项目可能已被删除
+                let mut projects = state.store.projects_mut();
+                let Some(project) = projects.iter_mut().find(|p| p.id == *project_id) else {
+                    continue;
+                };
+                project.metadata.language_stats = Some(stats.clone());
+                state.language_history.record(project);
+                persist::mark_dirty();
+                drop(projects);
+                task.emit(
+                    "language-stats://updated",
+                    LanguageStatsUpdated {
+                        project_id: project_id.clone(),
+                        stats,
+                    },
+                );
+                report.updated.push(project_id.clone());
+            }
+            state.language_history.save();
+            task.progress(total, total);
+            Ok(report)
+        })
+}
+
 #[tauri::command]
 fn get_project_language_stats(
     project_id: String,
     state: State<'_, AppState>,
 ) -> Result<Option<LanguageStats>, String> {
-    let store = state.store.lock().expect("store lock poisoned");
+    let projects = state.store.projects();
 
-    let project = store
-        .projects
+    let project = projects
         .iter()
         .find(|p| p.id == project_id)
         .ok_or_else(|| "项目不存在".to_string())?;
@@ -2186,19 +4074,62 @@ fn set_last_active_window(window_id: String, state: State<'_, AppState>) {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // 单实例需最先注册：再次启动（包括通过 dev-boom:// 链接唤起）时转交给已运行的实例
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            let prefix = format!("{DEEP_LINK_SCHEME}:");
+            if args.iter().any(|arg| arg.starts_with(&prefix)) {
+                return;
+            }
+            if let Some(main_win) = app.get_webview_window("main") {
+                let _ = main_win.show();
+                let _ = main_win.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .setup(|app| {
-            let app_data_dir = app
+            let default_data_dir = app
                 .path()
                 .app_data_dir()
                 .map_err(|e| format!("无法获取应用数据目录: {e}"))?;
+            let (app_data_dir, _) = data_location::resolve(&default_data_dir);
             fs::create_dir_all(&app_data_dir).map_err(|e| format!("无法创建应用数据目录: {e}"))?;
             let store_path = app_data_dir.join("store.json");
+            logging::init_logging(&store_path);
+            process::configure_launch_logs(&store_path);
             let store = load_store(&store_path);
+            let content = fs::read_to_string(&store_path).ok();
+            if let Some(content) = content.as_deref().filter(|c| parse_store(c).is_none()) {
+                store_lock::backup_corrupt(&store_path, content);
+            }
+            store_lock::remember(&store, content.as_deref());
+            let app_settings = settings::load_settings(&settings::settings_path(&store_path));
+            settings::apply(app.handle(), &app_settings);
+            let start_minimized = app_settings.start_minimized;
+            let launch_history = history::load_history(&history::history_path(&store_path));
+            let search_index = fulltext::IndexState::load(&store_path);
+            let sessions = timetrack::SessionTracker::load(&store_path);
+            let language_history = language_history::LanguageHistory::load(&store_path);
             app.manage(AppState {
                 file_path: store_path,
-                store: Mutex::new(store),
+                store: StoreState::new(store),
                 last_active_window: Mutex::new(Some("main".to_string())),
+                processes: process::ProcessRegistry::default(),
+                settings: Mutex::new(app_settings),
+                history: Mutex::new(launch_history),
+                search_index,
+                hotkey: hotkey::HotkeyState::default(),
+                sessions,
+                language_history,
+                undo: undo::UndoStack::default(),
+                background: background::TaskQueue::default(),
             });
+            let state = app.state::<AppState>();
+            state.background.attach(app.handle());
+            store_events::attach(app.handle(), &state.file_path, &state.store.snapshot());
+            trash::purge_expired(&app.state::<AppState>());
+            fulltext::start_indexing(app.handle());
+            persist::attach(app.handle());
+            store_watch::start_watching(app.handle());
 
             tray::create_tray(app).map_err(|e| format!("创建托盘失败: {e}"))?;
 
@@ -2206,6 +4137,7 @@ pub fn run() {
 
             // 监听主窗口事件
             if let Some(main_win) = app.get_webview_window("main") {
+                window_state::restore_main_window(&main_win, &app.state::<AppState>().file_path);
                 let win = main_win.clone();
                 let handle = app_handle.clone();
                 main_win.on_window_event(move |event| {
@@ -2214,6 +4146,9 @@ pub fn run() {
                             api.prevent_close();
                             let _ = win.hide();
                         }
+                        tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                            window_state::schedule_save(&win);
+                        }
                         tauri::WindowEvent::Focused(true) => {
                             // 窗口获得焦点时更新最后激活窗口
                             if let Some(state) = handle.try_state::<AppState>() {
@@ -2246,18 +4181,47 @@ pub fn run() {
                 });
             }
 
+            if start_minimized && autostart::launched_by_autostart() {
+                if let Some(main_win) = app.get_webview_window("main") {
+                    let _ = main_win.hide();
+                }
+            }
+
+            deeplink::init_deep_links(app);
+
             #[cfg(desktop)]
             {
-                // 全局快捷键功能已移除
-                // 如需重新启用，请确保正确处理热键注册冲突
+                // 注册失败不影响启动，错误通过 hotkey://error 事件通知前端
+                hotkey::init_hotkey(app.handle());
             }
 
+            updater::check_on_startup(app.handle());
+            sync::sync_on_startup(app.handle());
+            rescan::start(app.handle());
+
             Ok(())
         })
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(
+            tauri_plugin_autostart::Builder::new()
+                .args(autostart::launch_args())
+                .build(),
+        )
         .invoke_handler(tauri::generate_handler![
             get_projects,
+            get_project,
+            elevate::set_ide_elevated,
+            elevate::set_project_elevated_ides,
+            background::list_background_tasks,
+            background::cancel_background_task,
+            multiplexer::set_project_multiplexer,
+            validate_projects,
+            preview_launch_command,
+            relocate::relocate_project,
+            relocate::confirm_relocation,
             get_ides,
             add_project,
             remove_project,
@@ -2266,22 +4230,139 @@ pub fn run() {
             add_ide,
             remove_ide,
             set_ide_icon_from_file,
+            refresh_ide_icon,
+            set_ide_website,
+            refresh_all_ide_icons,
+            clear_icon_cache,
             reorder_projects,
+            reorder_ides,
             launch_project,
+            history::get_launch_failures,
+            launch_project_at,
             open_in_file_manager,
-            open_in_terminal,
+            terminal::open_in_terminal,
+            terminal::get_available_shells,
+            terminal::get_available_terminal_apps,
             scan_ides,
             add_detected_ides,
             set_project_ide_preferences,
-            save_mini_window_position,
-            load_mini_window_position,
+            set_project_launch_steps,
+            window_state::save_mini_window_position,
+            window_state::load_mini_window_position,
+            window_state::reset_mini_window_position,
             switch_to_mini_window,
             switch_to_main_window,
             scan_project_language_stats,
+            refresh_language_stats,
             get_project_language_stats,
+            language_history::get_language_stats_history,
             get_last_active_window,
             set_last_active_window,
+            compose::compose_up,
+            compose::compose_down,
+            compose::compose_status,
+            tasks::list_project_tasks,
+            tasks::run_project_task,
+            tasks::stop_project_task,
+            settings::get_settings,
+            settings::update_settings,
+            manifest::get_project_dependencies,
+            manifest::get_project_manifest_summary,
+            modules::get_project_modules,
+            modules::launch_project_module,
+            todos::scan_project_todos,
+            cleanup::clean_project_artifacts,
+            analysis::find_stale_projects,
+            analysis::get_dashboard_stats,
+            analysis::get_activity_heatmap,
+            readme::get_project_readme,
+            description::fill_auto_descriptions,
+            project_icon::get_project_icon,
+            project_icon::set_project_icon_from_file,
+            project_icon::clear_project_icon,
+            search::search_projects,
+            search::quick_switch,
+            update_project_description,
+            update_project_notes,
+            update_project_aliases,
+            tags::list_tag_definitions,
+            tags::save_tag_definition,
+            tags::delete_tag_definition,
+            clipboard::copy_project_path,
+            clipboard::copy_git_url,
+            content_search::search_in_project,
+            dev_server::get_dev_urls,
+            dev_server::open_dev_url,
+            dev_server::set_project_dev_url,
+            dev_server::start_dev_server,
+            dev_server::stop_dev_server,
+            dev_server::get_dev_server_status,
+            dev_server::set_project_dev_command,
+            file_search::find_files,
+            integrity::verify_store,
+            file_tree::get_project_tree,
+            fulltext::index_projects,
+            fulltext::full_text_search,
+            hotkey::get_global_hotkey,
+            hotkey::set_global_hotkey,
+            autostart::get_autostart,
+            autostart::set_autostart,
+            data_location::get_data_location,
+            data_location::set_data_dir,
+            diagnostics::export_diagnostics,
+            logging::get_recent_logs,
+            logging::open_log_folder,
+            updater::check_for_updates,
+            updater::install_update,
+            sync::sync_now,
+            vscode::import_from_vscode,
+            vscode::add_vscode_projects,
+            report::export_report,
+            timetrack::get_time_stats,
+            health::check_project_health,
+            version_manager::get_pinned_versions,
+            version_manager::set_project_version_activation,
+            envfile::preview_env_file,
+            envfile::set_project_env_file,
+            workspace::get_workspace_files,
+            workspace::set_project_workspace,
+            multiroot::set_project_paths,
+            multiroot::set_ide_multi_root_mode,
+            templates::get_templates,
+            templates::add_template,
+            templates::remove_template,
+            templates::create_project_from_template,
+            trash::list_trashed_projects,
+            trash::restore_project,
+            trash::purge_trash,
+            trash::delete_project_files,
+            undo::undo_last_operation,
+            prune::prune_projects,
+            rescan::rescan_all_roots,
+            ide_health::check_ides,
+            ide_health::remove_stale_ides,
+            ide_merge::find_duplicate_ides,
+            ide_merge::merge_ides,
+            arg_presets::get_ide_arg_presets,
+            arg_presets::save_ide_arg_preset,
+            arg_presets::delete_ide_arg_preset,
+            arg_presets::set_project_arg_preset,
+            secrets::list_secrets,
+            secrets::set_secret,
+            secrets::delete_secret,
+            ci::get_ci_status,
+            branches::list_branches,
+            branches::checkout_branch,
+            git_stats::get_git_stats,
+            persist::flush_store,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Err(err) = persist::flush(app) {
+                    tracing::error!("退出前保存数据失败: {err}");
+                }
+            }
+        });
 }
@@ -0,0 +1,115 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use tauri::State;
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+
+use crate::AppState;
+
+const LOG_FILE_PREFIX: &str = "dev-boom";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_RECENT_LINES: usize = 200;
+const MAX_RECENT_LINES: usize = 5000;
+
+/// 后台写日志线程的句柄，释放后缓冲区中的日志会丢失，所以在进程生命周期内一直持有
+static LOG_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+pub fn log_dir(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("logs")
+}
+
+/// 日志按天滚动，保留最近 7 天；初始化失败时只输出到 stderr，不影响启动
+pub fn init_logging(store_file_path: &Path) {
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir(store_file_path));
+    let appender = match appender {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("初始化日志文件失败: {err}");
+            return;
+        }
+    };
+
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let installed = tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_target(true)
+        .with_max_level(tracing::Level::INFO)
+        .try_init()
+        .is_ok();
+    if installed {
+        let _ = LOG_GUARD.set(guard);
+        tracing::info!(version = env!("CARGO_PKG_VERSION"), "dev-boom 启动");
+    }
+}
+
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(LOG_FILE_PREFIX) && n.ends_with(LOG_FILE_SUFFIX))
+                .unwrap_or(false)
+        })
+        .collect();
+    // 文件名中带日期，字典序即时间顺序
+    files.sort();
+    files
+}
+
+/// 从最新的日志文件往前读取，返回最后 lines 行（按时间正序）
+pub fn recent_log_lines(store_file_path: &Path, lines: usize) -> Vec<String> {
+    let mut collected: Vec<String> = vec![];
+    for path in log_files(&log_dir(store_file_path)).iter().rev() {
+        let Ok(content) = fs::read(path) else {
+            continue;
+        };
+        let content = String::from_utf8_lossy(&content);
+        let mut file_lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let needed = lines - collected.len();
+        if file_lines.len() > needed {
+            file_lines.drain(..file_lines.len() - needed);
+        }
+        file_lines.append(&mut collected);
+        collected = file_lines;
+        if collected.len() >= lines {
+            break;
+        }
+    }
+    collected
+}
+
+#[tauri::command]
+pub fn get_recent_logs(lines: Option<usize>, state: State<'_, AppState>) -> Vec<String> {
+    let lines = lines
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_RECENT_LINES)
+        .min(MAX_RECENT_LINES);
+    recent_log_lines(&state.file_path, lines)
+}
+
+#[tauri::command]
+pub fn open_log_folder(state: State<'_, AppState>) -> Result<(), String> {
+    let dir = log_dir(&state.file_path);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    crate::open_in_file_manager(dir.to_string_lossy().to_string())
+}
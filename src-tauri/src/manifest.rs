@@ -0,0 +1,716 @@
+use std::{fs, path::Path};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, AppState};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+    Peer,
+    Optional,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependency {
+    name: String,
+    version: Option<String>,
+    kind: DependencyKind,
+    ecosystem: &'static str,
+    /// 来源清单文件名，如 `Cargo.toml`
+    manifest: &'static str,
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse::<toml::Table>().ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn toml_dependency_version(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(v) => Some(v.clone()),
+        toml::Value::Table(t) => t
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or_else(|| {
+                t.get("git")
+                    .and_then(|v| v.as_str())
+                    .map(|git| format!("git:{git}"))
+            })
+            .or_else(|| {
+                t.get("path")
+                    .and_then(|v| v.as_str())
+                    .map(|path| format!("path:{path}"))
+            })
+            .or_else(|| {
+                t.get("workspace")
+                    .and_then(|v| v.as_bool())
+                    .filter(|v| *v)
+                    .map(|_| "workspace".to_string())
+            }),
+        _ => None,
+    }
+}
+
+fn cargo_dependencies(root: &Path) -> Vec<Dependency> {
+    let Some(manifest) = read_toml(&root.join("Cargo.toml")) else {
+        return vec![];
+    };
+    let sections = [
+        ("dependencies", DependencyKind::Normal),
+        ("dev-dependencies", DependencyKind::Dev),
+        ("build-dependencies", DependencyKind::Build),
+    ];
+
+    let mut deps = vec![];
+    for (section, kind) in sections {
+        let Some(table) = manifest.get(section).and_then(|v| v.as_table()) else {
+            continue;
+        };
+        for (name, value) in table {
+            let optional = value
+                .get("optional")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            deps.push(Dependency {
+                name: name.clone(),
+                version: toml_dependency_version(value),
+                kind: if optional && kind == DependencyKind::Normal {
+                    DependencyKind::Optional
+                } else {
+                    kind.clone()
+                },
+                ecosystem: "cargo",
+                manifest: "Cargo.toml",
+            });
+        }
+    }
+    deps
+}
+
+fn npm_dependencies(root: &Path) -> Vec<Dependency> {
+    let Some(manifest) = read_json(&root.join("package.json")) else {
+        return vec![];
+    };
+    let sections = [
+        ("dependencies", DependencyKind::Normal),
+        ("devDependencies", DependencyKind::Dev),
+        ("peerDependencies", DependencyKind::Peer),
+        ("optionalDependencies", DependencyKind::Optional),
+    ];
+
+    let mut deps = vec![];
+    for (section, kind) in sections {
+        let Some(table) = manifest.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, version) in table {
+            deps.push(Dependency {
+                name: name.clone(),
+                version: version.as_str().map(str::to_string),
+                kind: kind.clone(),
+                ecosystem: "npm",
+                manifest: "package.json",
+            });
+        }
+    }
+    deps
+}
+
+/// 拆分 PEP 508 依赖串，如 `requests[socks]>=2.31; python_version>"3.8"`
+fn split_pep508(spec: &str) -> (String, Option<String>) {
+    let spec = spec.split(';').next().unwrap_or("").trim();
+    let name_end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(spec.len());
+    let name = spec[..name_end].to_string();
+    let rest = spec[name_end..].trim();
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => extras.split_once(']').map(|(_, v)| v.trim()).unwrap_or(""),
+        None => rest,
+    };
+    let version = (!rest.is_empty()).then(|| {
+        rest.trim_start_matches(['(', ' '])
+            .trim_end_matches(')')
+            .to_string()
+    });
+    (name, version)
+}
+
+fn pyproject_dependencies(root: &Path) -> Vec<Dependency> {
+    let Some(manifest) = read_toml(&root.join("pyproject.toml")) else {
+        return vec![];
+    };
+    let mut deps = vec![];
+    let mut push_spec = |spec: &str, kind: DependencyKind| {
+        let (name, version) = split_pep508(spec);
+        if !name.is_empty() {
+            deps.push(Dependency {
+                name,
+                version,
+                kind,
+                ecosystem: "pypi",
+                manifest: "pyproject.toml",
+            });
+        }
+    };
+
+    // PEP 621
+    if let Some(project) = manifest.get("project") {
+        for spec in project
+            .get("dependencies")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+        {
+            push_spec(spec, DependencyKind::Normal);
+        }
+        for spec in project
+            .get("optional-dependencies")
+            .and_then(|v| v.as_table())
+            .into_iter()
+            .flat_map(|groups| groups.values())
+            .filter_map(|v| v.as_array())
+            .flatten()
+            .filter_map(|v| v.as_str())
+        {
+            push_spec(spec, DependencyKind::Optional);
+        }
+    }
+    // PEP 735 dependency-groups
+    for spec in manifest
+        .get("dependency-groups")
+        .and_then(|v| v.as_table())
+        .into_iter()
+        .flat_map(|groups| groups.values())
+        .filter_map(|v| v.as_array())
+        .flatten()
+        .filter_map(|v| v.as_str())
+    {
+        push_spec(spec, DependencyKind::Dev);
+    }
+
+    // Poetry
+    let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+    let mut poetry_sections: Vec<(&toml::Table, DependencyKind)> = vec![];
+    if let Some(table) = poetry
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|v| v.as_table())
+    {
+        poetry_sections.push((table, DependencyKind::Normal));
+    }
+    if let Some(table) = poetry
+        .and_then(|p| p.get("dev-dependencies"))
+        .and_then(|v| v.as_table())
+    {
+        poetry_sections.push((table, DependencyKind::Dev));
+    }
+    for group in poetry
+        .and_then(|p| p.get("group"))
+        .and_then(|v| v.as_table())
+        .into_iter()
+        .flat_map(|groups| groups.values())
+    {
+        if let Some(table) = group.get("dependencies").and_then(|v| v.as_table()) {
+            poetry_sections.push((table, DependencyKind::Dev));
+        }
+    }
+    for (table, kind) in poetry_sections {
+        for (name, value) in table {
+            // python 版本约束不是依赖
+            if name == "python" {
+                continue;
+            }
+            deps.push(Dependency {
+                name: name.clone(),
+                version: toml_dependency_version(value),
+                kind: kind.clone(),
+                ecosystem: "pypi",
+                manifest: "pyproject.toml",
+            });
+        }
+    }
+    deps
+}
+
+fn go_dependencies(root: &Path) -> Vec<Dependency> {
+    let Ok(content) = fs::read_to_string(root.join("go.mod")) else {
+        return vec![];
+    };
+    let mut deps = vec![];
+    let mut in_require_block = false;
+    for line in content.lines() {
+        let line = line.trim();
+        let spec = if in_require_block {
+            if line.starts_with(')') {
+                in_require_block = false;
+                continue;
+            }
+            line
+        } else if line.starts_with("require (") || line == "require(" {
+            in_require_block = true;
+            continue;
+        } else if let Some(rest) = line.strip_prefix("require ") {
+            rest
+        } else {
+            continue;
+        };
+
+        // 间接依赖不算直接依赖
+        if spec.contains("// indirect") {
+            continue;
+        }
+        let spec = spec.split("//").next().unwrap_or("").trim();
+        let mut parts = spec.split_whitespace();
+        if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
+            deps.push(Dependency {
+                name: name.to_string(),
+                version: Some(version.to_string()),
+                kind: DependencyKind::Normal,
+                ecosystem: "go",
+                manifest: "go.mod",
+            });
+        }
+    }
+    deps
+}
+
+fn xml_child_text<'a>(node: roxmltree::Node<'a, 'a>, tag: &str) -> Option<&'a str> {
+    node.children()
+        .find(|c| c.is_element() && c.tag_name().name() == tag)
+        .and_then(|c| c.text())
+        .map(str::trim)
+}
+
+fn maven_dependencies(root: &Path) -> Vec<Dependency> {
+    let Ok(content) = fs::read_to_string(root.join("pom.xml")) else {
+        return vec![];
+    };
+    let Ok(doc) = roxmltree::Document::parse(&content) else {
+        return vec![];
+    };
+    // 只取 <project><dependencies>，跳过 dependencyManagement / plugins 中的声明
+    let Some(dependencies) = doc
+        .root_element()
+        .children()
+        .find(|c| c.is_element() && c.tag_name().name() == "dependencies")
+    else {
+        return vec![];
+    };
+
+    dependencies
+        .children()
+        .filter(|c| c.is_element() && c.tag_name().name() == "dependency")
+        .filter_map(|dep| {
+            let group = xml_child_text(dep, "groupId")?;
+            let artifact = xml_child_text(dep, "artifactId")?;
+            let scope = xml_child_text(dep, "scope").unwrap_or("compile");
+            let optional = xml_child_text(dep, "optional") == Some("true");
+            Some(Dependency {
+                name: format!("{group}:{artifact}"),
+                version: xml_child_text(dep, "version").map(str::to_string),
+                kind: if optional {
+                    DependencyKind::Optional
+                } else if scope == "test" {
+                    DependencyKind::Dev
+                } else if scope == "provided" {
+                    DependencyKind::Peer
+                } else {
+                    DependencyKind::Normal
+                },
+                ecosystem: "maven",
+                manifest: "pom.xml",
+            })
+        })
+        .collect()
+}
+
+pub fn collect_dependencies(root: &Path) -> Vec<Dependency> {
+    let mut deps = cargo_dependencies(root);
+    deps.extend(npm_dependencies(root));
+    deps.extend(pyproject_dependencies(root));
+    deps.extend(go_dependencies(root));
+    deps.extend(maven_dependencies(root));
+    deps
+}
+
+#[tauri::command(async)]
+pub fn get_project_dependencies(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Dependency>, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    Ok(collect_dependencies(root))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestScript {
+    name: String,
+    command: String,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceInfo {
+    /// 项目本身是工作区根目录时列出成员（可能是通配符模式）
+    members: Vec<String>,
+    /// 项目是某个工作区的成员时，工作区根目录的路径
+    root: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSummary {
+    ecosystem: &'static str,
+    /// 来源清单文件名，如 `Cargo.toml`
+    pub manifest: &'static str,
+    name: Option<String>,
+    version: Option<String>,
+    pub description: Option<String>,
+    /// npm scripts 等可运行的命令
+    scripts: Vec<ManifestScript>,
+    /// 安装后提供的可执行文件
+    bins: Vec<String>,
+    workspace: Option<WorkspaceInfo>,
+}
+
+impl ManifestSummary {
+    fn new(ecosystem: &'static str, manifest: &'static str) -> Self {
+        ManifestSummary {
+            ecosystem,
+            manifest,
+            name: None,
+            version: None,
+            description: None,
+            scripts: vec![],
+            bins: vec![],
+            workspace: None,
+        }
+    }
+}
+
+/// 工作区成员最多向上查找的层数
+const MAX_WORKSPACE_DEPTH: usize = 5;
+
+fn find_workspace_root(root: &Path, is_root: impl Fn(&Path) -> bool) -> Option<String> {
+    root.ancestors()
+        .skip(1)
+        .take(MAX_WORKSPACE_DEPTH)
+        .find(|dir| is_root(dir))
+        .map(|dir| dir.to_string_lossy().to_string())
+}
+
+fn toml_str(table: Option<&toml::Value>, key: &str) -> Option<String> {
+    table?
+        .get(key)?
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn toml_strings(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+fn workspace_info(members: Vec<String>, root: Option<String>) -> Option<WorkspaceInfo> {
+    (!members.is_empty() || root.is_some()).then_some(WorkspaceInfo { members, root })
+}
+
+fn cargo_summary(root: &Path) -> Option<ManifestSummary> {
+    let manifest = read_toml(&root.join("Cargo.toml"))?;
+    let package = manifest.get("package");
+    let workspace_root = if manifest.contains_key("workspace") {
+        None
+    } else {
+        find_workspace_root(root, |dir| {
+            read_toml(&dir.join("Cargo.toml")).is_some_and(|m| m.contains_key("workspace"))
+        })
+    };
+    // `version.workspace = true` 等字段继承自工作区的 [workspace.package]
+    let inherited = |key: &str| {
+        let root = workspace_root.as_deref().map(Path::new).unwrap_or(root);
+        let manifest = read_toml(&root.join("Cargo.toml"))?;
+        toml_str(manifest.get("workspace")?.get("package"), key)
+    };
+    let field = |key: &str| {
+        let value = package?.get(key)?;
+        match value.as_str() {
+            Some(v) => Some(v.to_string()),
+            None => value
+                .get("workspace")
+                .and_then(|v| v.as_bool())
+                .filter(|v| *v)
+                .and_then(|_| inherited(key)),
+        }
+    };
+
+    let mut summary = ManifestSummary::new("cargo", "Cargo.toml");
+    summary.name = field("name");
+    summary.version = field("version");
+    summary.description = field("description");
+    summary.bins = manifest
+        .get("bin")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|bin| bin.get("name")?.as_str().map(str::to_string))
+        .collect();
+    if summary.bins.is_empty() && root.join("src").join("main.rs").is_file() {
+        summary.bins.extend(summary.name.clone());
+    }
+    summary.workspace = workspace_info(
+        toml_strings(manifest.get("workspace").and_then(|w| w.get("members"))),
+        workspace_root,
+    );
+    Some(summary)
+}
+
+fn json_str(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)?
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// `workspaces` 可以是数组，也可以是 `{ "packages": [...] }`
+fn npm_workspaces(manifest: &serde_json::Value) -> Vec<String> {
+    let workspaces = manifest.get("workspaces");
+    workspaces
+        .and_then(|w| w.as_array())
+        .or_else(|| workspaces?.get("packages")?.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// pnpm 的工作区在 `pnpm-workspace.yaml` 中声明，只取 `packages` 列表中的简单条目
+fn pnpm_workspaces(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return vec![];
+    };
+    let mut in_packages = false;
+    let mut members = vec![];
+    for line in content.lines() {
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = line.trim_end() == "packages:";
+            continue;
+        }
+        if let Some(item) = line.trim().strip_prefix('-').filter(|_| in_packages) {
+            members.push(item.trim().trim_matches(['"', '\'']).to_string());
+        }
+    }
+    members
+}
+
+fn npm_summary(root: &Path) -> Option<ManifestSummary> {
+    let manifest = read_json(&root.join("package.json"))?;
+    let mut summary = ManifestSummary::new("npm", "package.json");
+    summary.name = json_str(&manifest, "name");
+    summary.version = json_str(&manifest, "version");
+    summary.description = json_str(&manifest, "description");
+    summary.scripts = manifest
+        .get("scripts")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, command)| {
+            Some(ManifestScript {
+                name: name.clone(),
+                command: command.as_str()?.to_string(),
+            })
+        })
+        .collect();
+    // `bin` 为字符串时命令名就是包名（去掉 scope）
+    summary.bins = match manifest.get("bin") {
+        Some(serde_json::Value::String(_)) => summary
+            .name
+            .iter()
+            .map(|name| name.rsplit('/').next().unwrap_or(name).to_string())
+            .collect(),
+        Some(serde_json::Value::Object(bins)) => bins.keys().cloned().collect(),
+        _ => vec![],
+    };
+    let mut members = npm_workspaces(&manifest);
+    members.extend(pnpm_workspaces(root));
+    let workspace_root = members.is_empty().then(|| {
+        find_workspace_root(root, |dir| {
+            dir.join("pnpm-workspace.yaml").is_file()
+                || read_json(&dir.join("package.json"))
+                    .is_some_and(|m| !npm_workspaces(&m).is_empty())
+        })
+    });
+    summary.workspace = workspace_info(members, workspace_root.flatten());
+    Some(summary)
+}
+
+fn pyproject_summary(root: &Path) -> Option<ManifestSummary> {
+    let manifest = read_toml(&root.join("pyproject.toml"))?;
+    let project = manifest.get("project");
+    let tool = manifest.get("tool");
+    let poetry = tool.and_then(|t| t.get("poetry"));
+    let mut summary = ManifestSummary::new("pypi", "pyproject.toml");
+    summary.name = toml_str(project, "name").or_else(|| toml_str(poetry, "name"));
+    summary.version = toml_str(project, "version").or_else(|| toml_str(poetry, "version"));
+    summary.description =
+        toml_str(project, "description").or_else(|| toml_str(poetry, "description"));
+    // 入口脚本安装后就是可执行文件
+    summary.bins = [
+        project.and_then(|p| p.get("scripts")),
+        poetry.and_then(|p| p.get("scripts")),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|v| v.as_table())
+    .flat_map(|scripts| scripts.keys().cloned())
+    .collect();
+    let uv_workspace = |manifest: &toml::Table| {
+        manifest
+            .get("tool")
+            .and_then(|t| t.get("uv"))
+            .and_then(|uv| uv.get("workspace"))
+            .cloned()
+    };
+    let members = toml_strings(
+        uv_workspace(&manifest)
+            .as_ref()
+            .and_then(|w| w.get("members")),
+    );
+    let workspace_root = members.is_empty().then(|| {
+        find_workspace_root(root, |dir| {
+            read_toml(&dir.join("pyproject.toml")).is_some_and(|m| uv_workspace(&m).is_some())
+        })
+    });
+    summary.workspace = workspace_info(members, workspace_root.flatten());
+    Some(summary)
+}
+
+/// `go.work` 中的 `use` 指令
+fn go_work_members(dir: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(dir.join("go.work")).ok()?;
+    let mut members = vec![];
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line.starts_with(')') {
+                in_block = false;
+            } else if !line.is_empty() {
+                members.push(line.to_string());
+            }
+        } else if line.starts_with("use (") || line == "use(" {
+            in_block = true;
+        } else if let Some(member) = line.strip_prefix("use ") {
+            members.push(member.trim().to_string());
+        }
+    }
+    Some(members)
+}
+
+fn go_summary(root: &Path) -> Option<ManifestSummary> {
+    let content = fs::read_to_string(root.join("go.mod")).ok()?;
+    let mut summary = ManifestSummary::new("go", "go.mod");
+    summary.name = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))
+        .map(|module| module.trim().trim_matches('"').to_string());
+    if root.join("main.go").is_file() {
+        summary.bins.extend(
+            summary
+                .name
+                .iter()
+                .map(|name| name.rsplit('/').next().unwrap_or(name).to_string()),
+        );
+    }
+    let members = go_work_members(root).unwrap_or_default();
+    let workspace_root = members
+        .is_empty()
+        .then(|| find_workspace_root(root, |dir| dir.join("go.work").is_file()));
+    summary.workspace = workspace_info(members, workspace_root.flatten());
+    Some(summary)
+}
+
+fn maven_summary(root: &Path) -> Option<ManifestSummary> {
+    let content = fs::read_to_string(root.join("pom.xml")).ok()?;
+    let doc = roxmltree::Document::parse(&content).ok()?;
+    let project = doc.root_element();
+    let parent = project
+        .children()
+        .find(|c| c.is_element() && c.tag_name().name() == "parent");
+    // groupId 与 version 可以继承自 <parent>
+    let inherited = |tag: &str| {
+        xml_child_text(project, tag).or_else(|| parent.and_then(|p| xml_child_text(p, tag)))
+    };
+    let mut summary = ManifestSummary::new("maven", "pom.xml");
+    summary.name =
+        xml_child_text(project, "artifactId").map(|artifact| match inherited("groupId") {
+            Some(group) => format!("{group}:{artifact}"),
+            None => artifact.to_string(),
+        });
+    summary.version = inherited("version").map(str::to_string);
+    summary.description = xml_child_text(project, "description")
+        .filter(|v| !v.is_empty())
+        .map(str::to_string);
+    let members: Vec<String> = project
+        .children()
+        .find(|c| c.is_element() && c.tag_name().name() == "modules")
+        .into_iter()
+        .flat_map(|modules| modules.children())
+        .filter(|c| c.is_element() && c.tag_name().name() == "module")
+        .filter_map(|c| c.text())
+        .map(|m| m.trim().to_string())
+        .collect();
+    let workspace_root = parent
+        .is_some()
+        .then(|| find_workspace_root(root, |dir| dir.join("pom.xml").is_file()));
+    summary.workspace = workspace_info(members, workspace_root.flatten());
+    Some(summary)
+}
+
+/// 按 Cargo.toml、package.json、pyproject.toml、go.mod、pom.xml 的顺序取第一个能解析的清单
+pub fn manifest_summary(root: &Path) -> Option<ManifestSummary> {
+    cargo_summary(root)
+        .or_else(|| npm_summary(root))
+        .or_else(|| pyproject_summary(root))
+        .or_else(|| go_summary(root))
+        .or_else(|| maven_summary(root))
+}
+
+/// 项目主清单中的名称、版本、描述、脚本与工作区信息，没有可识别的清单时返回 None
+#[tauri::command(async)]
+pub fn get_project_manifest_summary(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<ManifestSummary>, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    Ok(manifest_summary(root))
+}
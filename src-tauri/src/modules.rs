@@ -0,0 +1,278 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{
+    default_launch_ides, find_project, launch_with_ide, mark_opened, normalize_windows_path_for_ui,
+    record_launch_failure, record_launches, AppState, IdeConfig,
+};
+
+/// Maven 聚合工程嵌套的最大层数
+const MAX_MAVEN_DEPTH: usize = 3;
+
+/// `.sln` 中解决方案文件夹的类型 GUID，它们不是项目
+const SOLUTION_FOLDER_GUID: &str = "2150E333-8FDC-42A3-9474-1A3956D46DE8";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ModuleKind {
+    Solution,
+    Maven,
+    Gradle,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectModule {
+    name: String,
+    kind: ModuleKind,
+    /// 相对于项目根目录的模块目录，使用 `/` 分隔
+    relative_path: String,
+    path: String,
+    exists: bool,
+}
+
+fn module(root: &Path, name: String, kind: ModuleKind, relative: &str) -> ProjectModule {
+    let relative = relative.replace('\\', "/");
+    let relative = relative.trim_start_matches("./").trim_end_matches('/');
+    let dir = root.join(relative);
+    ProjectModule {
+        name,
+        kind,
+        relative_path: relative.to_string(),
+        path: normalize_windows_path_for_ui(&dir.to_string_lossy()),
+        exists: dir.is_dir(),
+    }
+}
+
+fn files_with_extension(root: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn parent_dir(relative_file: &str) -> String {
+    let relative_file = relative_file.replace('\\', "/");
+    relative_file
+        .rsplit_once('/')
+        .map(|(dir, _)| dir.to_string())
+        .unwrap_or_default()
+}
+
+/// `Project("{类型}") = "名称", "相对路径\名称.csproj", "{项目 GUID}"`
+fn sln_modules(root: &Path, sln: &Path) -> Vec<ProjectModule> {
+    let Ok(content) = fs::read_to_string(sln) else {
+        return vec![];
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("Project(\"")?;
+            let (type_guid, rest) = rest.split_once("\")")?;
+            if type_guid
+                .trim_matches(['{', '}'])
+                .eq_ignore_ascii_case(SOLUTION_FOLDER_GUID)
+            {
+                return None;
+            }
+            let mut fields = rest
+                .split_once('=')?
+                .1
+                .split(',')
+                .map(|f| f.trim().trim_matches('"'));
+            let name = fields.next()?.to_string();
+            let file = fields.next()?;
+            Some(module(root, name, ModuleKind::Solution, &parent_dir(file)))
+        })
+        .collect()
+}
+
+/// 新格式的 `.slnx`：`<Project Path="src/App/App.csproj" />`，可能嵌套在 `<Folder>` 中
+fn slnx_modules(root: &Path, slnx: &Path) -> Vec<ProjectModule> {
+    let Ok(content) = fs::read_to_string(slnx) else {
+        return vec![];
+    };
+    let Ok(doc) = roxmltree::Document::parse(&content) else {
+        return vec![];
+    };
+    doc.descendants()
+        .filter(|node| node.is_element() && node.tag_name().name() == "Project")
+        .filter_map(|node| {
+            let file = node.attribute("Path")?.replace('\\', "/");
+            let name = file
+                .rsplit('/')
+                .next()
+                .and_then(|f| f.rsplit_once('.').map(|(stem, _)| stem))
+                .unwrap_or(&file)
+                .to_string();
+            Some(module(root, name, ModuleKind::Solution, &parent_dir(&file)))
+        })
+        .collect()
+}
+
+fn dotnet_modules(root: &Path) -> Vec<ProjectModule> {
+    // 有多个解决方案时只取第一个，通常只有一个
+    if let Some(sln) = files_with_extension(root, "sln").first() {
+        return sln_modules(root, sln);
+    }
+    files_with_extension(root, "slnx")
+        .first()
+        .map(|slnx| slnx_modules(root, slnx))
+        .unwrap_or_default()
+}
+
+fn maven_modules_in(root: &Path, prefix: &str, depth: usize, out: &mut Vec<ProjectModule>) {
+    let dir = root.join(prefix);
+    let Ok(content) = fs::read_to_string(dir.join("pom.xml")) else {
+        return;
+    };
+    let Ok(doc) = roxmltree::Document::parse(&content) else {
+        return;
+    };
+    let names: Vec<String> = doc
+        .root_element()
+        .children()
+        .filter(|c| c.is_element() && c.tag_name().name() == "modules")
+        .flat_map(|modules| modules.children())
+        .filter(|c| c.is_element() && c.tag_name().name() == "module")
+        .filter_map(|c| c.text())
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+    for name in names {
+        let relative = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let display = name.rsplit('/').next().unwrap_or(&name).to_string();
+        out.push(module(root, display, ModuleKind::Maven, &relative));
+        if depth < MAX_MAVEN_DEPTH {
+            maven_modules_in(root, &relative, depth + 1, out);
+        }
+    }
+}
+
+fn maven_modules(root: &Path) -> Vec<ProjectModule> {
+    let mut modules = vec![];
+    maven_modules_in(root, "", 1, &mut modules);
+    modules
+}
+
+/// 取 `include` 语句中的引号字符串，Groovy 与 Kotlin DSL 的写法都适用：
+/// `include ':app', ':lib:core'`、`include(":app")`
+fn gradle_includes(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or("").trim())
+        .filter_map(|line| line.strip_prefix("include"))
+        .filter(|rest| rest.starts_with([' ', '(', '\t']))
+        .flat_map(|rest| {
+            rest.split(['"', '\''])
+                .skip(1)
+                .step_by(2)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn gradle_modules(root: &Path) -> Vec<ProjectModule> {
+    let Some(content) = ["settings.gradle.kts", "settings.gradle"]
+        .iter()
+        .find_map(|name| fs::read_to_string(root.join(name)).ok())
+    else {
+        return vec![];
+    };
+    gradle_includes(&content)
+        .into_iter()
+        .map(|include| {
+            // `:lib:core` 默认对应 `lib/core` 目录
+            let relative = include.trim_start_matches(':').replace(':', "/");
+            let name = include.rsplit(':').next().unwrap_or(&include).to_string();
+            module(root, name, ModuleKind::Gradle, &relative)
+        })
+        .collect()
+}
+
+pub fn collect_modules(root: &Path) -> Vec<ProjectModule> {
+    let mut modules = dotnet_modules(root);
+    modules.extend(maven_modules(root));
+    modules.extend(gradle_modules(root));
+    modules
+}
+
+/// 解决方案中的 .NET 项目、Maven 子模块与 Gradle 子项目
+#[tauri::command(async)]
+pub fn get_project_modules(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectModule>, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    Ok(collect_modules(root))
+}
+
+/// 用 IDE 单独打开某个模块目录，只接受 get_project_modules 返回的模块
+#[tauri::command(async)]
+pub fn launch_project_module(
+    project_id: String,
+    relative_path: String,
+    ide_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let project = find_project(&state, &project_id)?;
+    let module = collect_modules(Path::new(&project.path))
+        .into_iter()
+        .find(|m| m.relative_path == relative_path.replace('\\', "/"))
+        .ok_or_else(|| "模块不存在".to_string())?;
+    if !module.exists {
+        return Err(format!("模块目录不存在: {}", module.path));
+    }
+
+    let ides = state.store.ides();
+    let explicit = ide_id.is_some();
+    let ide: IdeConfig = match ide_id {
+        Some(requested) => ides.iter().find(|i| i.id == requested).cloned(),
+        None => default_launch_ides(&state, &project, &ides)
+            .into_iter()
+            .next()
+            .or_else(|| ides.iter().min_by_key(|i| i.priority).cloned()),
+    }
+    .ok_or_else(|| "IDE 不存在".to_string())?;
+    drop(ides);
+
+    // 以模块目录作为项目路径启动，工作区文件与多根目录只属于整个项目
+    let mut target = project.clone();
+    target.path = module.path.clone();
+    target.paths.clear();
+    target.metadata.open_workspace_file = false;
+    let child = launch_with_ide(&target, &ide, None).inspect_err(|err| {
+        record_launch_failure(&state, &project.id, &ide.id, err);
+    })?;
+    if let Some(child) = child {
+        state.sessions.watch(&target, &ide, child);
+    }
+    record_launches(&state, &project.id, &[&ide.id]);
+    mark_opened(&state, &project.id, explicit.then_some(ide.id.as_str()))
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use chrono::Utc;
+
+use crate::{analysis, file_mtime_iso, git_output, should_skip_dir};
+
+/// 最多检查的文件数量，超大仓库只看前面的部分
+const MAX_FILES: usize = 20_000;
+/// 深度计算结果的缓存时间，列表刷新频繁时避免反复遍历
+const CACHE_TTL: Duration = Duration::from_secs(120);
+
+/// 计算时间与结果
+type CacheEntry = (Instant, Option<String>);
+
+static DEEP: AtomicBool = AtomicBool::new(false);
+static CACHE: Mutex<Option<HashMap<String, CacheEntry>>> = Mutex::new(None);
+
+pub fn configure(deep: bool) {
+    DEEP.store(deep, Ordering::Relaxed);
+}
+
+fn iso(time: SystemTime) -> String {
+    chrono::DateTime::<Utc>::from(time).to_rfc3339()
+}
+
+fn newest_mtime(files: impl Iterator<Item = PathBuf>) -> Option<SystemTime> {
+    files
+        .take(MAX_FILES)
+        .filter_map(|path| fs::symlink_metadata(path).ok()?.modified().ok())
+        .max()
+}
+
+/// git 仓库中取已跟踪与未被忽略的新文件，遵循 .gitignore
+fn git_files(root: &Path) -> Option<Vec<PathBuf>> {
+    let output = git_output(
+        root,
+        &[
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ],
+    )?;
+    Some(
+        output
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(|name| root.join(name))
+            .collect(),
+    )
+}
+
+/// 没有 git 时遍历目录，跳过依赖与构建产物目录
+fn walk_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                if !should_skip_dir(&path) {
+                    dirs.push(path);
+                }
+            } else if file_type.is_file() {
+                files.push(path);
+                if files.len() >= MAX_FILES {
+                    return files;
+                }
+            }
+        }
+    }
+    files
+}
+
+/// 项目内的文件：git 仓库遵循 .gitignore，否则遍历目录
+pub fn project_files(root: &Path) -> Vec<PathBuf> {
+    git_files(root).unwrap_or_else(|| walk_files(root))
+}
+
+fn deep_mtime_iso(root: &Path) -> Option<String> {
+    let files = git_files(root);
+    let is_repo = files.is_some();
+    let newest = newest_mtime(files.unwrap_or_else(|| walk_files(root)).into_iter());
+    let last_commit = is_repo.then(|| analysis::last_commit_iso(root)).flatten();
+    // 文件都未改动过（例如只有提交记录）时以最后提交时间为准
+    analysis::last_activity(newest.map(iso).as_deref(), last_commit.as_deref())
+        .map(|time| time.to_rfc3339())
+}
+
+/// 项目的最后修改时间。默认取项目根目录的修改时间；开启深度模式时取项目内文件的最新修改时间与最后提交时间，
+/// 结果缓存一段时间
+pub fn project_mtime_iso(path: &str) -> Option<String> {
+    if !DEEP.load(Ordering::Relaxed) {
+        return file_mtime_iso(path);
+    }
+    if let Some((at, cached)) = CACHE
+        .lock()
+        .expect("mtime cache lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .get(path)
+    {
+        if at.elapsed() < CACHE_TTL {
+            return cached.clone();
+        }
+    }
+    let root = Path::new(path);
+    let value = if root.is_dir() {
+        deep_mtime_iso(root).or_else(|| file_mtime_iso(path))
+    } else {
+        None
+    };
+    CACHE
+        .lock()
+        .expect("mtime cache lock poisoned")
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_string(), (Instant::now(), value.clone()));
+    value
+}
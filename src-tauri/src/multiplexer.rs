@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{persist, AppState, IdeCategory, IdeConfig, Project};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum Multiplexer {
+    Tmux,
+    Zellij,
+}
+
+/// 终端类 IDE 启动时创建或连接以项目命名的会话
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiplexerSettings {
+    kind: Multiplexer,
+    /// 仅在新建会话时于第一个窗格中执行
+    #[serde(default)]
+    startup_command: Option<String>,
+}
+
+/// tmux 会话名不能包含 `.` 与 `:`，统一只保留字母、数字、`-` 与 `_`
+pub fn session_name(project: &Project) -> String {
+    let name: String = project
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "dev-boom".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn quote(value: &str) -> String {
+    shlex::try_quote(value)
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+/// 在终端中执行的 shell 脚本：会话已存在时直接连接，否则新建并执行启动命令
+fn session_script(project: &Project, settings: &MultiplexerSettings) -> String {
+    let name = session_name(project);
+    let startup = settings
+        .startup_command
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    match settings.kind {
+        Multiplexer::Tmux => {
+            // `=` 前缀表示精确匹配会话名
+            let target = quote(&format!("={name}"));
+            let mut create = format!(
+                "tmux new-session -d -s {} -c {}",
+                quote(&name),
+                quote(&project.path)
+            );
+            if let Some(startup) = startup {
+                create.push_str(&format!(
+                    " && tmux send-keys -t {target} {} Enter",
+                    quote(startup)
+                ));
+            }
+            format!(
+                "tmux has-session -t {target} 2>/dev/null || {{ {create}; }}; \
+                 exec tmux attach-session -t {target}"
+            )
+        }
+        Multiplexer::Zellij => {
+            let name = quote(&name);
+            let mut create = format!("zellij attach --create-background {name}");
+            if let Some(startup) = startup {
+                create.push_str(&format!(
+                    " && zellij --session {name} action write-chars {}",
+                    quote(&format!("{startup}\n"))
+                ));
+            }
+            format!(
+                "cd {} && {{ zellij list-sessions --short 2>/dev/null | grep -qxF {name} || \
+                 {{ {create}; }}; }}; exec zellij attach {name}",
+                quote(&project.path)
+            )
+        }
+    }
+}
+
+/// 终端程序执行命令所用的参数，未知终端按 `-e` 处理
+fn exec_flag(ide: &IdeConfig) -> Option<&'static str> {
+    let program = Path::new(&ide.executable)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match program.as_str() {
+        "wezterm" | "gnome-terminal" | "ptyxis" => Some("--"),
+        "xfce4-terminal" => Some("-x"),
+        "kitty" | "foot" | "wt" => None,
+        _ => Some("-e"),
+    }
+}
+
+/// 项目配置了多路复用器且 IDE 属于终端类时，在参数后追加创建/连接会话的命令
+pub fn apply(project: &Project, ide: &IdeConfig, args: &mut Vec<String>) -> Result<(), String> {
+    if ide.category != IdeCategory::Terminal {
+        return Ok(());
+    }
+    let Some(settings) = &project.metadata.multiplexer else {
+        return Ok(());
+    };
+    if cfg!(target_os = "windows") {
+        return Err("Windows 上不支持 tmux/zellij 会话".to_string());
+    }
+    if let Some(flag) = exec_flag(ide) {
+        args.push(flag.to_string());
+    }
+    args.extend([
+        "sh".to_string(),
+        "-c".to_string(),
+        session_script(project, settings),
+    ]);
+    Ok(())
+}
+
+/// 设置项目使用的多路复用器，传入 None 关闭
+#[tauri::command]
+pub fn set_project_multiplexer(
+    project_id: String,
+    multiplexer: Option<MultiplexerSettings>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.metadata.multiplexer = multiplexer.map(|mut settings| {
+        settings.startup_command = settings
+            .startup_command
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        settings
+    });
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -0,0 +1,133 @@
+use std::{collections::HashSet, path::PathBuf, process::Child};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{
+    expand_args, goto_style_for_ide, normalize_windows_path_for_ui, persist, process, spawn_ide,
+    AppState, GotoStyle, IdeConfig, Project,
+};
+
+/// 多根项目的打开方式，按 IDE 配置
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub enum MultiRootMode {
+    /// VS Code 系列合并到一个窗口，其它 IDE 每个目录一个窗口
+    #[default]
+    Auto,
+    /// 所有目录在同一窗口打开，VS Code 系列使用 `--add`
+    SingleWindow,
+    WindowPerRoot,
+}
+
+fn effective_mode(ide: &IdeConfig) -> MultiRootMode {
+    match ide.multi_root {
+        MultiRootMode::Auto if goto_style_for_ide(ide) == GotoStyle::VsCode => {
+            MultiRootMode::SingleWindow
+        }
+        MultiRootMode::Auto => MultiRootMode::WindowPerRoot,
+        mode => mode,
+    }
+}
+
+/// 以附加目录为根的临时项目，供逐个窗口启动
+fn root_project(project: &Project, root: &str) -> Project {
+    let mut sub = project.clone();
+    sub.path = root.to_string();
+    sub.paths = vec![];
+    sub.metadata.open_workspace_file = false;
+    sub
+}
+
+/// 按 IDE 配置打开项目的所有根目录，返回主目录对应的进程
+pub fn launch(
+    project: &Project,
+    ide: &IdeConfig,
+    mut args: Vec<String>,
+) -> Result<Option<Child>, String> {
+    if project.paths.is_empty() {
+        return spawn_ide(project, ide, args);
+    }
+    match effective_mode(ide) {
+        MultiRootMode::WindowPerRoot => {
+            let child = spawn_ide(project, ide, args)?;
+            for root in &project.paths {
+                let sub = root_project(project, root);
+                let args = expand_args(&ide.args_template, &sub, ide, None);
+                match spawn_ide(&sub, ide, args) {
+                    Ok(Some(child)) => process::reap(child),
+                    Ok(None) => {}
+                    Err(err) => tracing::warn!(project = %project.name, root = %root, "{err}"),
+                }
+            }
+            Ok(child)
+        }
+        _ => {
+            append_single_window_roots(project, ide, &mut args);
+            spawn_ide(project, ide, args)
+        }
+    }
+}
+
+/// 单窗口模式下把其它根目录追加到参数中
+pub fn append_single_window_roots(project: &Project, ide: &IdeConfig, args: &mut Vec<String>) {
+    if effective_mode(ide) != MultiRootMode::SingleWindow {
+        return;
+    }
+    let vscode = goto_style_for_ide(ide) == GotoStyle::VsCode;
+    for root in &project.paths {
+        if vscode {
+            args.push("--add".to_string());
+        }
+        args.push(root.clone());
+    }
+}
+
+/// 设置主目录之外的其它根目录，传入空列表表示恢复为单根项目
+#[tauri::command]
+pub fn set_project_paths(
+    project_id: String,
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut normalized = vec![];
+    for path in paths.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let dir = PathBuf::from(path);
+        if !dir.is_dir() {
+            return Err(format!("目录不存在: {path}"));
+        }
+        let canonical = dir
+            .canonicalize()
+            .map_err(|e| format!("无法读取目录 {path}: {e}"))?;
+        normalized.push(normalize_windows_path_for_ui(&canonical.to_string_lossy()));
+    }
+
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    let mut seen: HashSet<String> = HashSet::from([project.path.clone()]);
+    normalized.retain(|p| seen.insert(p.clone()));
+    project.paths = normalized;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn set_ide_multi_root_mode(
+    ide_id: String,
+    mode: MultiRootMode,
+    state: State<'_, AppState>,
+) -> Result<IdeConfig, String> {
+    let mut ides = state.store.ides_mut();
+    let ide = ides
+        .iter_mut()
+        .find(|x| x.id == ide_id)
+        .ok_or_else(|| "IDE 不存在".to_string())?;
+    ide.multi_root = mode;
+    let updated = ide.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -0,0 +1,61 @@
+use std::{process::Command, sync::Mutex, time::Duration};
+
+use crate::settings::NetworkSettings;
+
+/// 当前生效的网络设置，图标下载等没有 AppState 的代码路径也需要读取
+static CONFIG: Mutex<NetworkSettings> = Mutex::new(NetworkSettings {
+    offline_mode: false,
+    proxy: String::new(),
+});
+
+pub const OFFLINE_ERROR: &str = "已开启离线模式，不访问网络";
+
+pub fn configure(settings: &NetworkSettings) {
+    *CONFIG.lock().expect("network config lock poisoned") = settings.clone();
+}
+
+pub fn is_offline() -> bool {
+    CONFIG
+        .lock()
+        .expect("network config lock poisoned")
+        .offline_mode
+}
+
+fn proxy() -> Option<String> {
+    let proxy = CONFIG
+        .lock()
+        .expect("network config lock poisoned")
+        .proxy
+        .clone();
+    (!proxy.is_empty()).then_some(proxy)
+}
+
+/// 所有 HTTP 请求都通过这里创建客户端：离线模式下直接返回错误；
+/// 未配置代理时 reqwest 默认读取 HTTPS_PROXY / HTTP_PROXY / NO_PROXY 环境变量
+pub fn client(timeout: Duration, user_agent: &str) -> Result<reqwest::blocking::Client, String> {
+    if is_offline() {
+        return Err(OFFLINE_ERROR.to_string());
+    }
+    let mut builder = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .user_agent(user_agent);
+    if let Some(proxy) = proxy() {
+        let proxy = reqwest::Proxy::all(&proxy).map_err(|e| format!("代理地址无效: {e}"))?;
+        builder = builder.proxy(proxy);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// 访问远端的 git 命令：离线模式下返回错误，配置了代理时通过环境变量传给 git
+pub fn prepare_git(command: &mut Command) -> Result<(), String> {
+    if is_offline() {
+        return Err(OFFLINE_ERROR.to_string());
+    }
+    if let Some(proxy) = proxy() {
+        command
+            .env("HTTPS_PROXY", &proxy)
+            .env("HTTP_PROXY", &proxy)
+            .env("ALL_PROXY", &proxy);
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Condvar, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{store_events, store_lock, AppState, AppStore};
+
+/// 最后一次修改后等待的时间，拖动排序等连续操作只写一次文件
+const DEBOUNCE: Duration = Duration::from_millis(500);
+/// 写入失败（如另一个实例长时间占用锁）后重试的间隔
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+struct Pending {
+    /// 最后一次修改的时间，写入后清空
+    changed_at: Option<Instant>,
+    /// 修改后尚未发送变更事件
+    unpublished: bool,
+}
+
+static PENDING: Mutex<Pending> = Mutex::new(Pending {
+    changed_at: None,
+    unpublished: false,
+});
+static CHANGED: Condvar = Condvar::new();
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static COMPACT: AtomicBool = AtomicBool::new(false);
+/// 写入期间持有，flush 返回时保证之前的修改都已落盘
+static WRITING: Mutex<()> = Mutex::new(());
+
+pub fn configure(compact: bool) {
+    COMPACT.store(compact, Ordering::Relaxed);
+}
+
+/// store.json 的序列化格式，开启紧凑模式时不缩进
+pub fn to_json(store: &AppStore) -> serde_json::Result<String> {
+    if COMPACT.load(Ordering::Relaxed) {
+        serde_json::to_string(store)
+    } else {
+        serde_json::to_string_pretty(store)
+    }
+}
+
+/// 启动后台线程：修改后立即发送变更事件，停止修改一段时间后写入 store.json
+pub fn attach(app: &AppHandle) {
+    if APP.set(app.clone()).is_err() {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        let mut pending = PENDING.lock().expect("persist lock poisoned");
+        if pending.unpublished {
+            pending.unpublished = false;
+            drop(pending);
+            // 等调用方释放写锁后读取，事件中的数据与保存的一致
+            store_events::publish(&app.state::<AppState>().store.snapshot());
+            continue;
+        }
+        let Some(changed_at) = pending.changed_at else {
+            drop(CHANGED.wait(pending).expect("persist lock poisoned"));
+            continue;
+        };
+        let elapsed = changed_at.elapsed();
+        if elapsed < DEBOUNCE {
+            drop(
+                CHANGED
+                    .wait_timeout(pending, DEBOUNCE - elapsed)
+                    .expect("persist lock poisoned"),
+            );
+            continue;
+        }
+        drop(pending);
+        if let Err(err) = flush(&app) {
+            tracing::warn!("保存数据失败，稍后重试: {err}");
+            let _ = app.emit("store://save-failed", err);
+        }
+    });
+}
+
+/// 修改项目、IDE 或回收站后调用，可以在持有写锁时调用
+pub fn mark_dirty() {
+    let mut pending = PENDING.lock().expect("persist lock poisoned");
+    pending.changed_at = Some(Instant::now());
+    pending.unpublished = true;
+    CHANGED.notify_all();
+}
+
+pub fn is_dirty() -> bool {
+    PENDING
+        .lock()
+        .expect("persist lock poisoned")
+        .changed_at
+        .is_some()
+}
+
+/// 立即写入尚未保存的修改，应用退出时也会调用。调用方不能持有 store 的写锁。
+/// 写入失败时保留未保存标记，后台线程在 RETRY_DELAY 后重试
+pub fn flush(app: &AppHandle) -> Result<(), String> {
+    let _writing = WRITING.lock().expect("persist lock poisoned");
+    if PENDING
+        .lock()
+        .expect("persist lock poisoned")
+        .changed_at
+        .take()
+        .is_none()
+    {
+        return Ok(());
+    }
+    let result = store_lock::save(app, &app.state::<AppState>());
+    if result.is_err() {
+        let mut pending = PENDING.lock().expect("persist lock poisoned");
+        // 写入期间又有修改时保留新的时间；后台线程按 changed_at + DEBOUNCE 决定何时重试
+        pending
+            .changed_at
+            .get_or_insert_with(|| Instant::now() + RETRY_DELAY);
+        CHANGED.notify_all();
+    }
+    result
+}
+
+#[tauri::command]
+pub fn flush_store(app: AppHandle) -> Result<(), String> {
+    flush(&app)
+}
@@ -0,0 +1,20 @@
+use std::{env, path::PathBuf};
+
+/// 以该参数启动时进入便携模式
+pub const PORTABLE_ARG: &str = "--portable";
+/// 可执行文件旁存在该文件时进入便携模式，适合放在 U 盘或同步盘中使用
+const FLAG_FILE: &str = "portable";
+/// 便携模式下存放数据的目录，位于可执行文件旁
+const DATA_DIR: &str = "data";
+
+pub fn launched_with_arg() -> bool {
+    env::args().any(|arg| arg == PORTABLE_ARG)
+}
+
+/// 便携模式下的数据目录；未启用时为 None，使用系统的应用数据目录。
+/// 项目列表、设置、图标缓存与日志都位于该目录中
+pub fn data_dir() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    let dir = exe.parent()?;
+    (launched_with_arg() || dir.join(FLAG_FILE).is_file()).then(|| dir.join(DATA_DIR))
+}
@@ -0,0 +1,468 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::AppState;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessOutput {
+    pub key: String,
+    pub stream: &'static str,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessExit {
+    pub key: String,
+    pub code: Option<i32>,
+    pub success: bool,
+}
+
+/// 控制台程序不弹出黑框
+#[cfg(target_os = "windows")]
+pub fn hide_console_window(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn hide_console_window(_command: &mut Command) {}
+
+/// Unix 上让子进程在新的会话中运行（同时成为新进程组的组长），脱离应用的控制终端，
+/// 应用退出、终端关闭或收到信号时不会连带结束 IDE 等子进程
+#[cfg(unix)]
+pub fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    // SAFETY: setsid 是 async-signal-safe 的，可以在 fork 之后、exec 之前调用
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+pub fn detach(_command: &mut Command) {}
+
+/// 不需要跟踪的子进程在后台等待退出，避免留下僵尸进程
+pub fn reap(mut child: Child) {
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
+
+/// 以独立进程组启动且不跟踪的进程，如终端与文件管理器
+pub fn spawn_detached(mut command: Command) -> std::io::Result<()> {
+    detach(&mut command);
+    reap(command.spawn()?);
+    Ok(())
+}
+
+/// Windows 上 npm/pnpm 等是 .cmd 脚本，需要经由 cmd 启动
+#[cfg(target_os = "windows")]
+pub fn program_command(program: &str, args: &[&str]) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(program).args(args);
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn program_command(program: &str, args: &[&str]) -> Command {
+    let mut command = Command::new(program);
+    command.args(args);
+    command
+}
+
+/// 用户填写的完整命令行，交给系统 shell 解析
+#[cfg(target_os = "windows")]
+pub fn shell_command(line: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+    let mut command = Command::new("cmd");
+    command.arg("/C").raw_arg(line);
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn shell_command(line: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", line]);
+    command
+}
+
+/// 启动失败时附带的输出最多保留的字节数
+const CAPTURE_LIMIT: u64 = 4096;
+/// 启动日志最多保留的个数，超出时删除最早的
+const MAX_LAUNCH_LOGS: usize = 20;
+
+static LAUNCH_LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// 启动日志放在日志目录下的 launch 子目录，未配置时使用临时目录
+pub fn configure_launch_logs(store_file_path: &Path) {
+    let _ = LAUNCH_LOG_DIR.set(crate::logging::log_dir(store_file_path).join("launch"));
+}
+
+/// 删除最早的启动日志，只保留 keep 个；仍被运行中的进程占用而删除失败的文件留到下次
+fn prune_launch_logs(dir: &Path, keep: usize) {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    // 文件名以启动时间开头，字典序即时间顺序
+    files.sort();
+    let overflow = files.len().saturating_sub(keep);
+    for path in &files[..overflow] {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// 每次启动的输出写入单独的日志文件而不是管道：子进程不依赖本应用读取输出，
+/// 应用退出后继续写输出也不会因管道断开收到 SIGPIPE
+fn launch_log_file() -> std::io::Result<(PathBuf, fs::File)> {
+    let dir = LAUNCH_LOG_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(|| std::env::temp_dir().join("dev-boom-launch"));
+    fs::create_dir_all(&dir)?;
+    prune_launch_logs(&dir, MAX_LAUNCH_LOGS - 1);
+    let name = format!(
+        "{}-{}.log",
+        chrono::Local::now().format("%Y%m%d-%H%M%S%3f"),
+        uuid::Uuid::new_v4().simple()
+    );
+    let path = dir.join(name);
+    let file = fs::File::create(&path)?;
+    Ok((path, file))
+}
+
+fn read_head(path: &Path) -> String {
+    let mut head = Vec::new();
+    if let Ok(file) = fs::File::open(path) {
+        let _ = file.take(CAPTURE_LIMIT).read_to_end(&mut head);
+    }
+    String::from_utf8_lossy(&head).trim().to_string()
+}
+
+#[derive(Debug)]
+pub enum SpawnError {
+    Spawn(std::io::Error),
+    /// 观察期内以非零码退出，output 为已捕获的输出
+    EarlyExit {
+        code: Option<i32>,
+        output: String,
+    },
+}
+
+impl std::fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpawnError::Spawn(err) => write!(f, "{err}"),
+            SpawnError::EarlyExit { code, output } => {
+                let code = code
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "未知".to_string());
+                if output.is_empty() {
+                    write!(f, "进程启动后立即退出（退出码 {code}）")
+                } else {
+                    write!(f, "进程启动后立即退出（退出码 {code}）：{output}")
+                }
+            }
+        }
+    }
+}
+
+/// 启动进程并观察 window 时长：期间以非零码退出视为启动失败，错误中附带已捕获的输出。
+/// 很多启动器把工作交给已运行的实例后以 0 退出，这种情况视为成功
+pub fn spawn_checked(mut command: Command, window: Duration) -> Result<Child, SpawnError> {
+    let (log_path, stdout) = launch_log_file().map_err(SpawnError::Spawn)?;
+    let spawned = stdout.try_clone().and_then(|stderr| {
+        command
+            .stdin(Stdio::null())
+            .stdout(stdout)
+            .stderr(stderr)
+            .spawn()
+    });
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) => {
+            let _ = fs::remove_file(&log_path);
+            return Err(SpawnError::Spawn(err));
+        }
+    };
+
+    let started = Instant::now();
+    while started.elapsed() < window {
+        match child.try_wait() {
+            Ok(Some(status)) if !status.success() => {
+                return Err(SpawnError::EarlyExit {
+                    code: status.code(),
+                    output: read_head(&log_path),
+                });
+            }
+            Ok(Some(_)) | Err(_) => break,
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+        }
+    }
+    Ok(child)
+}
+
+/// 受管子进程表，key 由调用方决定（如任务运行 id）
+#[derive(Default)]
+pub struct ProcessRegistry {
+    children: Mutex<HashMap<String, Child>>,
+}
+
+impl ProcessRegistry {
+    pub fn is_running(&self, key: &str) -> bool {
+        self.children
+            .lock()
+            .expect("process registry lock poisoned")
+            .contains_key(key)
+    }
+
+    /// 结束整个进程树（npm run 等会再派生子进程）
+    pub fn stop(&self, key: &str) -> Result<(), String> {
+        let mut children = self
+            .children
+            .lock()
+            .expect("process registry lock poisoned");
+        let child = children
+            .get_mut(key)
+            .ok_or_else(|| "进程未在运行".to_string())?;
+        kill_tree(child);
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn kill_tree(child: &mut Child) {
+    let mut taskkill = Command::new("taskkill");
+    taskkill.args(["/T", "/F", "/PID", &child.id().to_string()]);
+    hide_console_window(&mut taskkill);
+    if !taskkill.status().map(|s| s.success()).unwrap_or(false) {
+        let _ = child.kill();
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn kill_tree(child: &mut Child) {
+    // 子进程以自身 pid 作为进程组启动，向整个组发送 SIGTERM
+    let group = format!("-{}", child.id());
+    let killed = Command::new("kill")
+        .args(["-TERM", "--", &group])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !killed {
+        let _ = child.kill();
+    }
+}
+
+/// 每行输出转发前的回调，用于从输出中提取信息
+pub type LineHook = Arc<dyn Fn(&str) + Send + Sync>;
+
+fn forward_lines<R: Read + Send + 'static>(
+    app: AppHandle,
+    reader: R,
+    event: String,
+    key: String,
+    stream: &'static str,
+    on_line: Option<LineHook>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            if let Some(on_line) = &on_line {
+                on_line(&line);
+            }
+            let _ = app.emit(
+                &event,
+                ProcessOutput {
+                    key: key.clone(),
+                    stream,
+                    line,
+                },
+            );
+        }
+    })
+}
+
+fn forward_output(
+    app: &AppHandle,
+    child: &mut Child,
+    key: &str,
+    prefix: &str,
+    on_line: Option<LineHook>,
+) -> Vec<thread::JoinHandle<()>> {
+    let output_event = format!("{prefix}://output");
+    let mut readers = vec![];
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(forward_lines(
+            app.clone(),
+            stdout,
+            output_event.clone(),
+            key.to_string(),
+            "stdout",
+            on_line.clone(),
+        ));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(forward_lines(
+            app.clone(),
+            stderr,
+            output_event,
+            key.to_string(),
+            "stderr",
+            on_line,
+        ));
+    }
+    readers
+}
+
+/// 启动子进程，把 stdout/stderr 逐行转发为 `{prefix}://output` 事件，
+/// 退出后发送 `{prefix}://exit` 事件。返回子进程 PID。
+pub fn spawn_streaming(
+    app: &AppHandle,
+    command: Command,
+    key: &str,
+    prefix: &str,
+) -> Result<u32, String> {
+    spawn_streaming_with(app, command, key, prefix, |_| {})
+}
+
+/// 与 `spawn_streaming` 相同，退出事件发送前先以是否成功调用 on_exit
+pub fn spawn_streaming_with(
+    app: &AppHandle,
+    mut command: Command,
+    key: &str,
+    prefix: &str,
+    on_exit: impl FnOnce(bool) + Send + 'static,
+) -> Result<u32, String> {
+    hide_console_window(&mut command);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("启动进程失败: {e}"))?;
+    let pid = child.id();
+
+    let readers = forward_output(app, &mut child, key, prefix, None);
+
+    let app = app.clone();
+    let key = key.to_string();
+    let exit_event = format!("{prefix}://exit");
+    thread::spawn(move || {
+        let status = child.wait();
+        for reader in readers {
+            let _ = reader.join();
+        }
+        let code = status.as_ref().ok().and_then(|s| s.code());
+        let success = status.map(|s| s.success()).unwrap_or(false);
+        on_exit(success);
+        let _ = app.emit(&exit_event, ProcessExit { key, code, success });
+    });
+
+    Ok(pid)
+}
+
+/// 与 `spawn_streaming` 相同，但子进程登记到 `AppState::processes`，可通过 key 停止
+pub fn spawn_managed(
+    app: &AppHandle,
+    command: Command,
+    key: &str,
+    prefix: &str,
+) -> Result<u32, String> {
+    spawn_managed_with(app, command, key, prefix, None)
+}
+
+/// 与 `spawn_managed` 相同，每行输出先交给 on_line 处理
+pub fn spawn_managed_with(
+    app: &AppHandle,
+    mut command: Command,
+    key: &str,
+    prefix: &str,
+    on_line: Option<LineHook>,
+) -> Result<u32, String> {
+    let state = app.state::<AppState>();
+    // 检查、启动与登记在同一次加锁内完成，同一 key 的并发调用不会启动两个进程
+    let mut children = state
+        .processes
+        .children
+        .lock()
+        .expect("process registry lock poisoned");
+    if children.contains_key(key) {
+        return Err("该进程已在运行".to_string());
+    }
+
+    hide_console_window(&mut command);
+    // 以自身 pid 作为进程组，stop 时可结束整个进程树
+    detach(&mut command);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("启动进程失败: {e}"))?;
+    let pid = child.id();
+
+    let readers = forward_output(app, &mut child, key, prefix, on_line);
+    children.insert(key.to_string(), child);
+    drop(children);
+
+    let app = app.clone();
+    let key = key.to_string();
+    let exit_event = format!("{prefix}://exit");
+    thread::spawn(move || {
+        // 轮询 try_wait，避免 wait 长期占用进程表锁导致无法 stop
+        let status = loop {
+            let state = app.state::<AppState>();
+            let mut children = state
+                .processes
+                .children
+                .lock()
+                .expect("process registry lock poisoned");
+            let Some(child) = children.get_mut(&key) else {
+                break None;
+            };
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    children.remove(&key);
+                    break Some(status);
+                }
+                Ok(None) => {}
+                Err(_) => {
+                    children.remove(&key);
+                    break None;
+                }
+            }
+            drop(children);
+            thread::sleep(Duration::from_millis(200));
+        };
+        for reader in readers {
+            let _ = reader.join();
+        }
+        let code = status.and_then(|s| s.code());
+        let success = status.map(|s| s.success()).unwrap_or(false);
+        let _ = app.emit(&exit_event, ProcessExit { key, code, success });
+    });
+
+    Ok(pid)
+}
@@ -0,0 +1,244 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{
+    fetch_icon_to_cache, find_project, git_output, icon_data_url_from_user_file,
+    image_file_to_data_url, load_cached_icon, load_stale_cached_icon, network, persist,
+    stable_hash, svg_icon, AppState, Project,
+};
+
+/// 仓库内可作为项目图标的文件，按优先级排列
+const REPO_LOGO_CANDIDATES: &[&str] = &[
+    "logo.svg",
+    "logo.png",
+    "icon.svg",
+    "icon.png",
+    "favicon.svg",
+    "favicon.ico",
+    ".github/logo.svg",
+    ".github/logo.png",
+    "assets/logo.svg",
+    "assets/logo.png",
+    "assets/icon.png",
+    "public/logo.svg",
+    "public/logo.png",
+    "public/favicon.svg",
+    "public/favicon.ico",
+    "static/favicon.ico",
+];
+
+/// 下载失败的站点在该时间内不再重试
+const FAVICON_MISS_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+const AVATAR_COLORS: &[&str] = &[
+    "#ef4444", "#f97316", "#eab308", "#22c55e", "#14b8a6", "#3b82f6", "#6366f1", "#a855f7",
+    "#ec4899", "#64748b",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum ProjectIconSource {
+    Custom,
+    Repository,
+    Favicon,
+    Avatar,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectIcon {
+    data_url: String,
+    source: ProjectIconSource,
+}
+
+pub fn site_icon_cache_dir(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("site-icons")
+}
+
+fn repo_logo(root: &Path) -> Option<String> {
+    REPO_LOGO_CANDIDATES
+        .iter()
+        .map(|name| root.join(name))
+        .filter(|path| path.is_file())
+        .find_map(|path| image_file_to_data_url(&path).ok())
+}
+
+/// 从 `https://host/...`、`ssh://git@host:22/...`、`git@host:owner/repo` 中取出主机名
+fn git_remote_host(url: &str) -> Option<String> {
+    let url = url.trim();
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.split('/').next()?,
+        None => url.split_once(':')?.0,
+    };
+    let host = rest.rsplit('@').next()?;
+    let host = host.split(':').next()?.to_ascii_lowercase();
+    let valid = !host.is_empty()
+        && host.contains('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    valid.then_some(host)
+}
+
+fn project_remote_url(project: &Project) -> Option<String> {
+    project
+        .metadata
+        .git_url
+        .clone()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| {
+            git_output(
+                Path::new(&project.path),
+                &["config", "--get", "remote.origin.url"],
+            )
+        })
+        .filter(|v| !v.is_empty())
+}
+
+fn host_favicon(store_file_path: &Path, host: &str) -> Option<String> {
+    let cache_dir = site_icon_cache_dir(store_file_path);
+    if let Some(cached) = load_cached_icon(&cache_dir, host) {
+        return Some(cached);
+    }
+
+    let miss_marker = cache_dir.join(format!("{host}.miss"));
+    let recently_missed = fs::metadata(&miss_marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .map(|age| age < FAVICON_MISS_TTL)
+        .unwrap_or(false);
+    // 离线时不记录失败，恢复联网后立即重试
+    if recently_missed || network::is_offline() {
+        return load_stale_cached_icon(&cache_dir, host);
+    }
+
+    let urls = [
+        format!("https://{host}/favicon.ico"),
+        format!("https://{host}/favicon.png"),
+        format!("https://{host}/apple-touch-icon.png"),
+    ];
+    let icon = fetch_icon_to_cache(&urls, &cache_dir, host);
+    if icon.is_none() {
+        let _ = fs::create_dir_all(&cache_dir);
+        let _ = fs::write(&miss_marker, b"");
+    }
+    icon.or_else(|| load_stale_cached_icon(&cache_dir, host))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// 以项目名首字母生成 SVG 头像，颜色由名称决定，保证每次一致
+fn letter_avatar(name: &str) -> String {
+    let letter: String = name
+        .trim()
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .map(|c| c.to_uppercase().collect())
+        .unwrap_or_else(|| "?".to_string());
+    let hash = u64::from_str_radix(&stable_hash(name), 16).unwrap_or(0);
+    let color = AVATAR_COLORS[(hash % AVATAR_COLORS.len() as u64) as usize];
+    let svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="64" height="64" viewBox="0 0 64 64"><rect width="64" height="64" rx="14" fill="{color}"/><text x="32" y="32" dy=".35em" text-anchor="middle" font-family="Segoe UI, Helvetica, Arial, sans-serif" font-size="32" font-weight="600" fill="#ffffff">{}</text></svg>"##,
+        escape_xml(&letter)
+    );
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(svg);
+    format!("data:image/svg+xml;source=avatar-v1;base64,{encoded}")
+}
+
+/// 依次尝试：自定义图标 → 仓库 logo → 远程仓库站点 favicon → 首字母头像
+fn resolve_project_icon(store_file_path: &Path, project: &Project) -> ProjectIcon {
+    if let Some(icon) = project.icon.clone() {
+        return ProjectIcon {
+            data_url: icon,
+            source: ProjectIconSource::Custom,
+        };
+    }
+    if let Some(icon) = repo_logo(Path::new(&project.path)) {
+        return ProjectIcon {
+            data_url: icon,
+            source: ProjectIconSource::Repository,
+        };
+    }
+    if let Some(icon) = project_remote_url(project)
+        .and_then(|url| git_remote_host(&url))
+        .and_then(|host| host_favicon(store_file_path, &host))
+    {
+        return ProjectIcon {
+            data_url: icon,
+            source: ProjectIconSource::Favicon,
+        };
+    }
+    ProjectIcon {
+        data_url: letter_avatar(&project.name),
+        source: ProjectIconSource::Avatar,
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_project_icon(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectIcon, String> {
+    let project = find_project(&state, &project_id)?;
+    let icon = resolve_project_icon(&state.file_path, &project);
+    Ok(ProjectIcon {
+        data_url: svg_icon::display_icon(icon.data_url),
+        ..icon
+    })
+}
+
+#[tauri::command]
+pub fn set_project_icon_from_file(
+    project_id: String,
+    file_path: String,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let path = PathBuf::from(file_path);
+    if !path.exists() || !path.is_file() {
+        return Err("图标文件不存在".to_string());
+    }
+
+    let icon_data_url = icon_data_url_from_user_file(&path)?;
+    update_project_icon(&state, &project_id, Some(icon_data_url))
+}
+
+/// 清除自定义图标，恢复自动识别
+#[tauri::command]
+pub fn clear_project_icon(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    update_project_icon(&state, &project_id, None)
+}
+
+fn update_project_icon(
+    state: &AppState,
+    project_id: &str,
+    icon: Option<String>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.icon = icon;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
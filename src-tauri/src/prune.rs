@@ -0,0 +1,192 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{normalize_windows_path_for_ui, path_key, persist, trash, tray, AppState, Project};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MissingAction {
+    /// 移入回收站
+    Remove,
+    /// 只设置 missing 标记（默认），目录所在的磁盘未挂载时不会误删项目
+    #[default]
+    Mark,
+    Keep,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PruneOptions {
+    missing: MissingAction,
+    /// 删除指向同一目录（规范化路径相同）的重复项目
+    remove_duplicates: bool,
+    /// 为 true 时彻底删除，不进入回收站
+    permanent: bool,
+    /// 只返回将被清理的项目，不做修改
+    dry_run: bool,
+}
+
+impl Default for PruneOptions {
+    fn default() -> Self {
+        Self {
+            missing: MissingAction::Mark,
+            remove_duplicates: true,
+            permanent: false,
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunedProject {
+    project_id: String,
+    name: String,
+    path: String,
+    /// 重复项目保留的那一项
+    kept_project_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneReport {
+    removed_missing: Vec<PrunedProject>,
+    marked_missing: Vec<PrunedProject>,
+    removed_duplicates: Vec<PrunedProject>,
+    dry_run: bool,
+}
+
+fn pruned(project: &Project, kept_project_id: Option<&str>) -> PrunedProject {
+    PrunedProject {
+        project_id: project.id.clone(),
+        name: project.name.clone(),
+        path: project.path.clone(),
+        kept_project_id: kept_project_id.map(str::to_string),
+    }
+}
+
+/// 规范化后的路径，解析符号链接与大小写差异
+fn canonical_key(path: &str) -> std::io::Result<String> {
+    let canonical = Path::new(path).canonicalize()?;
+    Ok(path_key(&normalize_windows_path_for_ui(
+        &canonical.to_string_lossy(),
+    )))
+}
+
+/// 只有明确不存在才算丢失，权限不足、网络盘超时等错误时无法判断，保留项目
+fn is_missing(key: Option<&std::io::Result<String>>) -> bool {
+    matches!(key, Some(Err(e)) if e.kind() == std::io::ErrorKind::NotFound)
+}
+
+/// 重复项目中保留收藏的、最近打开过的，其次是最早添加的
+fn keep_rank(project: &Project) -> (bool, Option<&str>, std::cmp::Reverse<&str>) {
+    (
+        project.favorite,
+        project.last_opened.as_deref(),
+        std::cmp::Reverse(project.created_at.as_str()),
+    )
+}
+
+/// 清理目录已不存在的项目与重复项目，在应用外删除了整个代码目录后使用
+#[tauri::command(async)]
+pub fn prune_projects(
+    options: Option<PruneOptions>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PruneReport, String> {
+    let options = options.unwrap_or_default();
+    // 检查目录在锁外进行
+    let keys: HashMap<String, std::io::Result<String>> = state
+        .store
+        .projects()
+        .clone()
+        .into_iter()
+        .map(|p| {
+            let key = canonical_key(&p.path);
+            (p.id, key)
+        })
+        .collect();
+
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let mut report = PruneReport {
+        dry_run: options.dry_run,
+        ..PruneReport::default()
+    };
+    let mut remove_ids = vec![];
+
+    if options.remove_duplicates {
+        let mut groups: HashMap<&str, Vec<&Project>> = HashMap::new();
+        for project in projects.iter() {
+            if let Some(Ok(key)) = keys.get(&project.id) {
+                groups.entry(key.as_str()).or_default().push(project);
+            }
+        }
+        for group in groups.into_values().filter(|g| g.len() > 1) {
+            let Some(kept) = group.iter().max_by_key(|p| keep_rank(p)) else {
+                continue;
+            };
+            for project in group.iter().filter(|p| p.id != kept.id) {
+                report
+                    .removed_duplicates
+                    .push(pruned(project, Some(&kept.id)));
+                remove_ids.push(project.id.clone());
+            }
+        }
+    }
+
+    for project in projects.iter_mut() {
+        // 新添加的项目不在 keys 中，视为存在
+        if !is_missing(keys.get(&project.id)) {
+            continue;
+        }
+        match options.missing {
+            MissingAction::Remove => {
+                report.removed_missing.push(pruned(project, None));
+                remove_ids.push(project.id.clone());
+            }
+            MissingAction::Mark => {
+                report.marked_missing.push(pruned(project, None));
+                if !options.dry_run {
+                    project.missing = true;
+                }
+            }
+            MissingAction::Keep => {}
+        }
+    }
+
+    if options.dry_run {
+        return Ok(report);
+    }
+    let mut removed = vec![];
+    projects.retain(|p| {
+        if remove_ids.contains(&p.id) {
+            removed.push(p.clone());
+            return false;
+        }
+        true
+    });
+    for project in removed.iter().cloned() {
+        if options.permanent {
+            trash::forget_project_data(&state, &project);
+        } else {
+            trash::move_to_trash(&state, project);
+        }
+    }
+    if !removed.is_empty() || !report.marked_missing.is_empty() {
+        persist::mark_dirty();
+    }
+    drop(projects);
+    if !removed.is_empty() {
+        state.undo.push("清理项目", snapshot, &state.store);
+        tray::refresh_tray_menu(&app);
+    }
+    tracing::info!(
+        removed = removed.len(),
+        marked = report.marked_missing.len(),
+        "已清理项目列表"
+    );
+    Ok(report)
+}
@@ -0,0 +1,94 @@
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, AppState};
+
+const MAX_README_BYTES: u64 = 512 * 1024;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReadmeFormat {
+    Markdown,
+    Rst,
+    Asciidoc,
+    Text,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReadme {
+    pub file_name: String,
+    pub format: ReadmeFormat,
+    pub content: String,
+    /// 超过大小上限时只返回前 `MAX_README_BYTES` 字节
+    truncated: bool,
+}
+
+fn readme_format(file_name: &str) -> Option<ReadmeFormat> {
+    let lower = file_name.to_ascii_lowercase();
+    let (stem, ext) = match lower.split_once('.') {
+        Some((stem, ext)) => (stem.to_string(), ext.to_string()),
+        None => (lower.clone(), String::new()),
+    };
+    if stem != "readme" {
+        return None;
+    }
+    match ext.as_str() {
+        "md" | "markdown" | "mdown" => Some(ReadmeFormat::Markdown),
+        "rst" => Some(ReadmeFormat::Rst),
+        "adoc" | "asciidoc" => Some(ReadmeFormat::Asciidoc),
+        "txt" | "" => Some(ReadmeFormat::Text),
+        _ => None,
+    }
+}
+
+/// 在项目根目录查找 README（不区分大小写），按 Markdown > rst > adoc > 纯文本 的顺序取
+pub fn find_readme(root: &Path) -> Option<(PathBuf, ReadmeFormat)> {
+    let mut candidates: Vec<(PathBuf, ReadmeFormat)> = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let format = readme_format(&entry.file_name().to_string_lossy())?;
+            Some((entry.path(), format))
+        })
+        .collect();
+    candidates.sort_by_key(|(path, format)| (*format as u8, path.clone()));
+    candidates.into_iter().next()
+}
+
+pub fn read_readme(root: &Path) -> Option<ProjectReadme> {
+    let (path, format) = find_readme(root)?;
+    let file = fs::File::open(&path).ok()?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut bytes = Vec::new();
+    file.take(MAX_README_BYTES).read_to_end(&mut bytes).ok()?;
+    Some(ProjectReadme {
+        file_name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        format,
+        content: String::from_utf8_lossy(&bytes).to_string(),
+        truncated: size > MAX_README_BYTES,
+    })
+}
+
+#[tauri::command(async)]
+pub fn get_project_readme(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<ProjectReadme>, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+    Ok(read_readme(root))
+}
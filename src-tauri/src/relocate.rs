@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    find_project, git_output, normalize_windows_path_for_ui, path_key, persist, scan_projects_rec,
+    tray, AppState, Project,
+};
+
+const SEARCH_DEPTH: u8 = 5;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocateCandidate {
+    path: String,
+    /// 候选目录的 origin 远端与项目记录的远端一致；项目没有记录远端时为空
+    remote_matches: Option<bool>,
+}
+
+pub fn remote_url(root: &Path) -> Option<String> {
+    git_output(root, &["config", "--get", "remote.origin.url"]).filter(|v| !v.is_empty())
+}
+
+/// `git@github.com:a/b.git` 与 `https://github.com/a/b` 视为同一仓库
+fn normalize_remote(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let url = url
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or_else(|| url.strip_prefix("git@").unwrap_or(url));
+    // 去掉认证信息，scp 风格的 `host:path` 统一为 `host/path`
+    let url = url.rsplit_once('@').map(|(_, rest)| rest).unwrap_or(url);
+    url.replacen(':', "/", 1).to_lowercase()
+}
+
+fn same_name(candidate: &Path, name: &str) -> bool {
+    candidate.file_name().is_some_and(|n| {
+        let n = n.to_string_lossy();
+        if cfg!(target_os = "windows") {
+            n.eq_ignore_ascii_case(name)
+        } else {
+            n == name
+        }
+    })
+}
+
+fn find_candidates(project: &Project, roots: &[String]) -> Vec<RelocateCandidate> {
+    let Some(name) = Path::new(&project.path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+    else {
+        return vec![];
+    };
+    let expected = project.metadata.git_url.as_deref().map(normalize_remote);
+
+    let mut found: Vec<PathBuf> = vec![];
+    for root in roots {
+        let mut dirs = vec![];
+        scan_projects_rec(Path::new(root), 0, SEARCH_DEPTH, &[], &mut |dir| {
+            dirs.push(dir.to_path_buf())
+        });
+        found.extend(dirs.into_iter().filter(|dir| same_name(dir, &name)));
+    }
+
+    let mut candidates: Vec<RelocateCandidate> = found
+        .into_iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .map(|dir| RelocateCandidate {
+            remote_matches: expected.as_ref().map(|expected| {
+                remote_url(&dir).is_some_and(|url| &normalize_remote(&url) == expected)
+            }),
+            path: normalize_windows_path_for_ui(&dir.to_string_lossy()),
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.remote_matches
+            .cmp(&a.remote_matches)
+            .then(a.path.cmp(&b.path))
+    });
+    candidates.dedup_by(|a, b| a.path == b.path);
+    // 远端明确不一致的目录只是同名，不作为候选
+    candidates.retain(|c| c.remote_matches != Some(false));
+    candidates
+}
+
+/// 在设置中的扫描根目录下查找与项目同名的目录，有记录远端时要求 origin 一致，按匹配程度排序
+#[tauri::command]
+pub async fn relocate_project(
+    project_id: String,
+    app: AppHandle,
+) -> Result<Vec<RelocateCandidate>, String> {
+    let state = app.state::<AppState>();
+    let project = find_project(&state, &project_id)?;
+    let roots = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .scan_roots
+        .iter()
+        .map(|root| root.path.clone())
+        .collect::<Vec<_>>();
+    if roots.is_empty() {
+        return Err("尚未配置扫描根目录".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || find_candidates(&project, &roots))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 用户确认候选目录后更新项目路径
+#[tauri::command]
+pub fn confirm_relocation(
+    project_id: String,
+    new_path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let dir = PathBuf::from(new_path.trim());
+    if !dir.is_dir() {
+        return Err("新路径不存在或不是目录".to_string());
+    }
+    let canonical = dir
+        .canonicalize()
+        .map_err(|e| format!("无法读取项目路径: {e}"))?;
+    let path = normalize_windows_path_for_ui(&canonical.to_string_lossy());
+
+    let mut projects = state.store.projects_mut();
+    if projects
+        .iter()
+        .any(|p| p.id != project_id && path_key(&p.path) == path_key(&path))
+    {
+        return Err("该项目路径已存在".to_string());
+    }
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    tracing::info!(
+        project = %project.name,
+        from = %project.path,
+        to = %path,
+        "项目已重新定位"
+    );
+    project.path = path;
+    project.missing = false;
+    if project.metadata.git_url.is_none() {
+        project.metadata.git_url = remote_url(&canonical);
+    }
+    let updated = project.clone();
+    persist::mark_dirty();
+    drop(projects);
+    tray::refresh_tray_menu(&app);
+    Ok(updated)
+}
@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::{analysis, list_projects, AppState, Project};
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum ReportFormat {
+    Markdown,
+    /// 内联样式的独立 HTML 文件，可直接打开或粘贴进 wiki
+    Html,
+}
+
+struct ReportRow {
+    name: String,
+    project_type: String,
+    languages: String,
+    last_activity: String,
+    git_url: String,
+    tags: String,
+}
+
+/// 取占比最高的几种语言
+fn language_summary(project: &Project) -> String {
+    let Some(stats) = &project.metadata.language_stats else {
+        return String::new();
+    };
+    stats
+        .languages
+        .iter()
+        .take(3)
+        .map(|l| format!("{} {:.0}%", l.language, l.percentage))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn report_row(project: &Project) -> ReportRow {
+    let last_commit = analysis::last_commit_iso(Path::new(&project.path));
+    let last_activity =
+        analysis::last_activity(project.last_modified.as_deref(), last_commit.as_deref())
+            .map(|at| at.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+    let mut tags = project.tags.clone();
+    tags.extend(project.metadata.system_tags.iter().cloned());
+    ReportRow {
+        name: project.name.clone(),
+        project_type: format!("{:?}", project.project_type),
+        languages: language_summary(project),
+        last_activity,
+        git_url: project.metadata.git_url.clone().unwrap_or_default(),
+        tags: tags.join(", "),
+    }
+}
+
+const HEADERS: [&str; 6] = ["名称", "类型", "语言", "最后活动", "Git 地址", "标签"];
+
+fn escape_markdown(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_markdown(rows: &[ReportRow], generated_at: &str) -> String {
+    let mut out = format!(
+        "# 项目清单\n\n生成时间：{generated_at}，共 {} 个项目\n\n",
+        rows.len()
+    );
+    out.push_str(&format!("| {} |\n", HEADERS.join(" | ")));
+    out.push_str(&format!("|{}\n", " --- |".repeat(HEADERS.len())));
+    for row in rows {
+        let git_url = if row.git_url.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", row.git_url)
+        };
+        let cells = [
+            escape_markdown(&row.name),
+            row.project_type.clone(),
+            escape_markdown(&row.languages),
+            row.last_activity.clone(),
+            escape_markdown(&git_url),
+            escape_markdown(&row.tags),
+        ];
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+fn render_html(rows: &[ReportRow], generated_at: &str) -> String {
+    let header = HEADERS
+        .iter()
+        .map(|h| format!("<th>{h}</th>"))
+        .collect::<String>();
+    let body = rows
+        .iter()
+        .map(|row| {
+            let git_url = if row.git_url.is_empty() {
+                String::new()
+            } else {
+                let url = escape_html(&row.git_url);
+                format!("<a href=\"{url}\">{url}</a>")
+            };
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{git_url}</td><td>{}</td></tr>\n",
+                escape_html(&row.name),
+                row.project_type,
+                escape_html(&row.languages),
+                row.last_activity,
+                escape_html(&row.tags),
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<title>项目清单</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", "Microsoft YaHei", sans-serif; margin: 2rem; color: #1f2328; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border: 1px solid #d0d7de; padding: 6px 10px; text-align: left; font-size: 14px; }}
+th {{ background: #f6f8fa; }}
+tr:nth-child(even) td {{ background: #fafbfc; }}
+</style>
+</head>
+<body>
+<h1>项目清单</h1>
+<p>生成时间：{generated_at}，共 {count} 个项目</p>
+<table>
+<thead><tr>{header}</tr></thead>
+<tbody>
+{body}</tbody>
+</table>
+</body>
+</html>
+"#,
+        count = rows.len(),
+    )
+}
+
+/// 按 get_projects 相同的标签筛选条件导出项目清单，返回渲染后的文本，由前端决定保存位置
+#[tauri::command(async)]
+pub fn export_report(
+    format: ReportFormat,
+    tags: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> String {
+    let rows: Vec<ReportRow> = list_projects(&state, tags).iter().map(report_row).collect();
+    let generated_at = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    match format {
+        ReportFormat::Markdown => render_markdown(&rows, &generated_at),
+        ReportFormat::Html => render_html(&rows, &generated_at),
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::{background::TaskKind, scan_root, settings::ScanRoot, tray, AppState, Project};
+
+/// 启动后稍等再扫描，避免与窗口初始化争抢磁盘
+const STARTUP_DELAY: Duration = Duration::from_secs(30);
+/// 定期检查是否到了扫描时间，修改间隔后无需重启
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+static RESCAN_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedRoot {
+    path: String,
+    error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RescanReport {
+    scanned_roots: Vec<String>,
+    failed_roots: Vec<FailedRoot>,
+    added: Vec<Project>,
+    updated: Vec<Project>,
+    unchanged: usize,
+    missing: Vec<Project>,
+}
+
+fn rescan(state: &AppState, mark_missing: bool) -> Result<RescanReport, String> {
+    if RESCAN_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("正在重新扫描".to_string());
+    }
+    let roots: Vec<ScanRoot> = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .scan_roots
+        .clone();
+    let mut report = RescanReport::default();
+    for root in roots {
+        let path = Path::new(&root.path);
+        if !path.is_dir() {
+            report.failed_roots.push(FailedRoot {
+                path: root.path,
+                error: "目录不存在".to_string(),
+            });
+            continue;
+        }
+        let result = state
+            .background
+            .run(TaskKind::Scan, root.path.clone(), |task| {
+                scan_root(
+                    path,
+                    Some(root.max_depth),
+                    mark_missing,
+                    true,
+                    false,
+                    state,
+                    task,
+                )
+            });
+        match result {
+            Ok(scanned) => {
+                report.added.extend(scanned.added);
+                report.updated.extend(scanned.updated);
+                report.unchanged += scanned.unchanged.len();
+                report.missing.extend(scanned.missing);
+                report.scanned_roots.push(root.path);
+            }
+            Err(error) => report.failed_roots.push(FailedRoot {
+                path: root.path,
+                error,
+            }),
+        }
+    }
+    RESCAN_RUNNING.store(false, Ordering::SeqCst);
+    tracing::info!(
+        roots = report.scanned_roots.len(),
+        added = report.added.len(),
+        updated = report.updated.len(),
+        "重新扫描根目录完成"
+    );
+    Ok(report)
+}
+
+/// 扫描结果通过 `rescan://done` 事件通知前端
+fn rescan_and_notify(app: &AppHandle) {
+    match rescan(&app.state::<AppState>(), false) {
+        Ok(report) => {
+            if !report.added.is_empty() {
+                tray::refresh_tray_menu(app);
+            }
+            let _ = app.emit("rescan://done", report);
+        }
+        Err(err) => tracing::warn!("重新扫描根目录失败: {err}"),
+    }
+}
+
+/// 按设置在启动时与定期在后台重新扫描所有根目录，让新克隆的仓库自动出现
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let on_startup = app
+            .state::<AppState>()
+            .settings
+            .lock()
+            .expect("settings lock poisoned")
+            .rescan
+            .on_startup;
+        if on_startup {
+            std::thread::sleep(STARTUP_DELAY);
+            rescan_and_notify(&app);
+        }
+        let mut last_run = Instant::now();
+        loop {
+            std::thread::sleep(CHECK_INTERVAL);
+            let interval_hours = app
+                .state::<AppState>()
+                .settings
+                .lock()
+                .expect("settings lock poisoned")
+                .rescan
+                .interval_hours;
+            if interval_hours == 0
+                || last_run.elapsed() < Duration::from_secs(interval_hours as u64 * 3600)
+            {
+                continue;
+            }
+            rescan_and_notify(&app);
+            last_run = Instant::now();
+        }
+    });
+}
+
+/// 依次重新扫描设置中保存的所有根目录，不存在的根目录记为失败
+#[tauri::command(async)]
+pub fn rescan_all_roots(
+    mark_missing: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<RescanReport, String> {
+    let report = rescan(&state, mark_missing.unwrap_or(false))?;
+    if !report.added.is_empty() {
+        tray::refresh_tray_menu(&app);
+    }
+    Ok(report)
+}
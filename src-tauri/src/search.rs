@@ -0,0 +1,280 @@
+use std::collections::{HashMap, HashSet};
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use serde::Serialize;
+use tauri::State;
+
+use crate::{default_launch_ides, AppState, IdeConfig, Project};
+
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// 快速切换中参与排序的项目数
+const QUICK_SWITCH_PROJECTS: usize = 10;
+/// 每个项目列出的 IDE 动作数
+const QUICK_SWITCH_IDES: usize = 2;
+/// 同一项目中排在后面的动作逐个降低的比例，终端动作排在该项目的 IDE 之后
+const QUICK_SWITCH_STEP: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MatchField {
+    Name,
+    Alias,
+    Tag,
+    Path,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    project: Project,
+    score: f64,
+    matched_field: Option<MatchField>,
+    /// 命中字段中匹配字符的下标（按字符计），用于高亮
+    indices: Vec<usize>,
+}
+
+struct FieldMatch {
+    field: MatchField,
+    score: i64,
+    indices: Vec<usize>,
+}
+
+fn best_field_match(matcher: &SkimMatcherV2, project: &Project, query: &str) -> Option<FieldMatch> {
+    // 完整输入别名时直接排在最前
+    if let Some(alias) = project
+        .aliases
+        .iter()
+        .find(|a| a.eq_ignore_ascii_case(query))
+    {
+        return Some(FieldMatch {
+            field: MatchField::Alias,
+            score: i64::MAX / 4,
+            indices: (0..alias.chars().count()).collect(),
+        });
+    }
+    // 名称与别名权重最高，标签次之，路径最低
+    let mut candidates: Vec<(MatchField, &str, i64)> = vec![(MatchField::Name, &project.name, 4)];
+    candidates.extend(
+        project
+            .aliases
+            .iter()
+            .map(|a| (MatchField::Alias, a.as_str(), 4)),
+    );
+    candidates.extend(
+        project
+            .tags
+            .iter()
+            .map(|t| (MatchField::Tag, t.as_str(), 3)),
+    );
+    candidates.push((MatchField::Path, &project.path, 2));
+
+    candidates
+        .into_iter()
+        .filter_map(|(field, text, weight)| {
+            matcher
+                .fuzzy_indices(text, query)
+                .map(|(score, indices)| FieldMatch {
+                    field,
+                    score: score * weight / 4,
+                    indices,
+                })
+        })
+        .max_by_key(|m| m.score)
+}
+
+/// 模糊匹配得分乘以 frecency 加成，常用项目排在前面
+fn rank(fuzzy_score: i64, frecency: f64, favorite: bool) -> f64 {
+    let boost = 1.0 + (1.0 + frecency).ln() * 0.15 + if favorite { 0.1 } else { 0.0 };
+    fuzzy_score as f64 * boost
+}
+
+fn search(
+    projects: &[Project],
+    frecency: &HashMap<String, f64>,
+    query: &str,
+    limit: usize,
+) -> Vec<SearchHit> {
+    let query = query.trim();
+    let mut hits: Vec<SearchHit> = if query.is_empty() {
+        // 空查询按 frecency 排序，相当于「最近常用」
+        projects
+            .iter()
+            .map(|project| SearchHit {
+                project: project.clone(),
+                score: rank(
+                    1,
+                    frecency.get(&project.id).copied().unwrap_or(0.0),
+                    project.favorite,
+                ),
+                matched_field: None,
+                indices: vec![],
+            })
+            .collect()
+    } else {
+        let matcher = SkimMatcherV2::default().smart_case();
+        projects
+            .iter()
+            .filter_map(|project| {
+                let found = best_field_match(&matcher, project, query)?;
+                let frecency = frecency.get(&project.id).copied().unwrap_or(0.0);
+                Some(SearchHit {
+                    project: project.clone(),
+                    score: rank(found.score, frecency, project.favorite),
+                    matched_field: Some(found.field),
+                    indices: found.indices,
+                })
+            })
+            .collect()
+    };
+
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.project.last_opened.cmp(&a.project.last_opened))
+            .then_with(|| a.project.display_order.cmp(&b.project.display_order))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+#[tauri::command]
+pub fn search_projects(
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Vec<SearchHit> {
+    let frecency = state
+        .history
+        .lock()
+        .expect("history lock poisoned")
+        .frecency();
+    let projects = state.store.projects();
+    search(
+        &projects,
+        &frecency,
+        &query,
+        limit.filter(|v| *v > 0).unwrap_or(DEFAULT_SEARCH_LIMIT),
+    )
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum QuickActionKind {
+    /// 对应 launch_project(projectId, ideId)
+    OpenInIde,
+    /// 对应 open_in_terminal(path)
+    OpenInTerminal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAction {
+    /// 动作的稳定标识，前端可用作列表 key
+    id: String,
+    kind: QuickActionKind,
+    project_id: String,
+    project_name: String,
+    path: String,
+    ide_id: Option<String>,
+    ide_name: Option<String>,
+    score: f64,
+    matched_field: Option<MatchField>,
+    indices: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickSwitchResult {
+    actions: Vec<QuickAction>,
+    /// 循环到列表范围内的选中下标，列表为空时为 0
+    cursor: usize,
+}
+
+/// 项目的 IDE 按偏好、在该项目中的启动次数、全局优先级排列
+fn ranked_ides(
+    state: &AppState,
+    project: &Project,
+    ides: &[IdeConfig],
+    launches: &HashMap<(String, String), usize>,
+) -> Vec<IdeConfig> {
+    let mut ranked = default_launch_ides(state, project, ides);
+    let mut rest: Vec<&IdeConfig> = ides
+        .iter()
+        .filter(|ide| !ranked.iter().any(|r| r.id == ide.id))
+        .collect();
+    rest.sort_by_key(|ide| {
+        let count = launches
+            .get(&(project.id.clone(), ide.id.clone()))
+            .copied()
+            .unwrap_or(0);
+        (std::cmp::Reverse(count), ide.priority)
+    });
+    ranked.extend(rest.into_iter().cloned());
+    ranked.truncate(QUICK_SWITCH_IDES);
+    ranked
+}
+
+/// 迷你窗口的快速切换：把匹配的项目展开为「用某 IDE 打开」「在终端打开」等动作后统一排序。
+/// cursor 为前端当前选中的下标，上下移动越界时循环到另一端
+#[tauri::command]
+pub fn quick_switch(
+    query: String,
+    cursor: Option<i64>,
+    state: State<'_, AppState>,
+) -> QuickSwitchResult {
+    // 删除项目时会在持有 projects 写锁的情况下获取 history 锁，这里先释放 history 锁
+    let (frecency, launches) = {
+        let history = state.history.lock().expect("history lock poisoned");
+        let mut launches: HashMap<(String, String), usize> = HashMap::new();
+        for record in &history.records {
+            if let Some(ide_id) = &record.ide_id {
+                *launches
+                    .entry((record.project_id.clone(), ide_id.clone()))
+                    .or_default() += 1;
+            }
+        }
+        (history.frecency(), launches)
+    };
+    let projects = state.store.projects();
+    let ides = state.store.ides();
+    let hits = search(&projects, &frecency, &query, QUICK_SWITCH_PROJECTS);
+
+    let mut seen = HashSet::new();
+    let mut actions = vec![];
+    for hit in &hits {
+        let project = &hit.project;
+        let action = |kind, ide: Option<&IdeConfig>, step: usize| QuickAction {
+            id: match ide {
+                Some(ide) => format!("ide:{}:{}", project.id, ide.id),
+                None => format!("terminal:{}", project.id),
+            },
+            kind,
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            path: project.path.clone(),
+            ide_id: ide.map(|i| i.id.clone()),
+            ide_name: ide.map(|i| i.name.clone()),
+            score: hit.score * (1.0 - QUICK_SWITCH_STEP * step as f64),
+            matched_field: hit.matched_field,
+            indices: hit.indices.clone(),
+        };
+        let project_ides = ranked_ides(&state, project, &ides, &launches);
+        for (step, ide) in project_ides.iter().enumerate() {
+            actions.push(action(QuickActionKind::OpenInIde, Some(ide), step));
+        }
+        actions.push(action(
+            QuickActionKind::OpenInTerminal,
+            None,
+            project_ides.len(),
+        ));
+    }
+    actions.retain(|a| seen.insert(a.id.clone()));
+    actions.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let cursor = match (cursor, actions.len()) {
+        (_, 0) | (None, _) => 0,
+        (Some(cursor), len) => cursor.rem_euclid(len as i64) as usize,
+    };
+    QuickSwitchResult { actions, cursor }
+}
@@ -0,0 +1,282 @@
+use tauri::State;
+
+use crate::{settings, AppState};
+
+/// 钥匙串中的服务名，密钥以 `<SERVICE>:<name>` 区分
+const SERVICE: &str = "dev-boom";
+
+/// 检查更新、CI 状态等访问 GitHub API 时使用的令牌
+pub const GITHUB_TOKEN: &str = "github-token";
+/// 访问 GitLab API 时使用的个人访问令牌
+pub const GITLAB_TOKEN: &str = "gitlab-token";
+
+/// 密钥名称会出现在钥匙串与其他子系统的设置中，只允许简单字符
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("密钥名称不能为空".to_string());
+    }
+    if name.len() > 64
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err("密钥名称只能包含字母、数字、`-`、`_` 与 `.`，且不超过 64 个字符".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// 供其他子系统按名称读取密钥，未保存或钥匙串不可用时为 None
+pub fn get(name: &str) -> Option<String> {
+    match keychain::read(name) {
+        Ok(value) => value.filter(|v| !v.is_empty()),
+        Err(e) => {
+            tracing::warn!(name, error = %e, "读取密钥失败");
+            None
+        }
+    }
+}
+
+fn update_names(state: &AppState, apply: impl FnOnce(&mut Vec<String>)) -> Result<(), String> {
+    let mut settings = state.settings.lock().expect("settings lock poisoned");
+    let mut next = settings.clone();
+    apply(&mut next.secret_names);
+    next.secret_names.sort();
+    next.secret_names.dedup();
+    if next.secret_names != settings.secret_names {
+        settings::save_settings(&settings::settings_path(&state.file_path), &next)?;
+        *settings = next;
+    }
+    Ok(())
+}
+
+/// 已保存的密钥名称，值只保存在系统钥匙串中，不会返回给前端
+#[tauri::command]
+pub fn list_secrets(state: State<'_, AppState>) -> Vec<String> {
+    state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .secret_names
+        .clone()
+}
+
+#[tauri::command(async)]
+pub fn set_secret(name: String, value: String, state: State<'_, AppState>) -> Result<(), String> {
+    let name = validate_name(&name)?;
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("密钥内容不能为空".to_string());
+    }
+    keychain::write(&name, value)?;
+    update_names(&state, |names| names.push(name.clone()))?;
+    tracing::info!(name, "已保存密钥");
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub fn delete_secret(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    let name = validate_name(&name)?;
+    keychain::delete(&name)?;
+    update_names(&state, |names| names.retain(|n| *n != name))?;
+    tracing::info!(name, "已删除密钥");
+    Ok(())
+}
+
+/// Windows 凭据管理器
+#[cfg(target_os = "windows")]
+mod keychain {
+    use windows::{
+        core::{PCWSTR, PWSTR},
+        Win32::{
+            Foundation::ERROR_NOT_FOUND,
+            Security::Credentials::{
+                CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW,
+                CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC,
+            },
+        },
+    };
+
+    use super::SERVICE;
+
+    fn target(name: &str) -> Vec<u16> {
+        format!("{SERVICE}:{name}")
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect()
+    }
+
+    fn is_not_found(e: &windows::core::Error) -> bool {
+        e.code() == ERROR_NOT_FOUND.to_hresult()
+    }
+
+    pub fn read(name: &str) -> Result<Option<String>, String> {
+        let target = target(name);
+        let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+        match unsafe {
+            CredReadW(
+                PCWSTR(target.as_ptr()),
+                CRED_TYPE_GENERIC,
+                0,
+                &mut credential,
+            )
+        } {
+            Ok(()) => {}
+            Err(e) if is_not_found(&e) => return Ok(None),
+            Err(e) => return Err(format!("读取凭据失败: {e}")),
+        }
+        let value = unsafe {
+            let blob = std::slice::from_raw_parts(
+                (*credential).CredentialBlob,
+                (*credential).CredentialBlobSize as usize,
+            );
+            let value = String::from_utf8_lossy(blob).into_owned();
+            CredFree(credential.cast());
+            value
+        };
+        Ok(Some(value))
+    }
+
+    pub fn write(name: &str, value: &str) -> Result<(), String> {
+        let mut target = target(name);
+        let mut user: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut blob = value.as_bytes().to_vec();
+        let credential = CREDENTIALW {
+            Type: CRED_TYPE_GENERIC,
+            TargetName: PWSTR(target.as_mut_ptr()),
+            UserName: PWSTR(user.as_mut_ptr()),
+            CredentialBlobSize: blob.len() as u32,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: CRED_PERSIST_LOCAL_MACHINE,
+            ..Default::default()
+        };
+        unsafe { CredWriteW(&credential, 0) }.map_err(|e| format!("保存凭据失败: {e}"))
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        let target = target(name);
+        match unsafe { CredDeleteW(PCWSTR(target.as_ptr()), CRED_TYPE_GENERIC, 0) } {
+            Ok(()) => Ok(()),
+            Err(e) if is_not_found(&e) => Ok(()),
+            Err(e) => Err(format!("删除凭据失败: {e}")),
+        }
+    }
+}
+
+/// macOS 钥匙串，通过 Security.framework 直接访问，令牌不经过命令行参数
+#[cfg(target_os = "macos")]
+mod keychain {
+    use security_framework::{
+        base::Error,
+        passwords::{delete_generic_password, get_generic_password, set_generic_password},
+    };
+
+    use super::SERVICE;
+
+    /// errSecItemNotFound
+    const NOT_FOUND: i32 = -25300;
+
+    fn is_not_found(e: &Error) -> bool {
+        e.code() == NOT_FOUND
+    }
+
+    pub fn read(name: &str) -> Result<Option<String>, String> {
+        match get_generic_password(SERVICE, name) {
+            Ok(value) => Ok(Some(String::from_utf8_lossy(&value).into_owned())),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(format!("读取钥匙串失败: {e}")),
+        }
+    }
+
+    pub fn write(name: &str, value: &str) -> Result<(), String> {
+        set_generic_password(SERVICE, name, value.as_bytes())
+            .map_err(|e| format!("保存到钥匙串失败: {e}"))
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        match delete_generic_password(SERVICE, name) {
+            Ok(()) => Ok(()),
+            Err(e) if is_not_found(&e) => Ok(()),
+            Err(e) => Err(format!("从钥匙串删除失败: {e}")),
+        }
+    }
+}
+
+/// Linux Secret Service（GNOME Keyring、KWallet 等），通过 libsecret 的 `secret-tool` 访问
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod keychain {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    use super::SERVICE;
+
+    fn secret_tool(action: &str, name: &str) -> Command {
+        let mut command = Command::new("secret-tool");
+        command
+            .arg(action)
+            .args(["service", SERVICE, "account", name]);
+        command
+    }
+
+    fn unavailable(e: std::io::Error) -> String {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "未找到 secret-tool，请安装 libsecret-tools".to_string()
+        } else {
+            format!("无法访问 Secret Service: {e}")
+        }
+    }
+
+    pub fn read(name: &str) -> Result<Option<String>, String> {
+        let output = secret_tool("lookup", name).output().map_err(unavailable)?;
+        // 找不到条目时以 1 退出且没有输出
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return match stderr.trim() {
+                "" => Ok(None),
+                message => Err(format!("读取 Secret Service 失败: {message}")),
+            };
+        }
+        Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+
+    pub fn write(name: &str, value: &str) -> Result<(), String> {
+        // 通过标准输入传递，避免出现在进程参数中
+        let mut child = Command::new("secret-tool")
+            .arg("store")
+            .arg(format!("--label={SERVICE}: {name}"))
+            .args(["service", SERVICE, "account", name])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(unavailable)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(value.as_bytes())
+                .map_err(|e| format!("写入 Secret Service 失败: {e}"))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("写入 Secret Service 失败: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "保存到 Secret Service 失败: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        let output = secret_tool("clear", name).output().map_err(unavailable)?;
+        if !output.status.success() && !output.stderr.is_empty() {
+            return Err(format!(
+                "从 Secret Service 删除失败: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
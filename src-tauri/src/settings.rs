@@ -0,0 +1,461 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use tauri::State;
+
+use crate::{
+    arg_presets, detection, hotkey, mtime, network, persist, store_events, svg_icon,
+    terminal::{TerminalApp, TerminalShell},
+    tray, AppState,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub language_stats: LanguageStatsSettings,
+    /// 最后修改与最后提交都早于该天数的项目视为长期未动
+    pub stale_threshold_days: u32,
+    pub tray: TraySettings,
+    /// 切换迷你窗口的全局快捷键，空字符串表示禁用
+    pub global_hotkey: String,
+    /// 开机自启时不显示主窗口，只驻留托盘
+    pub start_minimized: bool,
+    pub update_channel: UpdateChannel,
+    /// 未指定 IDE 启动项目时如何选择 IDE
+    pub launch_mode: LaunchMode,
+    /// 用户保存的启动参数预设，按 IDE id 分组
+    pub ide_arg_presets: BTreeMap<String, Vec<ArgPreset>>,
+    /// 自定义项目识别规则，按顺序匹配
+    pub detection_rules: Vec<DetectionRule>,
+    /// 已保存到系统钥匙串的密钥名称，其他设置通过名称引用，值不写入配置文件
+    pub secret_names: Vec<String>,
+    /// 启动时在后台检查新版本
+    pub auto_check_updates: bool,
+    pub sync: SyncSettings,
+    pub terminal_shell: TerminalShell,
+    /// 仅 macOS 生效
+    pub terminal_app: TerminalApp,
+    /// 存放项目的根目录，扫描时自动记录，用于定期重新扫描与重新定位移动过的项目
+    #[serde(deserialize_with = "deserialize_scan_roots")]
+    pub scan_roots: Vec<ScanRoot>,
+    pub rescan: RescanSettings,
+    pub network: NetworkSettings,
+    /// 把 SVG 图标转为 PNG 返回给界面，缓存中保留原始 SVG
+    pub rasterize_svg_icons: bool,
+    /// store.json 不缩进，项目较多时文件更小、写入更快
+    pub compact_store_json: bool,
+    /// 按项目内文件（遵循 .gitignore）的最新修改时间与最后提交时间排序，而不是只看项目根目录
+    pub deep_last_modified: bool,
+    /// 自建 GitLab 的主机名（如 `git.example.com`）；只有 gitlab.com 与这些主机会被当作
+    /// GitLab 查询 CI 状态并发送 GitLab 令牌
+    pub gitlab_hosts: Vec<String>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            language_stats: LanguageStatsSettings::default(),
+            stale_threshold_days: 180,
+            tray: TraySettings::default(),
+            global_hotkey: hotkey::DEFAULT_HOTKEY.to_string(),
+            start_minimized: true,
+            update_channel: UpdateChannel::default(),
+            launch_mode: LaunchMode::default(),
+            ide_arg_presets: BTreeMap::new(),
+            detection_rules: vec![],
+            secret_names: vec![],
+            auto_check_updates: true,
+            sync: SyncSettings::default(),
+            terminal_shell: TerminalShell::default(),
+            terminal_app: TerminalApp::default(),
+            scan_roots: vec![],
+            rescan: RescanSettings::default(),
+            network: NetworkSettings::default(),
+            rasterize_svg_icons: false,
+            compact_store_json: false,
+            deep_last_modified: false,
+            gitlab_hosts: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    /// 任意 git 可访问的远端地址，认证沿用本机 git 的凭据配置
+    pub repo_url: String,
+    pub branch: String,
+    pub pull_on_startup: bool,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            repo_url: String::new(),
+            branch: "main".to_string(),
+            pull_on_startup: true,
+        }
+    }
+}
+
+pub const DEFAULT_SCAN_DEPTH: u8 = 3;
+const MAX_SCAN_DEPTH: u8 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScanRoot {
+    pub path: String,
+    pub max_depth: u8,
+    /// 扫描时跳过的目录，支持 `*` 与 `?` 通配符，匹配目录名或完整路径
+    pub exclude: Vec<String>,
+}
+
+impl ScanRoot {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ScanRoot {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            max_depth: DEFAULT_SCAN_DEPTH,
+            exclude: vec![],
+        }
+    }
+}
+
+/// 旧版本只保存路径字符串
+fn deserialize_scan_roots<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ScanRoot>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Path(String),
+        Root(ScanRoot),
+    }
+    Ok(Vec::<Entry>::deserialize(deserializer)?
+        .into_iter()
+        .map(|entry| match entry {
+            Entry::Path(path) => ScanRoot::new(path),
+            Entry::Root(root) => root,
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RescanSettings {
+    /// 启动后在后台重新扫描所有根目录
+    pub on_startup: bool,
+    /// 定期重新扫描的间隔，0 表示不定期扫描
+    pub interval_hours: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkSettings {
+    /// 禁止一切网络访问：图标下载、检查更新、同步与远端检查
+    pub offline_mode: bool,
+    /// 如 `http://127.0.0.1:7890`；为空时使用 HTTPS_PROXY 等环境变量
+    pub proxy: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// 同时接收预发布版本
+    Beta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DetectionRule {
+    /// 项目根目录下的标记文件或目录名，如 `deno.json`，支持 `*` 与 `?` 通配符
+    pub marker: String,
+    /// 内置类型名（如 `Rust`）或自定义类型名（如 `Deno`），为空时只添加标签
+    pub project_type: Option<String>,
+    /// 作为系统标签添加到匹配的项目
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArgPreset {
+    pub id: String,
+    pub label: String,
+    pub args_template: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub enum LaunchMode {
+    /// 按项目的首选 IDE 启动，没有时使用优先级最高的 IDE
+    #[default]
+    Preferences,
+    /// 使用上次指定打开该项目的 IDE，没有记录时同 Preferences
+    LastUsed,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum TrayClickAction {
+    /// 恢复最后激活的窗口
+    RestoreLast,
+    ShowMain,
+    ShowMini,
+    /// 弹出托盘菜单，仅单击可用
+    ShowMenu,
+    /// 在主窗口与迷你窗口之间切换
+    Toggle,
+    Nothing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TraySettings {
+    pub single_click: TrayClickAction,
+    pub double_click: TrayClickAction,
+}
+
+impl Default for TraySettings {
+    fn default() -> Self {
+        Self {
+            single_click: TrayClickAction::RestoreLast,
+            double_click: TrayClickAction::Toggle,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LanguageStatsSettings {
+    /// 扩展名（不含点，小写）→ 语言名，优先于内置识别
+    pub extension_map: BTreeMap<String, String>,
+    /// 完整文件名（如 `Dockerfile`，不区分大小写）→ 语言名
+    pub filename_map: BTreeMap<String, String>,
+    /// 超过该大小（KB）的文件不参与统计
+    pub max_file_size_kb: u64,
+    /// JSON/YAML/XML/CSV 等数据文件的大小上限（KB），这类文件常为生成产物
+    pub max_data_file_size_kb: u64,
+    /// 按目录分组统计的层数，0 表示不分组
+    pub directory_depth: u32,
+}
+
+impl Default for LanguageStatsSettings {
+    fn default() -> Self {
+        Self {
+            extension_map: BTreeMap::new(),
+            filename_map: BTreeMap::new(),
+            max_file_size_kb: 4096,
+            max_data_file_size_kb: 1024,
+            directory_depth: 1,
+        }
+    }
+}
+
+/// 目录分组统计的最大层数
+pub const MAX_DIRECTORY_DEPTH: u32 = 4;
+
+/// 按数据文件大小上限处理的扩展名
+const DATA_FILE_EXTENSIONS: &[&str] = &[
+    "json", "jsonl", "ndjson", "geojson", "yaml", "yml", "xml", "csv", "tsv", "svg", "lock",
+];
+
+impl LanguageStatsSettings {
+    pub fn language_for(&self, path: &Path) -> Option<&str> {
+        let file_name = path.file_name()?.to_str()?;
+        if let Some(language) = self
+            .filename_map
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(file_name))
+            .map(|(_, language)| language)
+        {
+            return Some(language);
+        }
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.extension_map.get(&ext).map(String::as_str)
+    }
+
+    /// 文件大小是否在统计上限内
+    pub fn within_size_limit(&self, path: &Path, size: u64) -> bool {
+        let is_data = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| DATA_FILE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        let limit_kb = if is_data {
+            self.max_data_file_size_kb.min(self.max_file_size_kb)
+        } else {
+            self.max_file_size_kb
+        };
+        size <= limit_kb.saturating_mul(1024)
+    }
+
+    fn normalize(&mut self) {
+        self.extension_map = std::mem::take(&mut self.extension_map)
+            .into_iter()
+            .map(|(ext, language)| {
+                (
+                    ext.trim().trim_start_matches('.').to_ascii_lowercase(),
+                    language.trim().to_string(),
+                )
+            })
+            .filter(|(ext, language)| !ext.is_empty() && !language.is_empty())
+            .collect();
+        self.filename_map = std::mem::take(&mut self.filename_map)
+            .into_iter()
+            .map(|(name, language)| (name.trim().to_string(), language.trim().to_string()))
+            .filter(|(name, language)| !name.is_empty() && !language.is_empty())
+            .collect();
+        let defaults = Self::default();
+        if self.max_file_size_kb == 0 {
+            self.max_file_size_kb = defaults.max_file_size_kb;
+        }
+        if self.max_data_file_size_kb == 0 {
+            self.max_data_file_size_kb = defaults.max_data_file_size_kb;
+        }
+        self.directory_depth = self.directory_depth.min(MAX_DIRECTORY_DEPTH);
+    }
+}
+
+impl AppSettings {
+    fn normalize(&mut self) {
+        self.language_stats.normalize();
+        self.detection_rules = std::mem::take(&mut self.detection_rules)
+            .into_iter()
+            .map(|rule| DetectionRule {
+                marker: rule.marker.trim().to_string(),
+                project_type: rule
+                    .project_type
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty()),
+                tags: rule
+                    .tags
+                    .into_iter()
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect(),
+            })
+            .filter(|rule| !rule.marker.is_empty())
+            .collect();
+        if self.stale_threshold_days == 0 {
+            self.stale_threshold_days = Self::default().stale_threshold_days;
+        }
+        self.global_hotkey = self.global_hotkey.trim().to_string();
+        self.sync.repo_url = self.sync.repo_url.trim().to_string();
+        self.sync.branch = self.sync.branch.trim().to_string();
+        self.network.proxy = self.network.proxy.trim().to_string();
+        self.gitlab_hosts = std::mem::take(&mut self.gitlab_hosts)
+            .into_iter()
+            .map(|host| {
+                let host = host.trim().to_lowercase();
+                let host = host.split_once("://").map(|(_, h)| h).unwrap_or(&host);
+                host.trim_end_matches('/').to_string()
+            })
+            .filter(|host| !host.is_empty())
+            .collect();
+        if self.sync.branch.is_empty() {
+            self.sync.branch = SyncSettings::default().branch;
+        }
+        let mut seen = HashSet::new();
+        self.scan_roots = std::mem::take(&mut self.scan_roots)
+            .into_iter()
+            .map(|root| ScanRoot {
+                path: root.path.trim().to_string(),
+                max_depth: match root.max_depth {
+                    0 => DEFAULT_SCAN_DEPTH,
+                    depth => depth.min(MAX_SCAN_DEPTH),
+                },
+                exclude: root
+                    .exclude
+                    .into_iter()
+                    .map(|pattern| pattern.trim().to_string())
+                    .filter(|pattern| !pattern.is_empty())
+                    .collect(),
+            })
+            .filter(|root| !root.path.is_empty() && seen.insert(root.path.clone()))
+            .collect();
+        // 系统托盘不支持通过双击弹出菜单
+        if self.tray.double_click == TrayClickAction::ShowMenu {
+            self.tray.double_click = TraySettings::default().double_click;
+        }
+    }
+}
+
+pub fn settings_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("settings.json")
+}
+
+pub fn load_settings(path: &Path) -> AppSettings {
+    let mut settings: AppSettings = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    settings.normalize();
+    settings
+}
+
+pub fn save_settings(path: &Path, settings: &AppSettings) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    store_events::note_settings_saved(path, settings);
+    Ok(())
+}
+
+/// 让设置在各模块中生效；启动、修改设置与同步后都调用
+pub fn apply(app: &tauri::AppHandle, settings: &AppSettings) {
+    network::configure(&settings.network);
+    svg_icon::configure(settings.rasterize_svg_icons);
+    persist::configure(settings.compact_store_json);
+    mtime::configure(settings.deep_last_modified);
+    arg_presets::configure(&settings.ide_arg_presets);
+    detection::configure(&settings.detection_rules);
+    tray::apply_tray_settings(app, &settings.tray);
+}
+
+#[tauri::command]
+pub fn get_settings(state: State<'_, AppState>) -> AppSettings {
+    state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .clone()
+}
+
+#[tauri::command]
+pub fn update_settings(
+    settings: AppSettings,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<AppSettings, String> {
+    let mut settings = settings;
+    settings.normalize();
+    {
+        // 复制、保存与替换期间持有锁，避免覆盖同时进行的 set_secret/set_global_hotkey
+        let mut current = state.settings.lock().expect("settings lock poisoned");
+        // 快捷键需通过 set_global_hotkey 修改，以便重新注册
+        settings.global_hotkey = current.global_hotkey.clone();
+        // 密钥名称与钥匙串保持一致，只能通过 set_secret/delete_secret 修改
+        settings.secret_names = current.secret_names.clone();
+        save_settings(&settings_path(&state.file_path), &settings)?;
+        *current = settings.clone();
+    }
+    apply(&app, &settings);
+    Ok(settings)
+}
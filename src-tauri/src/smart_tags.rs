@@ -0,0 +1,150 @@
+use std::{fs, path::Path};
+
+use crate::{compose, detection};
+
+fn has_file_with_ext(dir: &Path, exts: &[&str]) -> bool {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .any(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|v| v.to_str())
+                .map(|ext| exts.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+                .unwrap_or(false)
+        })
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn has_docker(root: &Path) -> bool {
+    root.join("Dockerfile").is_file()
+        || root.join(".devcontainer").is_dir()
+        || compose::find_compose_file(root).is_some()
+}
+
+fn is_monorepo(root: &Path) -> bool {
+    let marker_files = [
+        "pnpm-workspace.yaml",
+        "lerna.json",
+        "nx.json",
+        "turbo.json",
+        "rush.json",
+        "go.work",
+    ];
+    if marker_files.iter().any(|name| root.join(name).is_file()) {
+        return true;
+    }
+    let npm_workspaces = read_json(&root.join("package.json"))
+        .map(|manifest| manifest.get("workspaces").is_some())
+        .unwrap_or(false);
+    if npm_workspaces {
+        return true;
+    }
+    fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|manifest| {
+            manifest
+                .get("workspace")
+                .and_then(|w| w.get("members"))
+                .and_then(|m| m.as_array())
+                .map(|members| !members.is_empty())
+        })
+        .unwrap_or(false)
+}
+
+fn uses_typescript(root: &Path) -> bool {
+    if root.join("tsconfig.json").is_file() || root.join("deno.json").is_file() {
+        return true;
+    }
+    read_json(&root.join("package.json"))
+        .map(|manifest| {
+            ["dependencies", "devDependencies"].iter().any(|section| {
+                manifest
+                    .get(section)
+                    .and_then(|deps| deps.get("typescript"))
+                    .is_some()
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn ci_tags(root: &Path) -> Vec<&'static str> {
+    let mut tags = vec![];
+    if has_file_with_ext(&root.join(".github").join("workflows"), &["yml", "yaml"]) {
+        tags.push("ci:github-actions");
+    }
+    if root.join(".gitlab-ci.yml").is_file() {
+        tags.push("ci:gitlab");
+    }
+    if root.join("azure-pipelines.yml").is_file() {
+        tags.push("ci:azure-pipelines");
+    }
+    if root.join(".circleci").join("config.yml").is_file() {
+        tags.push("ci:circleci");
+    }
+    if root.join("Jenkinsfile").is_file() {
+        tags.push("ci:jenkins");
+    }
+    if root.join(".travis.yml").is_file() {
+        tags.push("ci:travis");
+    }
+    tags
+}
+
+fn has_tests(root: &Path) -> bool {
+    let test_dirs = [
+        "tests",
+        "test",
+        "__tests__",
+        "spec",
+        "src/test",
+        "src/__tests__",
+    ];
+    if test_dirs.iter().any(|dir| root.join(dir).is_dir()) {
+        return true;
+    }
+    // Go 与部分 Python 项目把测试文件放在源码旁边
+    fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .any(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            name.ends_with("_test.go")
+                || (name.starts_with("test_") && name.ends_with(".py"))
+                || name == "pytest.ini"
+                || name.starts_with("jest.config")
+                || name.starts_with("vitest.config")
+        })
+}
+
+/// 根据项目目录中的事实自动生成系统标签，与用户标签分开保存，每次扫描时重新计算
+pub fn detect_system_tags(root: &Path) -> Vec<String> {
+    let mut tags: Vec<&str> = vec![];
+    if has_docker(root) {
+        tags.push("has-docker");
+    }
+    if is_monorepo(root) {
+        tags.push("monorepo");
+    }
+    if uses_typescript(root) {
+        tags.push("uses-typescript");
+    }
+    tags.extend(ci_tags(root));
+    if has_tests(root) {
+        tags.push("tests-present");
+    }
+    let mut tags: Vec<String> = tags.into_iter().map(str::to_string).collect();
+    for tag in detection::rule_tags(root) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    project_merge_value,
+    settings::{settings_path, AppSettings},
+    AppStore, Project,
+};
+
+/// 一次变更中增删改的条目 id；reordered 表示排列顺序发生了变化
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreChange {
+    added: Vec<String>,
+    removed: Vec<String>,
+    updated: Vec<String>,
+    reordered: bool,
+}
+
+impl StoreChange {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.updated.is_empty()
+            && !self.reordered
+    }
+}
+
+struct Publisher {
+    app: AppHandle,
+    path: PathBuf,
+    /// 上次发布时的项目与 IDE（id → 序列化内容），按原顺序
+    projects: Vec<(String, serde_json::Value)>,
+    ides: Vec<(String, serde_json::Value)>,
+}
+
+static PUBLISHER: OnceLock<Mutex<Publisher>> = OnceLock::new();
+
+fn entries<T: Serialize>(items: &[T], id: impl Fn(&T) -> &str) -> Vec<(String, serde_json::Value)> {
+    items
+        .iter()
+        .map(|item| {
+            (
+                id(item).to_string(),
+                serde_json::to_value(item).unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// 项目不比较按磁盘状态计算的字段，get_projects 刷新这些字段不算作修改
+fn project_entries(projects: &[Project]) -> Vec<(String, serde_json::Value)> {
+    projects
+        .iter()
+        .map(|p| (p.id.clone(), project_merge_value(p)))
+        .collect()
+}
+
+fn diff(
+    before: &[(String, serde_json::Value)],
+    after: &[(String, serde_json::Value)],
+    order_key: Option<&str>,
+) -> StoreChange {
+    let old: HashMap<&str, &serde_json::Value> =
+        before.iter().map(|(id, v)| (id.as_str(), v)).collect();
+    let new: HashMap<&str, &serde_json::Value> =
+        after.iter().map(|(id, v)| (id.as_str(), v)).collect();
+    let mut change = StoreChange::default();
+    for (id, value) in after {
+        match old.get(id.as_str()) {
+            None => change.added.push(id.clone()),
+            Some(previous) if *previous != value => change.updated.push(id.clone()),
+            Some(_) => {}
+        }
+    }
+    change.removed = before
+        .iter()
+        .filter(|(id, _)| !new.contains_key(id.as_str()))
+        .map(|(id, _)| id.clone())
+        .collect();
+    change.reordered = match order_key {
+        // 项目按 displayOrder 排序，只有该字段变化才算重新排序
+        Some(key) => after.iter().any(|(id, value)| {
+            old.get(id.as_str())
+                .is_some_and(|previous| previous.get(key) != value.get(key))
+        }),
+        None => {
+            let kept: Vec<&str> = before
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .filter(|id| new.contains_key(id))
+                .collect();
+            let still: Vec<&str> = after
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .filter(|id| old.contains_key(id))
+                .collect();
+            kept != still
+        }
+    };
+    change
+}
+
+/// 记录初始数据，之后每次修改（见 persist::mark_dirty）都与上次比较并发送变更事件
+pub fn attach(app: &AppHandle, path: &Path, store: &AppStore) {
+    let _ = PUBLISHER.set(Mutex::new(Publisher {
+        app: app.clone(),
+        path: path.to_path_buf(),
+        projects: project_entries(&store.projects),
+        ides: entries(&store.ides, |i| &i.id),
+    }));
+}
+
+/// 与上次发布的数据比较，有变化时发送 `store://projects-changed` / `store://ides-changed`
+pub fn publish(store: &AppStore) {
+    let Some(publisher) = PUBLISHER.get() else {
+        return;
+    };
+    let mut publisher = publisher.lock().expect("store events lock poisoned");
+    let projects = project_entries(&store.projects);
+    let ides = entries(&store.ides, |i| &i.id);
+    let project_change = diff(&publisher.projects, &projects, Some("displayOrder"));
+    let ide_change = diff(&publisher.ides, &ides, None);
+    publisher.projects = projects;
+    publisher.ides = ides;
+    if !project_change.is_empty() {
+        let _ = publisher
+            .app
+            .emit("store://projects-changed", project_change);
+    }
+    if !ide_change.is_empty() {
+        let _ = publisher.app.emit("store://ides-changed", ide_change);
+    }
+}
+
+/// 偏好设置保存到主设置文件时发送 `store://settings-changed`，内容为完整设置
+pub fn note_settings_saved(path: &Path, settings: &AppSettings) {
+    let Some(publisher) = PUBLISHER.get() else {
+        return;
+    };
+    let publisher = publisher.lock().expect("store events lock poisoned");
+    if settings_path(&publisher.path) == path {
+        let _ = publisher.app.emit("store://settings-changed", settings);
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File, OpenOptions, TryLockError},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    now_iso, parse_store, project_merge_value, save_store, store_events, store_watch, tray,
+    AppState, AppStore,
+};
+
+/// 与 store.json 同目录的锁文件，写入期间由一个实例独占
+pub const LOCK_FILE: &str = "store.json.lock";
+/// 等待其它实例释放锁的最长时间
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 本实例最近一次读取或写入的数据及文件内容的哈希，作为三方合并的基准
+struct Base {
+    store: AppStore,
+    hash: Option<u64>,
+}
+
+static BASE: Mutex<Option<Base>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreMerged {
+    /// 两边都修改过、保留了本实例版本的条目数
+    conflicts: usize,
+    revision: u64,
+}
+
+/// 启动时与重新加载后调用，记录文件当前的内容作为合并基准
+pub fn remember(store: &AppStore, content: Option<&str>) {
+    *BASE.lock().expect("store lock poisoned") = Some(Base {
+        store: store.clone(),
+        hash: content.map(store_watch::content_hash),
+    });
+}
+
+fn lock_path(store_path: &Path) -> PathBuf {
+    store_path.with_file_name(LOCK_FILE)
+}
+
+/// 获取写入锁，返回的文件关闭时自动释放；进程异常退出时由系统释放，不会留下死锁
+fn acquire(store_path: &Path) -> Result<File, String> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(store_path))
+        .map_err(|e| format!("无法打开锁文件: {e}"))?;
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(file),
+            Err(TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(TryLockError::WouldBlock) => {
+                return Err("store.json 正被其它实例占用".to_string());
+            }
+            Err(TryLockError::Error(e)) => return Err(format!("无法锁定 store.json: {e}")),
+        }
+    }
+}
+
+/// 无法解析的 store.json 在被覆盖前另存一份，文件名带时间，如 `store.json.20240506093000.corrupt`
+pub fn backup_corrupt(path: &Path, content: &str) {
+    let stamp: String = now_iso()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .take(14)
+        .collect();
+    let file_name = format!(
+        "{}.{stamp}.corrupt",
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    );
+    let backup = path.with_file_name(file_name);
+    match fs::write(&backup, content) {
+        Ok(()) => tracing::warn!(backup = %backup.display(), "store.json 无法解析，已备份"),
+        Err(e) => tracing::error!("备份无法解析的 store.json 失败: {e}"),
+    }
+}
+
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// 按 key 做三方合并：只有一边改动时采用改动的一边，两边都改动时保留本实例的版本并计为冲突；
+/// 一边删除、另一边未改动时删除，另一边改动过则保留。same 判断两个版本是否相同
+fn merge_by<T: Clone>(
+    base: &[T],
+    local: &[T],
+    remote: &[T],
+    key: impl Fn(&T) -> String,
+    same: impl Fn(&T, &T) -> bool,
+    conflicts: &mut usize,
+) -> Vec<T> {
+    let base_map: HashMap<String, &T> = base.iter().map(|item| (key(item), item)).collect();
+    let remote_map: HashMap<String, &T> = remote.iter().map(|item| (key(item), item)).collect();
+    let mut seen = HashSet::new();
+    let mut merged = vec![];
+    for ours in local {
+        let k = key(ours);
+        if !seen.insert(k.clone()) {
+            continue;
+        }
+        let base_item = base_map.get(&k);
+        let local_changed = base_item.is_none_or(|b| !same(ours, *b));
+        match remote_map.get(&k) {
+            Some(theirs) if !local_changed => merged.push((*theirs).clone()),
+            Some(theirs) => {
+                if base_item.is_some_and(|b| !same(*theirs, *b)) {
+                    *conflicts += 1;
+                }
+                merged.push(ours.clone());
+            }
+            // 其它实例删除了本实例未改动的条目
+            None if base_item.is_some() && !local_changed => {}
+            None => {
+                if base_item.is_some() {
+                    *conflicts += 1;
+                }
+                merged.push(ours.clone());
+            }
+        }
+    }
+    for theirs in remote {
+        let k = key(theirs);
+        if !seen.insert(k.clone()) {
+            continue;
+        }
+        match base_map.get(&k) {
+            None => merged.push(theirs.clone()),
+            // 本实例已删除且其它实例未改动，保持删除
+            Some(b) if same(theirs, *b) => {}
+            Some(_) => {
+                *conflicts += 1;
+                merged.push(theirs.clone());
+            }
+        }
+    }
+    merged
+}
+
+fn merge(base: &AppStore, local: &AppStore, remote: &AppStore) -> (AppStore, usize) {
+    let mut conflicts = 0;
+    let store = AppStore {
+        projects: merge_by(
+            &base.projects,
+            &local.projects,
+            &remote.projects,
+            |p| p.id.clone(),
+            |a, b| project_merge_value(a) == project_merge_value(b),
+            &mut conflicts,
+        ),
+        ides: merge_by(
+            &base.ides,
+            &local.ides,
+            &remote.ides,
+            |i| i.id.clone(),
+            same,
+            &mut conflicts,
+        ),
+        trash: merge_by(
+            &base.trash,
+            &local.trash,
+            &remote.trash,
+            |t| t.project.id.clone(),
+            same,
+            &mut conflicts,
+        ),
+        tags: merge_by(
+            &base.tags,
+            &local.tags,
+            &remote.tags,
+            |t| t.name().to_lowercase(),
+            same,
+            &mut conflicts,
+        ),
+        revision: base.revision.max(remote.revision),
+    };
+    (store, conflicts)
+}
+
+fn write(path: &Path, store: &mut AppStore) -> Result<(), String> {
+    store.revision += 1;
+    save_store(path, store)?;
+    let content = fs::read_to_string(path).ok();
+    remember(store, content.as_deref());
+    Ok(())
+}
+
+/// 在锁内写入 store.json。文件在本实例上次读写之后被其它实例改写过时，
+/// 先按 id 与文件中的数据合并，合并结果同时更新到内存并发送 `store://merged` 事件
+pub fn save(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    let path = &state.file_path;
+    let _lock = acquire(path)?;
+    let content = fs::read_to_string(path).ok();
+    let (base, base_hash) = {
+        let base = BASE.lock().expect("store lock poisoned");
+        match base.as_ref() {
+            Some(base) => (base.store.clone(), base.hash),
+            None => (AppStore::default(), None),
+        }
+    };
+    let changed = content
+        .as_deref()
+        .filter(|c| Some(store_watch::content_hash(c)) != base_hash);
+    let remote = changed.and_then(|c| {
+        let remote = parse_store(c);
+        if remote.is_none() {
+            backup_corrupt(path, c);
+        }
+        remote
+    });
+    let Some(remote) = remote else {
+        // 文件未被改动、不存在或无法解析（已另存备份），直接写入
+        let mut store = state.store.snapshot();
+        store.revision = base.revision;
+        return write(path, &mut store);
+    };
+
+    let (mut merged, conflicts) = state.store.update_all(|local| {
+        let (merged, conflicts) = merge(&base, local, &remote);
+        local.projects = merged.projects.clone();
+        local.ides = merged.ides.clone();
+        local.trash = merged.trash.clone();
+        local.tags = merged.tags.clone();
+        (merged, conflicts)
+    });
+    write(path, &mut merged)?;
+    tracing::info!(
+        revision = merged.revision,
+        conflicts,
+        "store.json 已被其它实例修改，已合并后保存"
+    );
+    // 撤销记录基于合并前的数据，恢复会覆盖其它实例对同一条目的修改
+    state.undo.clear();
+    store_events::publish(&merged);
+    tray::refresh_tray_menu(app);
+    let _ = app.emit(
+        "store://merged",
+        StoreMerged {
+            conflicts,
+            revision: merged.revision,
+        },
+    );
+    Ok(())
+}
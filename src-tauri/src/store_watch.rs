@@ -0,0 +1,126 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{load_store, persist, store_events, store_lock, tray, AppState, AppStore};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 应用自身最近一次写入各文件的内容哈希
+static WRITTEN: OnceLock<Mutex<HashMap<PathBuf, u64>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreReloaded {
+    projects: usize,
+    ides: usize,
+}
+
+pub fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn note_written(path: &Path, content: &str) {
+    WRITTEN
+        .get_or_init(Default::default)
+        .lock()
+        .expect("store watch lock poisoned")
+        .insert(path.to_path_buf(), content_hash(content));
+}
+
+fn written_by_app(path: &Path, hash: u64) -> bool {
+    WRITTEN
+        .get()
+        .and_then(|written| {
+            written
+                .lock()
+                .expect("store watch lock poisoned")
+                .get(path)
+                .copied()
+        })
+        .is_some_and(|written| written == hash)
+}
+
+/// 与 save_store 写入的格式一致
+fn memory_hash(store: &AppStore) -> Option<u64> {
+    persist::to_json(store)
+        .ok()
+        .map(|content| content_hash(&content))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
+/// 文件内容与内存中的数据不一致时重新加载，返回是否发生了重新加载
+fn reload_if_changed(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    // 还有未写入的修改时以内存数据为准，写入后会覆盖外部修改
+    if persist::is_dirty() {
+        return false;
+    }
+    let Ok(content) = fs::read_to_string(&state.file_path) else {
+        return false;
+    };
+    let hash = content_hash(&content);
+    if written_by_app(&state.file_path, hash) || memory_hash(&state.store.snapshot()) == Some(hash)
+    {
+        return false;
+    }
+    if serde_json::from_str::<AppStore>(&content).is_err() {
+        // 编辑器可能正在写入或内容有误，保留内存中的数据，等下次修改再检查
+        tracing::warn!(
+            path = %state.file_path.display(),
+            "store.json 无法解析，暂不重新加载"
+        );
+        return false;
+    }
+    let store = load_store(&state.file_path);
+    // 读取期间界面做了修改时放弃本次重新加载
+    if !state.store.replace_if_clean(store.clone()) {
+        return false;
+    }
+    store_lock::remember(&store, Some(&content));
+    store_events::publish(&store);
+    let payload = StoreReloaded {
+        projects: store.projects.len(),
+        ides: store.ides.len(),
+    };
+    // 撤销快照基于旧数据，恢复会覆盖外部修改
+    state.undo.clear();
+    tracing::info!(
+        projects = payload.projects,
+        "检测到 store.json 被外部修改，已重新加载"
+    );
+    tray::refresh_tray_menu(app);
+    let _ = app.emit("store://reloaded", payload);
+    true
+}
+
+/// 定期检查 store.json 的修改时间，被外部编辑器或同步工具改写后重新加载并发送 `store://reloaded` 事件
+pub fn start_watching(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let path = app.state::<AppState>().file_path.clone();
+        let mut last_modified = modified(&path);
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = modified(&path);
+            if current == last_modified {
+                continue;
+            }
+            last_modified = current;
+            reload_if_changed(&app);
+        }
+    });
+}
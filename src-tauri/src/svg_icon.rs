@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
+
+use resvg::{tiny_skia, usvg};
+
+/// 栅格化输出的尺寸，界面使用 128px，托盘等小图标使用 64px
+pub const SIZES: [u32; 2] = [64, 128];
+const DISPLAY_SIZE: u32 = 128;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn configure(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 系统字体加载较慢，只加载一次（首字母头像等 SVG 含文字）
+fn fontdb() -> Arc<usvg::fontdb::Database> {
+    static FONTDB: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTDB
+        .get_or_init(|| {
+            let mut db = usvg::fontdb::Database::new();
+            db.load_system_fonts();
+            Arc::new(db)
+        })
+        .clone()
+}
+
+/// 按比例缩放到 size×size 的透明画布中居中绘制，返回 PNG 数据
+pub fn rasterize(svg: &[u8], size: u32) -> Option<Vec<u8>> {
+    let options = usvg::Options {
+        fontdb: fontdb(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_data(svg, &options).ok()?;
+    let mut pixmap = tiny_skia::Pixmap::new(size, size)?;
+    let (width, height) = (tree.size().width(), tree.size().height());
+    let scale = (size as f32 / width).min(size as f32 / height);
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+        (size as f32 - width * scale) / 2.0,
+        (size as f32 - height * scale) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    pixmap.encode_png().ok()
+}
+
+/// 缓存目录中 `{key}.svg` 对应的 `{key}.{size}.png`
+pub fn rendition_path(svg_path: &Path, size: u32) -> PathBuf {
+    let stem = svg_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    svg_path.with_file_name(format!("{stem}.{size}.png"))
+}
+
+/// 写入所有尺寸的 PNG，原始 SVG 保留不变
+pub fn store_renditions(svg_path: &Path, svg: &[u8]) {
+    for size in SIZES {
+        if let Some(png) = rasterize(svg, size) {
+            let _ = fs::write(rendition_path(svg_path, size), png);
+        }
+    }
+}
+
+fn png_data_url(png: &[u8]) -> String {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+    format!("data:image/png;source=svg-raster-v1;base64,{encoded}")
+}
+
+/// 缓存 SVG 的显示用 PNG data URL，渲染结果缺失或早于 SVG 时重新生成
+pub fn cached_display_icon(svg_path: &Path, svg: &[u8]) -> Option<String> {
+    let rendition = rendition_path(svg_path, DISPLAY_SIZE);
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let fresh = matches!(
+        (modified(&rendition), modified(svg_path)),
+        (Some(png), Some(svg)) if png >= svg
+    );
+    if !fresh {
+        store_renditions(svg_path, svg);
+    }
+    fs::read(&rendition).ok().map(|png| png_data_url(&png))
+}
+
+/// 开启栅格化时把 SVG data URL 转为 PNG data URL，其它图标原样返回。
+/// 配置中保存的仍是原始 SVG，结果按内容缓存在内存中
+pub fn display_icon(icon: String) -> String {
+    static RENDERED: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+    if !enabled() || !icon.starts_with("data:image/svg+xml") {
+        return icon;
+    }
+    let rendered = RENDERED.get_or_init(Default::default);
+    if let Some(png) = rendered.lock().expect("svg cache lock poisoned").get(&icon) {
+        return png.clone();
+    }
+    let Some(svg) = icon.split_once(";base64,").and_then(|(_, data)| {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.decode(data).ok()
+    }) else {
+        return icon;
+    };
+    match rasterize(&svg, DISPLAY_SIZE) {
+        Some(png) => {
+            let png = png_data_url(&png);
+            rendered
+                .lock()
+                .expect("svg cache lock poisoned")
+                .insert(icon, png.clone());
+            png
+        }
+        None => icon,
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    fulltext, git_command, network, path_key, persist, project_merge_value, run_git,
+    settings::{self, AppSettings},
+    tags::{self, TagDefinition},
+    tray, AppState, Project,
+};
+
+const STORE_FILE: &str = "store.json";
+const SETTINGS_FILE: &str = "settings.json";
+
+static SYNC_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    added: usize,
+    removed: usize,
+    updated: usize,
+    /// 两边都修改过的项目路径，这些项目保留本机版本
+    conflicts: Vec<String>,
+    settings_updated: bool,
+    pushed: bool,
+}
+
+/// 写入同步仓库的数据：只有项目与标签定义。IDE 可执行文件路径与回收站只属于本机，
+/// 旧版本写入的完整 store.json 也能按此读取
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SyncPayload {
+    #[serde(default)]
+    projects: Vec<Project>,
+    #[serde(default)]
+    tags: Vec<TagDefinition>,
+}
+
+fn data_dir(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
+
+/// 同步仓库的本地克隆，只作为中转，每次同步前都重置为远端状态
+fn repo_dir(store_file_path: &Path) -> PathBuf {
+    data_dir(store_file_path).join("sync-repo")
+}
+
+/// 上一次同步完成时的快照，作为三方合并的共同祖先
+fn base_dir(store_file_path: &Path) -> PathBuf {
+    data_dir(store_file_path).join("sync-base")
+}
+
+/// 同步仓库的 git 命令，远程操作需要应用的网络代理设置
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let mut command = git_command(dir, args);
+    // 凭据缺失时直接失败，不要卡在交互提示上
+    command.env("GIT_TERMINAL_PROMPT", "0");
+    network::prepare_git(&mut command)?;
+    run_git(command, args)
+}
+
+/// 克隆或更新同步仓库，并把工作区重置到远端分支（远端还没有该分支时保留空工作区）
+fn prepare_repo(dir: &Path, url: &str, branch: &str) -> Result<(), String> {
+    if !dir.join(".git").is_dir() {
+        fs::create_dir_all(dir).map_err(|e| format!("创建同步目录失败: {e}"))?;
+        git(dir, &["init"])?;
+        git(dir, &["remote", "add", "origin", url])?;
+    } else {
+        git(dir, &["remote", "set-url", "origin", url])?;
+    }
+    git(dir, &["fetch", "origin"])?;
+
+    let remote_branch = format!("origin/{branch}");
+    if git(dir, &["rev-parse", "--verify", "--quiet", &remote_branch]).is_ok() {
+        git(dir, &["checkout", "-f", "-B", branch, &remote_branch])?;
+    } else {
+        git(dir, &["checkout", "--orphan", branch]).or_else(|_| git(dir, &["checkout", branch]))?;
+    }
+    Ok(())
+}
+
+fn commit_and_push(dir: &Path, branch: &str) -> Result<bool, String> {
+    git(dir, &["add", STORE_FILE, SETTINGS_FILE])?;
+    if git(dir, &["status", "--porcelain"])?.is_empty() {
+        return Ok(false);
+    }
+
+    let host = std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    let message = format!("sync from {host} at {}", crate::now_iso());
+    // 没有配置提交身份时使用固定身份，避免提交失败
+    let mut args = vec![];
+    if git(dir, &["config", "user.email"]).is_err() {
+        args.extend([
+            "-c",
+            "user.name=dev-boom",
+            "-c",
+            "user.email=dev-boom@localhost",
+        ]);
+    }
+    args.extend(["commit", "-m", &message]);
+    git(dir, &args)?;
+    git(dir, &["push", "origin", &format!("HEAD:{branch}")])?;
+    Ok(true)
+}
+
+/// 只比较持久化的用户数据，最后修改时间与 missing 在各台机器上不同
+fn same_project(a: &Project, b: &Project) -> bool {
+    project_merge_value(a) == project_merge_value(b)
+}
+
+fn by_path(projects: &[Project]) -> HashMap<String, &Project> {
+    projects.iter().map(|p| (path_key(&p.path), p)).collect()
+}
+
+/// 以路径为键做三方合并：只有一边改动时采用改动的一边；两边都改动时保留本机版本并记为冲突；
+/// 一边删除、另一边修改时保留修改后的项目
+fn merge_projects(
+    base: &[Project],
+    local: &[Project],
+    remote: &[Project],
+    report: &mut SyncReport,
+) -> Vec<Project> {
+    let base_map = by_path(base);
+    let remote_map = by_path(remote);
+    let mut seen = HashSet::new();
+    let mut merged = vec![];
+
+    for project in local {
+        let key = path_key(&project.path);
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        match (base_map.get(&key), remote_map.get(&key)) {
+            (_, Some(theirs)) if same_project(project, theirs) => merged.push(project.clone()),
+            (None, None) => merged.push(project.clone()),
+            (None, Some(_)) => {
+                report.conflicts.push(project.path.clone());
+                merged.push(project.clone());
+            }
+            (Some(base), None) => {
+                if same_project(project, base) {
+                    report.removed += 1;
+                } else {
+                    report.conflicts.push(project.path.clone());
+                    merged.push(project.clone());
+                }
+            }
+            (Some(base), Some(theirs)) => {
+                if same_project(project, base) {
+                    report.updated += 1;
+                    merged.push((*theirs).clone());
+                } else {
+                    if !same_project(theirs, base) {
+                        report.conflicts.push(project.path.clone());
+                    }
+                    merged.push(project.clone());
+                }
+            }
+        }
+    }
+
+    for project in remote {
+        let key = path_key(&project.path);
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        match base_map.get(&key) {
+            None => {
+                report.added += 1;
+                merged.push(project.clone());
+            }
+            // 本机已删除且远端未改动，保持删除
+            Some(base) if same_project(project, base) => {}
+            Some(_) => {
+                report.conflicts.push(project.path.clone());
+                merged.push(project.clone());
+            }
+        }
+    }
+    merged
+}
+
+fn settings_value(settings: &AppSettings) -> serde_json::Value {
+    serde_json::to_value(settings).unwrap_or_default()
+}
+
+/// 去掉只属于本机的设置：同步配置、代理等网络设置、全局快捷键、扫描根目录与密钥名称。
+/// 代理地址可能带有账号密码，这些设置不写入同步仓库
+fn shared_settings(settings: &AppSettings) -> AppSettings {
+    let defaults = AppSettings::default();
+    AppSettings {
+        sync: defaults.sync,
+        global_hotkey: defaults.global_hotkey,
+        network: defaults.network,
+        scan_roots: defaults.scan_roots,
+        secret_names: defaults.secret_names,
+        ..settings.clone()
+    }
+}
+
+/// 设置整体合并：本机自上次同步后没有改动时采用远端设置。
+/// 只属于本机的设置不参与比较，始终保留本机的值
+fn merge_settings(
+    base: Option<&AppSettings>,
+    local: &AppSettings,
+    remote: Option<AppSettings>,
+) -> Option<AppSettings> {
+    let (base, mut remote) = (base?, remote?);
+    if settings_value(&shared_settings(local)) != settings_value(&shared_settings(base)) {
+        return None;
+    }
+    remote.sync = local.sync.clone();
+    remote.global_hotkey = local.global_hotkey.clone();
+    remote.network = local.network.clone();
+    remote.scan_roots = local.scan_roots.clone();
+    remote.secret_names = local.secret_names.clone();
+    (settings_value(&remote) != settings_value(local)).then_some(remote)
+}
+
+fn read_payload(path: &Path) -> Option<SyncPayload> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 项目去掉按磁盘状态计算的字段，并且不受 compact_store_json 影响，
+/// 内容不变时两台机器写出的文件完全相同，不会产生多余的提交
+fn save_payload(path: &Path, payload: &SyncPayload) -> Result<(), String> {
+    let projects: Vec<serde_json::Value> =
+        payload.projects.iter().map(project_merge_value).collect();
+    let value = serde_json::json!({ "projects": projects, "tags": payload.tags });
+    let content = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+fn read_settings(path: &Path) -> Option<AppSettings> {
+    path.is_file().then(|| settings::load_settings(path))
+}
+
+fn write_snapshot(dir: &Path, payload: &SyncPayload, settings: &AppSettings) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("创建目录失败: {e}"))?;
+    save_payload(&dir.join(STORE_FILE), payload)?;
+    settings::save_settings(&dir.join(SETTINGS_FILE), &shared_settings(settings))
+}
+
+fn run_sync(app: &AppHandle) -> Result<SyncReport, String> {
+    let state = app.state::<AppState>();
+    let config = state
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .sync
+        .clone();
+    let url = config.repo_url.trim();
+    if url.is_empty() {
+        return Err("尚未配置同步仓库".to_string());
+    }
+    let branch = config.branch.trim();
+
+    let repo = repo_dir(&state.file_path);
+    let base = base_dir(&state.file_path);
+    prepare_repo(&repo, url, branch)?;
+
+    let remote_store = read_payload(&repo.join(STORE_FILE));
+    let remote_settings = read_settings(&repo.join(SETTINGS_FILE));
+    let base_store = read_payload(&base.join(STORE_FILE)).unwrap_or_default();
+    let base_settings = read_settings(&base.join(SETTINGS_FILE));
+
+    let mut report = SyncReport::default();
+    if let Some(remote_store) = &remote_store {
+        let mut projects = state.store.projects_mut();
+        let merged = merge_projects(
+            &base_store.projects,
+            &projects,
+            &remote_store.projects,
+            &mut report,
+        );
+        *projects = merged;
+        let mut tags = state.store.tags_mut();
+        tags::merge(&base_store.tags, &mut tags, &remote_store.tags);
+        tags::ensure_definitions(&projects, &mut tags);
+        persist::mark_dirty();
+        drop(tags);
+        drop(projects);
+        // 撤销记录基于同步前的数据，整体替换后不再适用
+        state.undo.clear();
+    }
+    // IDE 可执行文件路径与机器相关，只同步项目与标签定义
+    let projects = state.store.projects().clone();
+    let tags = state.store.tags().clone();
+    let merged_store = SyncPayload { projects, tags };
+
+    // 拉取期间本机设置可能已被修改，持有锁时按当前设置合并并写回
+    let (merged_settings, settings_updated) = {
+        let mut current = state.settings.lock().expect("settings lock poisoned");
+        match merge_settings(base_settings.as_ref(), &current, remote_settings) {
+            Some(remote) => {
+                settings::save_settings(&settings::settings_path(&state.file_path), &remote)?;
+                *current = remote.clone();
+                (remote, true)
+            }
+            None => (current.clone(), false),
+        }
+    };
+    if settings_updated {
+        settings::apply(app, &merged_settings);
+        report.settings_updated = true;
+    }
+
+    write_snapshot(&repo, &merged_store, &merged_settings)?;
+    report.pushed = commit_and_push(&repo, branch)?;
+    write_snapshot(&base, &merged_store, &merged_settings)?;
+
+    tray::refresh_tray_menu(app);
+    fulltext::start_indexing(app);
+    tracing::info!(
+        added = report.added,
+        removed = report.removed,
+        updated = report.updated,
+        conflicts = report.conflicts.len(),
+        pushed = report.pushed,
+        "同步完成"
+    );
+    Ok(report)
+}
+
+/// 同一时间只允许一个同步任务；结果通过 `sync://done` / `sync://error` 事件通知前端
+fn sync_and_notify(app: &AppHandle) -> Result<SyncReport, String> {
+    if SYNC_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("同步正在进行中".to_string());
+    }
+    let result = run_sync(app);
+    SYNC_RUNNING.store(false, Ordering::SeqCst);
+    match &result {
+        Ok(report) => {
+            let _ = app.emit("sync://done", report.clone());
+        }
+        Err(err) => {
+            tracing::warn!("同步失败: {err}");
+            let _ = app.emit("sync://error", err.clone());
+        }
+    }
+    result
+}
+
+/// 启用同步且勾选了启动时拉取时，在后台线程中同步一次
+pub fn sync_on_startup(app: &AppHandle) {
+    let config = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .sync
+        .clone();
+    if !config.enabled || !config.pull_on_startup || network::is_offline() {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let _ = sync_and_notify(&app);
+    });
+}
+
+/// 拉取远端数据做三方合并，写回本地后提交并推送到同步仓库
+#[tauri::command]
+pub async fn sync_now(app: AppHandle) -> Result<SyncReport, String> {
+    tauri::async_runtime::spawn_blocking(move || sync_and_notify(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{persist, AppState, Project};
+
+/// 自动创建的标签按名称从中取色，同名标签在各台机器上颜色一致
+const PALETTE: &[&str] = &[
+    "#ef4444", "#f97316", "#eab308", "#22c55e", "#14b8a6", "#3b82f6", "#6366f1", "#a855f7",
+    "#ec4899", "#64748b",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagDefinition {
+    name: String,
+    /// `#rrggbb` 形式的颜色
+    color: String,
+    /// emoji 或图标名
+    #[serde(default)]
+    icon: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+impl TagDefinition {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn default_color(name: &str) -> String {
+    let hash = name
+        .to_lowercase()
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(u32::from(b)));
+    PALETTE[hash as usize % PALETTE.len()].to_string()
+}
+
+fn find<'a>(definitions: &'a [TagDefinition], name: &str) -> Option<&'a TagDefinition> {
+    definitions
+        .iter()
+        .find(|d| d.name.eq_ignore_ascii_case(name))
+}
+
+/// 项目引用了但还没有定义的标签按默认颜色补上
+pub fn ensure_definitions(projects: &[Project], definitions: &mut Vec<TagDefinition>) {
+    for tag in projects.iter().flat_map(|p| p.tags.iter()) {
+        if find(definitions, tag).is_none() {
+            definitions.push(TagDefinition {
+                name: tag.clone(),
+                color: default_color(tag),
+                icon: None,
+                description: None,
+            });
+        }
+    }
+}
+
+/// 同步时合并标签定义：本机修改过的保留本机的，否则采用远端的；远端新增的加入，本机已删除的不再加回
+pub fn merge(base: &[TagDefinition], local: &mut Vec<TagDefinition>, remote: &[TagDefinition]) {
+    for theirs in remote {
+        let base_def = find(base, &theirs.name);
+        match local
+            .iter_mut()
+            .find(|d| d.name.eq_ignore_ascii_case(&theirs.name))
+        {
+            Some(ours) if base_def == Some(ours) => *ours = theirs.clone(),
+            Some(_) => {}
+            None if base_def.is_none() => local.push(theirs.clone()),
+            None => {}
+        }
+    }
+}
+
+fn normalize_color(color: Option<String>, name: &str) -> Result<String, String> {
+    let Some(color) = color
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+    else {
+        return Ok(default_color(name));
+    };
+    let hex = color.strip_prefix('#').unwrap_or(&color);
+    if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("颜色格式无效: {color}"));
+    }
+    Ok(format!("#{hex}"))
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[tauri::command]
+pub fn list_tag_definitions(state: State<'_, AppState>) -> Vec<TagDefinition> {
+    state.store.tags().clone()
+}
+
+/// 新建或修改标签定义；传入 original_name 且名称变化时，同时重命名项目中的标签
+#[tauri::command]
+pub fn save_tag_definition(
+    name: String,
+    original_name: Option<String>,
+    color: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TagDefinition, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("标签名称不能为空".to_string());
+    }
+    let definition = TagDefinition {
+        color: normalize_color(color, &name)?,
+        name,
+        icon: trimmed(icon),
+        description: trimmed(description),
+    };
+    let original = original_name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| definition.name.clone());
+    let renamed = !original.eq_ignore_ascii_case(&definition.name);
+
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let mut tags = state.store.tags_mut();
+    if renamed && find(&tags, &definition.name).is_some() {
+        return Err(format!("标签已存在: {}", definition.name));
+    }
+    match tags
+        .iter_mut()
+        .find(|d| d.name.eq_ignore_ascii_case(&original))
+    {
+        Some(existing) => *existing = definition.clone(),
+        None => tags.push(definition.clone()),
+    }
+    if renamed {
+        for project in projects.iter_mut() {
+            for tag in project
+                .tags
+                .iter_mut()
+                .filter(|t| t.eq_ignore_ascii_case(&original))
+            {
+                *tag = definition.name.clone();
+            }
+            // 原本同时有两个标签的项目去重
+            let mut seen = HashSet::new();
+            project.tags.retain(|t| seen.insert(t.to_lowercase()));
+        }
+    }
+    persist::mark_dirty();
+    drop(tags);
+    drop(projects);
+    if renamed {
+        state
+            .undo
+            .push(&format!("重命名标签 {original}"), snapshot, &state.store);
+    }
+    Ok(definition)
+}
+
+/// 删除标签定义，并从所有项目中移除该标签，否则定义会被自动重新创建
+#[tauri::command]
+pub fn delete_tag_definition(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    let snapshot = state.store.snapshot();
+    let mut projects = state.store.projects_mut();
+    let mut tags = state.store.tags_mut();
+    let before = tags.len();
+    tags.retain(|d| !d.name.eq_ignore_ascii_case(&name));
+    if tags.len() == before {
+        return Err("标签不存在".to_string());
+    }
+    for project in projects.iter_mut() {
+        project.tags.retain(|t| !t.eq_ignore_ascii_case(&name));
+    }
+    persist::mark_dirty();
+    drop(tags);
+    drop(projects);
+    state
+        .undo
+        .push(&format!("删除标签 {name}"), snapshot, &state.store);
+    Ok(())
+}
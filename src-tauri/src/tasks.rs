@@ -0,0 +1,266 @@
+use std::{fs, path::Path};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{envfile, find_project, process, AppState};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum TaskKind {
+    Npm,
+    Cargo,
+    Make,
+    Just,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTask {
+    /// `kind:name`，作为 run_project_task 的参数
+    id: String,
+    name: String,
+    kind: TaskKind,
+    program: String,
+    args: Vec<String>,
+    command_line: String,
+}
+
+impl ProjectTask {
+    fn new(kind: TaskKind, name: &str, program: &str, args: Vec<String>) -> Self {
+        let prefix = match kind {
+            TaskKind::Npm => "npm",
+            TaskKind::Cargo => "cargo",
+            TaskKind::Make => "make",
+            TaskKind::Just => "just",
+        };
+        let command_line = std::iter::once(program.to_string())
+            .chain(args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            id: format!("{prefix}:{name}"),
+            name: name.to_string(),
+            kind,
+            program: program.to_string(),
+            args,
+            command_line,
+        }
+    }
+}
+
+pub fn node_package_manager(root: &Path) -> &'static str {
+    if root.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if root.join("yarn.lock").exists() {
+        "yarn"
+    } else if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+        "bun"
+    } else {
+        "npm"
+    }
+}
+
+fn npm_tasks(root: &Path) -> Vec<ProjectTask> {
+    let Ok(content) = fs::read_to_string(root.join("package.json")) else {
+        return vec![];
+    };
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return vec![];
+    };
+    let manager = node_package_manager(root);
+    manifest
+        .get("scripts")
+        .and_then(|v| v.as_object())
+        .map(|scripts| {
+            scripts
+                .keys()
+                .map(|name| {
+                    ProjectTask::new(
+                        TaskKind::Npm,
+                        name,
+                        manager,
+                        vec!["run".to_string(), name.clone()],
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn cargo_tasks(root: &Path) -> Vec<ProjectTask> {
+    let Ok(content) = fs::read_to_string(root.join("Cargo.toml")) else {
+        return vec![];
+    };
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return vec![];
+    };
+
+    let mut bins: Vec<String> = manifest
+        .get("bin")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|bin| bin.get("name").and_then(|n| n.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // 没有显式 [[bin]] 时，src/main.rs 对应与包同名的默认 bin
+    if let Some(package_name) = manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+    {
+        if root.join("src").join("main.rs").exists() && !bins.iter().any(|b| b == package_name) {
+            bins.insert(0, package_name.to_string());
+        }
+    }
+
+    bins.iter()
+        .map(|name| {
+            ProjectTask::new(
+                TaskKind::Cargo,
+                name,
+                "cargo",
+                vec!["run".to_string(), "--bin".to_string(), name.clone()],
+            )
+        })
+        .collect()
+}
+
+fn make_targets(content: &str) -> Vec<String> {
+    let mut targets = vec![];
+    for line in content.lines() {
+        if line.starts_with(|c: char| c.is_whitespace() || c == '#' || c == '.') {
+            continue;
+        }
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // 跳过变量赋值 `A := b` / `A ::= b`
+        if rest.starts_with('=') || rest.starts_with(":=") || head.contains('=') {
+            continue;
+        }
+        for name in head.split_whitespace() {
+            if name.contains('%') || name.contains('$') {
+                continue;
+            }
+            if !targets.iter().any(|t| t == name) {
+                targets.push(name.to_string());
+            }
+        }
+    }
+    targets
+}
+
+fn make_tasks(root: &Path) -> Vec<ProjectTask> {
+    let content = ["Makefile", "makefile", "GNUmakefile"]
+        .iter()
+        .find_map(|name| fs::read_to_string(root.join(name)).ok());
+    let Some(content) = content else {
+        return vec![];
+    };
+    make_targets(&content)
+        .iter()
+        .map(|name| ProjectTask::new(TaskKind::Make, name, "make", vec![name.clone()]))
+        .collect()
+}
+
+fn just_recipes(content: &str) -> Vec<String> {
+    let mut recipes = vec![];
+    for line in content.lines() {
+        if line.starts_with(|c: char| c.is_whitespace() || c == '#' || c == '[') {
+            continue;
+        }
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') {
+            continue;
+        }
+        let head = head.trim_start_matches('@');
+        let Some(name) = head.split_whitespace().next() else {
+            continue;
+        };
+        // `_` 开头的是私有 recipe；set/alias/export 等是指令
+        if name.starts_with('_')
+            || matches!(name, "set" | "alias" | "export" | "import" | "mod")
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            continue;
+        }
+        if !recipes.iter().any(|r| r == name) {
+            recipes.push(name.to_string());
+        }
+    }
+    recipes
+}
+
+fn just_tasks(root: &Path) -> Vec<ProjectTask> {
+    let content = ["justfile", "Justfile", ".justfile"]
+        .iter()
+        .find_map(|name| fs::read_to_string(root.join(name)).ok());
+    let Some(content) = content else {
+        return vec![];
+    };
+    just_recipes(&content)
+        .iter()
+        .map(|name| ProjectTask::new(TaskKind::Just, name, "just", vec![name.clone()]))
+        .collect()
+}
+
+fn collect_tasks(root: &Path) -> Vec<ProjectTask> {
+    let mut tasks = npm_tasks(root);
+    tasks.extend(cargo_tasks(root));
+    tasks.extend(make_tasks(root));
+    tasks.extend(just_tasks(root));
+    tasks
+}
+
+fn task_run_key(project_id: &str, task_id: &str) -> String {
+    format!("{project_id}/{task_id}")
+}
+
+#[tauri::command]
+pub fn list_project_tasks(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectTask>, String> {
+    let project = find_project(&state, &project_id)?;
+    Ok(collect_tasks(Path::new(&project.path)))
+}
+
+/// 以受管子进程运行任务，输出通过 `task://output` / `task://exit` 事件推送，返回运行 key
+#[tauri::command]
+pub fn run_project_task(
+    project_id: String,
+    task: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    let task = collect_tasks(root)
+        .into_iter()
+        .find(|t| t.id == task)
+        .ok_or_else(|| "任务不存在".to_string())?;
+
+    let args: Vec<&str> = task.args.iter().map(String::as_str).collect();
+    let mut command = process::program_command(&task.program, &args);
+    command.current_dir(root);
+    envfile::apply_env_file(&mut command, &project);
+
+    let key = task_run_key(&project.id, &task.id);
+    process::spawn_managed(&app, command, &key, "task")?;
+    Ok(key)
+}
+
+#[tauri::command]
+pub fn stop_project_task(run_key: String, state: State<'_, AppState>) -> Result<(), String> {
+    state.processes.stop(&run_key)
+}
@@ -0,0 +1,368 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use uuid::Uuid;
+
+use crate::{add_project, process, split_args_template, AppState, NewProjectInput, ProjectType};
+
+/// 复制本地模板目录时跳过的依赖与构建产物
+const COPY_SKIP: &[&str] = &[".git", "node_modules", "target", "dist", "build", ".venv"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum TemplateKind {
+    /// 在父目录中运行生成命令，如 `cargo new {name}`
+    Command,
+    /// 复制本地目录
+    Folder,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTemplate {
+    id: String,
+    name: String,
+    kind: TemplateKind,
+    /// 生成命令，支持 `{name}` 与 `{projectPath}` 占位符
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    source_dir: Option<String>,
+    /// 为空时按生成结果自动识别
+    #[serde(default)]
+    project_type: Option<ProjectType>,
+    #[serde(default, skip_deserializing)]
+    builtin: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTemplateInput {
+    name: String,
+    kind: TemplateKind,
+    command: Option<String>,
+    source_dir: Option<String>,
+    project_type: Option<ProjectType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct TemplateStore {
+    templates: Vec<ProjectTemplate>,
+}
+
+fn builtin(id: &str, name: &str, command: &str, project_type: ProjectType) -> ProjectTemplate {
+    ProjectTemplate {
+        id: id.to_string(),
+        name: name.to_string(),
+        kind: TemplateKind::Command,
+        command: Some(command.to_string()),
+        source_dir: None,
+        project_type: Some(project_type),
+        builtin: true,
+    }
+}
+
+fn builtin_templates() -> Vec<ProjectTemplate> {
+    vec![
+        builtin(
+            "cargo-bin",
+            "Rust 可执行程序",
+            "cargo new {name}",
+            ProjectType::Rust,
+        ),
+        builtin(
+            "cargo-lib",
+            "Rust 库",
+            "cargo new --lib {name}",
+            ProjectType::Rust,
+        ),
+        builtin(
+            "vite",
+            "Vite (TypeScript)",
+            "npm create vite@latest {name} -- --template vanilla-ts",
+            ProjectType::Nodejs,
+        ),
+        builtin("uv", "Python (uv)", "uv init {name}", ProjectType::Python),
+        builtin(
+            "dotnet-console",
+            ".NET 控制台程序",
+            "dotnet new console -o {name}",
+            ProjectType::Dotnet,
+        ),
+    ]
+}
+
+fn templates_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("templates.json")
+}
+
+fn load_templates(store_file_path: &Path) -> TemplateStore {
+    fs::read_to_string(templates_path(store_file_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_templates(store_file_path: &Path, store: &TemplateStore) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    fs::write(templates_path(store_file_path), content).map_err(|e| e.to_string())
+}
+
+fn all_templates(store_file_path: &Path) -> Vec<ProjectTemplate> {
+    let mut templates = builtin_templates();
+    templates.extend(load_templates(store_file_path).templates);
+    templates
+}
+
+/// 项目名会作为目录名，不允许包含路径分隔符
+fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("项目名称不能为空".to_string());
+    }
+    if name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err("项目名称不能包含路径分隔符".to_string());
+    }
+    Ok(name)
+}
+
+/// 符号链接按链接本身复制，不跟随到模板目录之外；Windows 上创建链接需要额外权限，跳过
+#[cfg(unix)]
+fn copy_link(source: &Path, target: &Path) -> Result<(), String> {
+    let link =
+        fs::read_link(source).map_err(|e| format!("读取链接失败 {}: {e}", source.display()))?;
+    std::os::unix::fs::symlink(link, target)
+        .map_err(|e| format!("创建链接失败 {}: {e}", target.display()))
+}
+
+#[cfg(not(unix))]
+fn copy_link(source: &Path, _target: &Path) -> Result<(), String> {
+    tracing::warn!(path = %source.display(), "跳过模板中的符号链接");
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path, copied: &mut usize) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("创建目录失败 {}: {e}", to.display()))?;
+    let entries = fs::read_dir(from).map_err(|e| format!("读取模板目录失败: {e}"))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let source = entry.path();
+        let target = to.join(&name);
+        let Ok(file_type) = fs::symlink_metadata(&source).map(|m| m.file_type()) else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            copy_link(&source, &target)?;
+        } else if file_type.is_dir() {
+            if COPY_SKIP.contains(&name.to_string_lossy().as_ref()) {
+                continue;
+            }
+            copy_dir(&source, &target, copied)?;
+        } else {
+            fs::copy(&source, &target)
+                .map_err(|e| format!("复制文件失败 {}: {e}", source.display()))?;
+            *copied += 1;
+        }
+    }
+    Ok(())
+}
+
+/// 生成成功后登记为项目，并发送 `template://created` 事件
+fn register_created(app: &AppHandle, target: &Path, template: &ProjectTemplate) {
+    if !target.is_dir() {
+        tracing::warn!(
+            template = %template.name,
+            target = %target.display(),
+            "模板没有生成项目目录"
+        );
+        return;
+    }
+    let input = NewProjectInput {
+        name: String::new(),
+        path: target.to_string_lossy().to_string(),
+        project_type: template.project_type.clone(),
+        favorite: None,
+        tags: None,
+        description: None,
+        ide_preferences: None,
+    };
+    match add_project(input, app.state::<AppState>()) {
+        Ok(project) => {
+            tracing::info!(
+                template = %template.name,
+                project = %project.name,
+                "已从模板创建项目"
+            );
+            let _ = app.emit("template://created", project);
+        }
+        Err(err) => tracing::warn!(template = %template.name, "登记模板项目失败: {err}"),
+    }
+}
+
+#[tauri::command]
+pub fn get_templates(state: State<'_, AppState>) -> Vec<ProjectTemplate> {
+    all_templates(&state.file_path)
+}
+
+#[tauri::command]
+pub fn add_template(
+    input: NewTemplateInput,
+    state: State<'_, AppState>,
+) -> Result<ProjectTemplate, String> {
+    if input.name.trim().is_empty() {
+        return Err("模板名称不能为空".to_string());
+    }
+    let command = input
+        .command
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let source_dir = input
+        .source_dir
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    match input.kind {
+        TemplateKind::Command if command.is_none() => {
+            return Err("生成命令不能为空".to_string());
+        }
+        TemplateKind::Folder if !source_dir.as_deref().is_some_and(|d| Path::new(d).is_dir()) => {
+            return Err("模板目录不存在".to_string());
+        }
+        _ => {}
+    }
+
+    let template = ProjectTemplate {
+        id: Uuid::new_v4().to_string(),
+        name: input.name.trim().to_string(),
+        kind: input.kind,
+        command,
+        source_dir,
+        project_type: input.project_type,
+        builtin: false,
+    };
+    let mut store = load_templates(&state.file_path);
+    store.templates.push(template.clone());
+    save_templates(&state.file_path, &store)?;
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn remove_template(template_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let mut store = load_templates(&state.file_path);
+    let before = store.templates.len();
+    store.templates.retain(|t| t.id != template_id);
+    if store.templates.len() == before {
+        return Err("模板不存在或为内置模板".to_string());
+    }
+    save_templates(&state.file_path, &store)
+}
+
+/// 在 parent_dir 下创建名为 name 的项目。生成过程的输出通过 `template://output` /
+/// `template://exit` 事件推送，完成后自动登记为项目。返回运行 key（目标目录）
+#[tauri::command]
+pub fn create_project_from_template(
+    template_id: String,
+    parent_dir: String,
+    name: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let template = all_templates(&state.file_path)
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| "模板不存在".to_string())?;
+    let name = validate_name(&name)?.to_string();
+    let parent = PathBuf::from(parent_dir.trim());
+    if !parent.is_dir() {
+        return Err("父目录不存在或不是目录".to_string());
+    }
+    let target = parent.join(&name);
+    if target.exists() {
+        return Err(format!("目录已存在: {}", target.display()));
+    }
+    let key = target.to_string_lossy().to_string();
+
+    match template.kind {
+        TemplateKind::Command => {
+            let command_line = template
+                .command
+                .clone()
+                .ok_or_else(|| "模板缺少生成命令".to_string())?;
+            let parts: Vec<String> = split_args_template(&command_line)
+                .into_iter()
+                .map(|arg| arg.replace("{name}", &name).replace("{projectPath}", &key))
+                .collect();
+            let (program, args) = parts
+                .split_first()
+                .ok_or_else(|| "模板缺少生成命令".to_string())?;
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            let mut command = process::program_command(program, &args);
+            command.current_dir(&parent);
+            tracing::info!(template = %template.name, target = %key, "运行模板生成命令");
+
+            let handle = app.clone();
+            process::spawn_streaming_with(&app, command, &key, "template", move |success| {
+                if success {
+                    register_created(&handle, &target, &template);
+                }
+            })?;
+        }
+        TemplateKind::Folder => {
+            let source = PathBuf::from(
+                template
+                    .source_dir
+                    .clone()
+                    .ok_or_else(|| "模板缺少源目录".to_string())?,
+            );
+            if !source.is_dir() {
+                return Err(format!("模板目录不存在: {}", source.display()));
+            }
+            // 目标在模板目录内时会把正在生成的项目再复制进去，无限递归
+            let inside_source = source
+                .canonicalize()
+                .ok()
+                .zip(parent.canonicalize().ok())
+                .is_some_and(|(source, parent)| parent.join(&name).starts_with(source));
+            if inside_source {
+                return Err("不能在模板目录内创建项目".to_string());
+            }
+            let key = key.clone();
+            thread::spawn(move || {
+                let mut copied = 0;
+                let result = copy_dir(&source, &target, &mut copied);
+                let line = match &result {
+                    Ok(()) => format!("已复制 {copied} 个文件"),
+                    Err(err) => err.clone(),
+                };
+                let _ = app.emit(
+                    "template://output",
+                    process::ProcessOutput {
+                        key: key.clone(),
+                        stream: if result.is_ok() { "stdout" } else { "stderr" },
+                        line,
+                    },
+                );
+                if result.is_ok() {
+                    register_created(&app, &target, &template);
+                }
+                let _ = app.emit(
+                    "template://exit",
+                    process::ProcessExit {
+                        key,
+                        code: None,
+                        success: result.is_ok(),
+                    },
+                );
+            });
+        }
+    }
+    Ok(key)
+}
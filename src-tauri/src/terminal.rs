@@ -0,0 +1,481 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{envfile, find_executable_in_path, path_key, process, version_manager, AppState};
+
+/// `open_in_terminal` 使用的 shell。选择的 shell 不可用时的回退顺序：
+/// Windows 上依次尝试 pwsh → Windows PowerShell → cmd；
+/// macOS/Linux 上使用终端程序的默认 shell
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub enum TerminalShell {
+    /// Windows 上等同于回退顺序，其它系统使用默认 shell
+    #[default]
+    Auto,
+    Pwsh,
+    WindowsPowerShell,
+    Cmd,
+    GitBash,
+    Bash,
+    Zsh,
+    Fish,
+}
+
+const ALL_SHELLS: [TerminalShell; 7] = [
+    TerminalShell::Pwsh,
+    TerminalShell::WindowsPowerShell,
+    TerminalShell::Cmd,
+    TerminalShell::GitBash,
+    TerminalShell::Bash,
+    TerminalShell::Zsh,
+    TerminalShell::Fish,
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellAvailability {
+    shell: TerminalShell,
+    path: String,
+}
+
+#[cfg(target_os = "windows")]
+fn git_bash_path() -> Option<PathBuf> {
+    let roots = ["ProgramFiles", "ProgramFiles(x86)", "ProgramW6432"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .map(|root| PathBuf::from(root).join("Git"))
+        .chain(
+            std::env::var_os("LOCALAPPDATA")
+                .map(|root| PathBuf::from(root).join("Programs").join("Git")),
+        );
+    for root in roots {
+        let bash = root.join("bin").join("bash.exe");
+        if bash.is_file() {
+            return Some(bash);
+        }
+    }
+    // git.exe 位于 <安装目录>\cmd 下
+    let git = find_executable_in_path("git")?;
+    let bash = git.parent()?.parent()?.join("bin").join("bash.exe");
+    bash.is_file().then_some(bash)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn git_bash_path() -> Option<PathBuf> {
+    None
+}
+
+fn resolve_shell(shell: TerminalShell) -> Option<PathBuf> {
+    let windows = cfg!(target_os = "windows");
+    match shell {
+        TerminalShell::Auto => None,
+        TerminalShell::Pwsh => find_executable_in_path("pwsh"),
+        TerminalShell::WindowsPowerShell if windows => find_executable_in_path("powershell"),
+        TerminalShell::Cmd if windows => find_executable_in_path("cmd"),
+        TerminalShell::GitBash => git_bash_path(),
+        TerminalShell::Bash if !windows => find_executable_in_path("bash"),
+        TerminalShell::Zsh if !windows => find_executable_in_path("zsh"),
+        TerminalShell::Fish if !windows => find_executable_in_path("fish"),
+        _ => None,
+    }
+}
+
+/// 按设置与回退顺序选出实际使用的 shell；返回 None 表示使用终端默认 shell
+fn pick_shell(preferred: TerminalShell) -> Option<(TerminalShell, PathBuf)> {
+    let fallbacks: &[TerminalShell] = if cfg!(target_os = "windows") {
+        &[
+            TerminalShell::Pwsh,
+            TerminalShell::WindowsPowerShell,
+            TerminalShell::Cmd,
+        ]
+    } else {
+        &[]
+    };
+    std::iter::once(preferred)
+        .chain(fallbacks.iter().copied())
+        .find_map(|shell| resolve_shell(shell).map(|path| (shell, path)))
+}
+
+/// 交互式启动参数，工作目录统一通过 current_dir 设置，避免路径引号问题
+fn shell_args(shell: TerminalShell) -> &'static [&'static str] {
+    match shell {
+        TerminalShell::Pwsh | TerminalShell::WindowsPowerShell => &["-NoExit", "-NoLogo"],
+        TerminalShell::GitBash | TerminalShell::Bash => &["--login", "-i"],
+        TerminalShell::Zsh => &["-l"],
+        TerminalShell::Fish => &["-l"],
+        TerminalShell::Cmd | TerminalShell::Auto => &[],
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn launch(
+    path: &str,
+    shell: TerminalShell,
+    _terminal: TerminalApp,
+    launch_env: Vec<(String, String)>,
+) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+
+    let (shell, executable) = pick_shell(shell).ok_or("未找到可用的 shell")?;
+    let mut command = Command::new(executable);
+    command
+        .args(shell_args(shell))
+        .current_dir(path)
+        .envs(launch_env)
+        .creation_flags(0x00000010); // CREATE_NEW_CONSOLE
+    if shell == TerminalShell::GitBash {
+        // 否则 --login 会切换到 HOME 目录
+        command.env("CHERE_INVOKING", "1");
+    }
+    process::spawn_detached(command).map_err(|e| format!("打开终端失败: {e}"))?;
+    Ok(())
+}
+
+/// macOS 上 `open_in_terminal` 使用的终端程序，未安装时回退到 Terminal.app
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub enum TerminalApp {
+    /// 优先使用已安装的第三方终端，否则使用 Terminal.app
+    #[default]
+    Auto,
+    Terminal,
+    ITerm2,
+    WezTerm,
+    Alacritty,
+    Kitty,
+}
+
+const ALL_TERMINAL_APPS: [TerminalApp; 5] = [
+    TerminalApp::Terminal,
+    TerminalApp::ITerm2,
+    TerminalApp::WezTerm,
+    TerminalApp::Alacritty,
+    TerminalApp::Kitty,
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalAppAvailability {
+    app: TerminalApp,
+    path: String,
+}
+
+#[cfg(target_os = "macos")]
+impl TerminalApp {
+    fn bundle_name(self) -> Option<&'static str> {
+        match self {
+            TerminalApp::Auto => None,
+            TerminalApp::Terminal => Some("Terminal.app"),
+            TerminalApp::ITerm2 => Some("iTerm.app"),
+            TerminalApp::WezTerm => Some("WezTerm.app"),
+            TerminalApp::Alacritty => Some("Alacritty.app"),
+            TerminalApp::Kitty => Some("kitty.app"),
+        }
+    }
+
+    /// 应用包内的命令行程序，用于支持 CLI 启动的终端
+    fn cli_name(self) -> Option<&'static str> {
+        match self {
+            TerminalApp::WezTerm => Some("wezterm"),
+            TerminalApp::Alacritty => Some("alacritty"),
+            TerminalApp::Kitty => Some("kitty"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn resolve_terminal_app(app: TerminalApp) -> Option<PathBuf> {
+    let bundle = app.bundle_name()?;
+    let mut roots = vec![
+        PathBuf::from("/Applications"),
+        PathBuf::from("/System/Applications/Utilities"),
+        PathBuf::from("/Applications/Utilities"),
+    ];
+    if let Some(home) = std::env::var_os("HOME") {
+        roots.push(PathBuf::from(home).join("Applications"));
+    }
+    roots
+        .into_iter()
+        .map(|root| root.join(bundle))
+        .find(|path| path.is_dir())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn resolve_terminal_app(_app: TerminalApp) -> Option<PathBuf> {
+    None
+}
+
+/// Auto 时按 iTerm2 → WezTerm → kitty → Alacritty 的顺序选择，最后回退到 Terminal.app
+#[cfg(target_os = "macos")]
+fn pick_terminal_app(preferred: TerminalApp) -> (TerminalApp, Option<PathBuf>) {
+    let candidates: &[TerminalApp] = match preferred {
+        TerminalApp::Auto => &[
+            TerminalApp::ITerm2,
+            TerminalApp::WezTerm,
+            TerminalApp::Kitty,
+            TerminalApp::Alacritty,
+        ],
+        _ => std::slice::from_ref(&preferred),
+    };
+    candidates
+        .iter()
+        .find_map(|&app| resolve_terminal_app(app).map(|path| (app, Some(path))))
+        .unwrap_or((TerminalApp::Terminal, None))
+}
+
+#[cfg(target_os = "macos")]
+fn shell_quote(value: &str) -> String {
+    shlex::try_quote(value)
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn is_env_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 把环境变量写入仅当前用户可读写的临时脚本，脚本先删除自身，设置变量后 exec 目标 shell。
+/// 变量值不会出现在终端中输入的命令、回滚缓冲区、shell 历史与 ps 中
+#[cfg(target_os = "macos")]
+fn write_env_launcher(launch_env: &[(String, String)], exec_line: &str) -> Result<PathBuf, String> {
+    use std::{fs, io::Write, os::unix::fs::OpenOptionsExt};
+
+    let path = std::env::temp_dir().join(format!("dev-boom-env-{}.sh", uuid::Uuid::new_v4()));
+    let mut content = String::from("rm -f \"$0\"\n");
+    for (key, value) in launch_env.iter().filter(|(key, _)| is_env_name(key)) {
+        content.push_str(&format!("export {key}={}\n", shell_quote(value)));
+    }
+    content.push_str(&format!("exec {exec_line}\n"));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| format!("写入环境变量失败: {e}"))?;
+    Ok(path)
+}
+
+/// 在终端内执行的命令行：进入项目目录，需要时带上环境变量并启动指定 shell
+#[cfg(target_os = "macos")]
+fn shell_script(
+    path: &str,
+    shell: Option<(TerminalShell, PathBuf)>,
+    launch_env: &[(String, String)],
+) -> Result<String, String> {
+    let mut script = format!("cd {}", shell_quote(path));
+    if shell.is_none() && launch_env.is_empty() {
+        return Ok(script);
+    }
+    let exec_line = match shell {
+        Some((shell, executable)) => {
+            let mut line = shell_quote(&executable.to_string_lossy());
+            for arg in shell_args(shell) {
+                line.push(' ');
+                line.push_str(arg);
+            }
+            line
+        }
+        None => {
+            let default_shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
+            format!("{} -l", shell_quote(&default_shell))
+        }
+    };
+    if launch_env.is_empty() {
+        script.push_str(" && exec ");
+        script.push_str(&exec_line);
+    } else {
+        let launcher = write_env_launcher(launch_env, &exec_line)?;
+        script.push_str(" && exec /bin/sh ");
+        script.push_str(&shell_quote(&launcher.to_string_lossy()));
+    }
+    Ok(script)
+}
+
+#[cfg(target_os = "macos")]
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(target_os = "macos")]
+fn run_osascript(lines: &[String]) -> Result<(), String> {
+    let mut command = Command::new("osascript");
+    for line in lines {
+        command.arg("-e").arg(line);
+    }
+    process::spawn_detached(command).map_err(|e| format!("打开终端失败: {e}"))
+}
+
+#[cfg(target_os = "macos")]
+fn launch(
+    path: &str,
+    shell: TerminalShell,
+    terminal: TerminalApp,
+    launch_env: Vec<(String, String)>,
+) -> Result<(), String> {
+    let shell = pick_shell(shell);
+    let (terminal, bundle) = pick_terminal_app(terminal);
+    match terminal {
+        TerminalApp::ITerm2 => {
+            let script = shell_script(path, shell, &launch_env)?;
+            run_osascript(&[
+                "tell application \"iTerm\"".to_string(),
+                "activate".to_string(),
+                "set newWindow to (create window with default profile)".to_string(),
+                format!(
+                    "tell current session of newWindow to write text {}",
+                    applescript_string(&script)
+                ),
+                "end tell".to_string(),
+            ])
+        }
+        TerminalApp::WezTerm | TerminalApp::Alacritty | TerminalApp::Kitty => {
+            let bundle = bundle.ok_or("未找到终端程序")?;
+            let cli = terminal.cli_name().ok_or("未找到终端程序")?;
+            let mut command = Command::new(bundle.join("Contents").join("MacOS").join(cli));
+            match terminal {
+                TerminalApp::WezTerm => {
+                    command.args(["start", "--cwd", path]);
+                }
+                TerminalApp::Alacritty => {
+                    command.args(["--working-directory", path]);
+                }
+                _ => {
+                    command.args(["--single-instance", "--directory", path]);
+                }
+            }
+            if let Some((shell, executable)) = shell {
+                match terminal {
+                    TerminalApp::WezTerm => command.arg("--"),
+                    TerminalApp::Alacritty => command.arg("-e"),
+                    _ => &mut command,
+                };
+                command.arg(executable).args(shell_args(shell));
+            }
+            command.current_dir(path).envs(launch_env);
+            process::spawn_detached(command).map_err(|e| format!("打开终端失败: {e}"))
+        }
+        TerminalApp::Terminal | TerminalApp::Auto => {
+            if shell.is_none() && launch_env.is_empty() {
+                let mut command = Command::new("open");
+                command.arg("-a").arg("Terminal").arg(path);
+                return process::spawn_detached(command).map_err(|e| format!("打开终端失败: {e}"));
+            }
+            // 指定 shell 或需要环境变量时通过 AppleScript 在新窗口中执行，
+            // 环境变量经临时脚本传入，不出现在命令行中
+            let script = shell_script(path, shell, &launch_env)?;
+            run_osascript(&[
+                format!(
+                    "tell application \"Terminal\" to do script {}",
+                    applescript_string(&script)
+                ),
+                "tell application \"Terminal\" to activate".to_string(),
+            ])
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn launch(
+    path: &str,
+    shell: TerminalShell,
+    _terminal: TerminalApp,
+    launch_env: Vec<(String, String)>,
+) -> Result<(), String> {
+    let shell = pick_shell(shell).map(|(shell, executable)| {
+        let mut command = vec![executable.to_string_lossy().to_string()];
+        command.extend(shell_args(shell).iter().map(|arg| arg.to_string()));
+        command
+    });
+
+    // 尝试常见的 Linux 终端，使用 --new-window 或直接启动新实例
+    let terminals: [(&str, Vec<&str>, &str); 4] = [
+        ("gnome-terminal", vec!["--working-directory", path], "--"),
+        ("konsole", vec!["--new-window", "--workdir", path], "-e"),
+        ("xfce4-terminal", vec!["--working-directory", path], "-x"),
+        ("xterm", vec![], "-e"),
+    ];
+    for (term, args, exec_flag) in terminals {
+        let mut cmd = Command::new(term);
+        cmd.args(args).current_dir(path).envs(launch_env.clone());
+        if let Some(shell) = &shell {
+            cmd.arg(exec_flag).args(shell);
+        }
+        if process::spawn_detached(cmd).is_ok() {
+            return Ok(());
+        }
+    }
+    Err("未找到可用的终端".to_string())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn launch(
+    _path: &str,
+    _shell: TerminalShell,
+    _terminal: TerminalApp,
+    _env: Vec<(String, String)>,
+) -> Result<(), String> {
+    Err("当前系统不支持打开终端".to_string())
+}
+
+#[tauri::command]
+pub fn open_in_terminal(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let (shell, terminal) = {
+        let settings = state.settings.lock().expect("settings lock poisoned");
+        (settings.terminal_shell, settings.terminal_app)
+    };
+    // 路径属于已登记项目时，终端继承项目 .env 与版本激活的环境变量
+    let launch_env: Vec<(String, String)> = state
+        .store
+        .projects()
+        .iter()
+        .find(|p| path_key(&p.path) == path_key(&path))
+        .map(|p| {
+            let mut vars = envfile::project_env(p);
+            if p.metadata.activate_version_managers {
+                vars.extend(version_manager::activation_env(Path::new(&p.path)));
+            }
+            vars
+        })
+        .unwrap_or_default();
+    launch(&path, shell, terminal, launch_env)
+}
+
+/// 列出当前系统上可用的 shell，供设置界面选择
+#[tauri::command]
+pub fn get_available_shells() -> Vec<ShellAvailability> {
+    ALL_SHELLS
+        .into_iter()
+        .filter_map(|shell| {
+            resolve_shell(shell).map(|path| ShellAvailability {
+                shell,
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 列出已安装的终端程序（仅 macOS），供设置界面选择
+#[tauri::command]
+pub fn get_available_terminal_apps() -> Vec<TerminalAppAvailability> {
+    ALL_TERMINAL_APPS
+        .into_iter()
+        .filter_map(|app| {
+            resolve_terminal_app(app).map(|path| TerminalAppAvailability {
+                app,
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect()
+}
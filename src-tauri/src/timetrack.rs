@@ -0,0 +1,324 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    process::Child,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Datelike, Local, Utc};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::State;
+
+use crate::{AppState, IdeConfig, Project};
+
+const MAX_SESSIONS: usize = 20_000;
+/// 过短的会话不计入
+const MIN_SESSION_SECS: u64 = 60;
+/// 启动器退出后等待 IDE 进程出现的时间
+const HANDOFF_WAIT: Duration = Duration::from_secs(30);
+/// 进程启动时间只精确到秒，判断是否在启动之后出现时留出的余量
+const HANDOFF_START_SLACK_SECS: u64 = 2;
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+const DEFAULT_RANGE_DAYS: u32 = 84;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub project_id: String,
+    pub ide_id: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLog {
+    pub sessions: Vec<Session>,
+}
+
+/// 启动 IDE 后等待进程退出并记录会话时长
+pub struct SessionTracker {
+    log: Arc<Mutex<SessionLog>>,
+    path: PathBuf,
+}
+
+pub fn sessions_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("time-sessions.json")
+}
+
+impl SessionTracker {
+    pub fn load(store_file_path: &Path) -> Self {
+        let path = sessions_path(store_file_path);
+        let log = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            log: Arc::new(Mutex::new(log)),
+            path,
+        }
+    }
+
+    fn sessions(&self) -> Vec<Session> {
+        self.log
+            .lock()
+            .expect("session log lock poisoned")
+            .sessions
+            .clone()
+    }
+
+    pub fn remove_project(&self, project_id: &str) {
+        let mut log = self.log.lock().expect("session log lock poisoned");
+        log.sessions.retain(|s| s.project_id != project_id);
+        let _ = save_log(&self.path, &log);
+    }
+
+    /// project 为实际启动的项目，模块启动时其路径为模块目录
+    pub fn watch(&self, project: &Project, ide: &IdeConfig, mut child: Child) {
+        let log = self.log.clone();
+        let path = self.path.clone();
+        let project_id = project.id.clone();
+        let roots: Vec<String> = std::iter::once(&project.path)
+            .chain(&project.paths)
+            .map(|root| comparable_path(root))
+            .collect();
+        let ide_id = ide.id.clone();
+        let process_name = process_name(&ide.executable);
+        std::thread::spawn(move || {
+            let started_at = Utc::now();
+            let started = Instant::now();
+            let _ = child.wait();
+            // `code`、`wt` 与 JetBrains 的启动脚本会把工作交给 IDE 进程后立即退出，
+            // 此时改为等待启动后新出现、命令行或工作目录指向该项目的 IDE 进程退出。
+            // 交给已运行的实例时无法区分各项目的时长，不记录这次会话
+            if started.elapsed().as_secs() < MIN_SESSION_SECS {
+                let Some(name) = &process_name else {
+                    return;
+                };
+                let launched_at = started_at.timestamp().max(0) as u64;
+                if !wait_for_handoff_exit(name, &roots, launched_at) {
+                    return;
+                }
+            }
+            let duration_secs = started.elapsed().as_secs();
+            if duration_secs < MIN_SESSION_SECS {
+                return;
+            }
+            let mut log = log.lock().expect("session log lock poisoned");
+            log.sessions.push(Session {
+                project_id,
+                ide_id,
+                started_at: started_at.to_rfc3339(),
+                ended_at: Utc::now().to_rfc3339(),
+                duration_secs,
+            });
+            if log.sessions.len() > MAX_SESSIONS {
+                let overflow = log.sessions.len() - MAX_SESSIONS;
+                log.sessions.drain(..overflow);
+            }
+            if let Err(err) = save_log(&path, &log) {
+                tracing::warn!(error = %err, "保存使用时长记录失败");
+            }
+        });
+    }
+}
+
+/// 可执行文件名去掉扩展名与 JetBrains 的 64 后缀，如 `idea64.exe` → `idea`、`code.cmd` → `code`
+fn process_name(executable: &str) -> Option<String> {
+    let stem = Path::new(executable).file_stem()?.to_str()?.to_lowercase();
+    let stem = stem.strip_suffix("64").unwrap_or(&stem).to_string();
+    (!stem.is_empty()).then_some(stem)
+}
+
+/// 统一分隔符与大小写后比较路径，去掉末尾的分隔符
+fn comparable_path(path: &str) -> String {
+    path.replace('\\', "/").trim_end_matches('/').to_lowercase()
+}
+
+fn mentions_root(text: &str, roots: &[String]) -> bool {
+    let text = comparable_path(text);
+    roots
+        .iter()
+        .any(|root| !root.is_empty() && text.contains(root.as_str()))
+}
+
+/// 启动之后出现、命令行参数或工作目录包含项目路径的同名进程
+fn find_handoff_process(
+    system: &mut System,
+    name: &str,
+    roots: &[String],
+    launched_at: u64,
+) -> Option<Pid> {
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_cwd(UpdateKind::OnlyIfNotSet),
+    );
+    system
+        .processes()
+        .iter()
+        .find(|(_, process)| {
+            process_name(&process.name().to_string_lossy()).as_deref() == Some(name)
+                && process.start_time() + HANDOFF_START_SLACK_SECS >= launched_at
+                && (process
+                    .cmd()
+                    .iter()
+                    .any(|arg| mentions_root(&arg.to_string_lossy(), roots))
+                    || process
+                        .cwd()
+                        .is_some_and(|cwd| mentions_root(&cwd.to_string_lossy(), roots)))
+        })
+        .map(|(pid, _)| *pid)
+}
+
+/// 等待接手的 IDE 进程出现后再等它退出；一直没有出现时返回 false
+fn wait_for_handoff_exit(name: &str, roots: &[String], launched_at: u64) -> bool {
+    let mut system = System::new();
+    let deadline = Instant::now() + HANDOFF_WAIT;
+    let pid = loop {
+        if let Some(pid) = find_handoff_process(&mut system, name, roots, launched_at) {
+            break pid;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_secs(2));
+    };
+    loop {
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+        if system.process(pid).is_none() {
+            return true;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn save_log(path: &Path, log: &SessionLog) -> Result<(), String> {
+    let content = serde_json::to_string(log).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectHours {
+    project_id: String,
+    name: String,
+    hours: f64,
+    sessions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekHours {
+    /// 该周周一的本地日期
+    week_start: String,
+    hours: f64,
+    projects: Vec<ProjectHours>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeStats {
+    total_hours: f64,
+    projects: Vec<ProjectHours>,
+    weeks: Vec<WeekHours>,
+}
+
+fn to_hours(secs: u64) -> f64 {
+    (secs as f64 / 3600.0 * 100.0).round() / 100.0
+}
+
+fn project_hours(
+    totals: HashMap<String, (u64, usize)>,
+    names: &HashMap<String, String>,
+) -> Vec<ProjectHours> {
+    let mut projects: Vec<ProjectHours> = totals
+        .into_iter()
+        .map(|(project_id, (secs, sessions))| ProjectHours {
+            name: names.get(&project_id).cloned().unwrap_or_default(),
+            hours: to_hours(secs),
+            sessions,
+            project_id,
+        })
+        .collect();
+    projects.sort_by(|a, b| {
+        b.hours
+            .total_cmp(&a.hours)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    projects
+}
+
+/// 汇总最近 range 天内的使用时长，按周（以会话开始时间所在的周计）与项目分组
+#[tauri::command]
+pub fn get_time_stats(
+    project_id: Option<String>,
+    range: Option<u32>,
+    state: State<'_, AppState>,
+) -> TimeStats {
+    let range = range.filter(|v| *v > 0).unwrap_or(DEFAULT_RANGE_DAYS);
+    let since = Utc::now() - chrono::Duration::days(range as i64);
+    let names: HashMap<String, String> = state
+        .store
+        .projects()
+        .iter()
+        .map(|p| (p.id.clone(), p.name.clone()))
+        .collect();
+
+    let mut totals: HashMap<String, (u64, usize)> = HashMap::new();
+    let mut weeks: BTreeMap<String, HashMap<String, (u64, usize)>> = BTreeMap::new();
+    for session in state.sessions.sessions() {
+        if project_id
+            .as_deref()
+            .is_some_and(|id| id != session.project_id)
+        {
+            continue;
+        }
+        if !names.contains_key(&session.project_id) {
+            continue;
+        }
+        let Ok(started) = DateTime::parse_from_rfc3339(&session.started_at) else {
+            continue;
+        };
+        if started < since {
+            continue;
+        }
+        let day = started.with_timezone(&Local).date_naive();
+        let week_start = day - chrono::Days::new(day.weekday().num_days_from_monday() as u64);
+        for bucket in [
+            &mut totals,
+            weeks.entry(week_start.to_string()).or_default(),
+        ] {
+            let entry = bucket.entry(session.project_id.clone()).or_default();
+            entry.0 += session.duration_secs;
+            entry.1 += 1;
+        }
+    }
+
+    let total_secs = totals.values().map(|(secs, _)| secs).sum();
+    TimeStats {
+        total_hours: to_hours(total_secs),
+        projects: project_hours(totals, &names),
+        weeks: weeks
+            .into_iter()
+            .map(|(week_start, projects)| WeekHours {
+                week_start,
+                hours: to_hours(projects.values().map(|(secs, _)| secs).sum()),
+                projects: project_hours(projects, &names),
+            })
+            .collect(),
+    }
+}
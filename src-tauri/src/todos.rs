@@ -0,0 +1,155 @@
+use std::{fs, path::Path};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, language_stats_settings, settings, should_skip_dir_for_stats, AppState};
+
+const TODO_MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+const MAX_TODO_FILE_SIZE: u64 = 1024 * 1024;
+const MAX_TODO_ITEMS: usize = 5000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoItem {
+    line: usize,
+    kind: &'static str,
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoFile {
+    /// 相对项目根目录的路径，可直接传给 launch_project_at
+    path: String,
+    items: Vec<TodoItem>,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// 在一行中查找 `TODO:` / `FIXME(name):` / `HACK ` 之类的标记
+fn parse_todo_line(line: &str) -> Option<(&'static str, String)> {
+    for marker in TODO_MARKERS {
+        let mut search_from = 0;
+        while let Some(offset) = line[search_from..].find(marker) {
+            let start = search_from + offset;
+            let end = start + marker.len();
+            search_from = end;
+
+            let before_ok = line[..start]
+                .chars()
+                .next_back()
+                .map(|c| !is_word_char(c))
+                .unwrap_or(true);
+            let after = &line[end..];
+            let after_ok = after
+                .chars()
+                .next()
+                .map(|c| matches!(c, ':' | '(' | ' ' | '\t' | '!'))
+                .unwrap_or(true);
+            if !before_ok || !after_ok {
+                continue;
+            }
+
+            let mut text = after;
+            if let Some(rest) = text.strip_prefix('(') {
+                text = rest.split_once(')').map(|(_, v)| v).unwrap_or(rest);
+            }
+            let text = text
+                .trim_start_matches([':', '!', ' ', '\t'])
+                .trim_end_matches("*/")
+                .trim_end_matches("-->")
+                .trim();
+            return Some((marker, text.to_string()));
+        }
+    }
+    None
+}
+
+fn is_source_file(path: &Path, language_settings: &settings::LanguageStatsSettings) -> bool {
+    language_settings.language_for(path).is_some()
+        || tokei::LanguageType::from_path(path, &tokei::Config::default()).is_some()
+}
+
+fn scan_todos_in_file(path: &Path) -> Vec<TodoItem> {
+    let Ok(content) = fs::read(path) else {
+        return vec![];
+    };
+    String::from_utf8_lossy(&content)
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            parse_todo_line(line).map(|(kind, text)| TodoItem {
+                line: idx + 1,
+                kind,
+                text,
+            })
+        })
+        .collect()
+}
+
+fn scan_todos_rec(
+    root: &Path,
+    dir: &Path,
+    language_settings: &settings::LanguageStatsSettings,
+    out: &mut Vec<TodoFile>,
+    total: &mut usize,
+) {
+    if *total >= MAX_TODO_ITEMS || (dir != root && should_skip_dir_for_stats(dir)) {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        if *total >= MAX_TODO_ITEMS {
+            return;
+        }
+        if path.is_dir() {
+            scan_todos_rec(root, &path, language_settings, out, total);
+            continue;
+        }
+        let too_large = fs::metadata(&path)
+            .map(|m| m.len() > MAX_TODO_FILE_SIZE)
+            .unwrap_or(true);
+        if too_large || !is_source_file(&path, language_settings) {
+            continue;
+        }
+        let items = scan_todos_in_file(&path);
+        if items.is_empty() {
+            continue;
+        }
+        *total += items.len();
+        out.push(TodoFile {
+            path: path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string(),
+            items,
+        });
+    }
+}
+
+#[tauri::command(async)]
+pub fn scan_project_todos(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TodoFile>, String> {
+    let language_settings = language_stats_settings(&state);
+    let project = find_project(&state, &project_id)?;
+    let root = Path::new(&project.path);
+    if !root.is_dir() {
+        return Err("项目路径不存在或不是目录".to_string());
+    }
+
+    let mut files = vec![];
+    let mut total = 0;
+    scan_todos_rec(root, root, &language_settings, &mut files, &mut total);
+    Ok(files)
+}
@@ -0,0 +1,186 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{history, language_cache_path, now_iso, path_key, persist, tray, AppState, Project};
+
+/// 回收站中的项目超过该天数后自动清除
+const AUTO_PURGE_DAYS: u32 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedProject {
+    pub project: Project,
+    pub deleted_at: String,
+}
+
+/// 彻底删除时清理语言统计缓存与历史、启动历史与使用时长记录
+pub fn forget_project_data(state: &AppState, project: &Project) {
+    let _ = fs::remove_file(language_cache_path(
+        &state.file_path,
+        Path::new(&project.path),
+    ));
+    {
+        let mut history = state.history.lock().expect("history lock poisoned");
+        history.remove_project(&project.id);
+        let _ = history::save_history(&history::history_path(&state.file_path), &history);
+    }
+    state.sessions.remove_project(&project.id);
+    state.language_history.remove_project(&project.id);
+}
+
+/// 调用方可以持有 projects 写锁（锁顺序为 projects → ides → trash）
+pub fn move_to_trash(state: &AppState, project: Project) {
+    tracing::info!(project = %project.name, "项目已移入回收站");
+    state.store.trash_mut().push(TrashedProject {
+        project,
+        deleted_at: now_iso(),
+    });
+}
+
+/// 从回收站中移除删除时间早于 older_than_days 天前的项目，返回被清除的项目
+fn purge_older_than(trash: &mut Vec<TrashedProject>, older_than_days: u32) -> Vec<Project> {
+    let cutoff = Utc::now() - chrono::Duration::days(older_than_days as i64);
+    let (expired, kept): (Vec<TrashedProject>, Vec<TrashedProject>) =
+        trash.drain(..).partition(|item| {
+            DateTime::parse_from_rfc3339(&item.deleted_at)
+                .map(|deleted| deleted <= cutoff)
+                .unwrap_or(true)
+        });
+    *trash = kept;
+    expired.into_iter().map(|item| item.project).collect()
+}
+
+fn purge(state: &AppState, older_than_days: u32) -> Result<usize, String> {
+    let snapshot = state.store.snapshot();
+    let purged = purge_older_than(&mut state.store.trash_mut(), older_than_days);
+    if purged.is_empty() {
+        return Ok(0);
+    }
+    for project in &purged {
+        forget_project_data(state, project);
+    }
+    state.undo.push("清空回收站", snapshot, &state.store);
+    persist::mark_dirty();
+    tracing::info!(count = purged.len(), "已清除回收站中的项目");
+    Ok(purged.len())
+}
+
+/// 启动时清除回收站中超过 30 天的项目
+pub fn purge_expired(state: &AppState) {
+    if let Err(err) = purge(state, AUTO_PURGE_DAYS) {
+        tracing::warn!("清理回收站失败: {err}");
+    }
+}
+
+#[tauri::command]
+pub fn list_trashed_projects(state: State<'_, AppState>) -> Vec<TrashedProject> {
+    let mut trash = state.store.trash().clone();
+    trash.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    trash
+}
+
+#[tauri::command]
+pub fn restore_project(
+    project_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let mut trash = state.store.trash_mut();
+    let idx = trash
+        .iter()
+        .position(|item| item.project.id == project_id)
+        .ok_or_else(|| "回收站中没有该项目".to_string())?;
+    let key = path_key(&trash[idx].project.path);
+    if projects.iter().any(|p| path_key(&p.path) == key) {
+        return Err("该项目路径已存在".to_string());
+    }
+    let project = trash.remove(idx).project;
+    projects.push(project.clone());
+    persist::mark_dirty();
+    drop(trash);
+    drop(projects);
+    tray::refresh_tray_menu(&app);
+    Ok(project)
+}
+
+/// 彻底删除回收站中删除时间超过 older_than_days 天的项目，传 0 清空回收站，返回清除数量
+#[tauri::command]
+pub fn purge_trash(older_than_days: u32, state: State<'_, AppState>) -> Result<usize, String> {
+    purge(&state, older_than_days)
+}
+
+/// 删除前确认路径是项目目录本身，且不是磁盘根目录、用户主目录或应用数据目录的上级
+fn verify_deletable(state: &AppState, project: &Project) -> Result<PathBuf, String> {
+    let dir = Path::new(&project.path)
+        .canonicalize()
+        .map_err(|e| format!("无法读取项目路径: {e}"))?;
+    if !dir.is_dir() {
+        return Err("项目路径不是目录".to_string());
+    }
+    if dir.parent().is_none() {
+        return Err("不能删除磁盘根目录".to_string());
+    }
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .and_then(|home| PathBuf::from(home).canonicalize().ok());
+    if home.is_some_and(|home| home.starts_with(&dir)) {
+        return Err("不能删除用户主目录或其上级目录".to_string());
+    }
+    if let Some(data_dir) = state.file_path.parent().and_then(|p| p.canonicalize().ok()) {
+        if data_dir.starts_with(&dir) {
+            return Err("项目目录包含应用数据目录，不能删除".to_string());
+        }
+    }
+    Ok(dir)
+}
+
+/// 把项目目录移到系统回收站，然后彻底移除项目记录。
+/// 检查与移动目录都不持有 store 锁，只在移除记录时加写锁
+#[tauri::command(async)]
+pub fn delete_project_files(
+    project_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let projects = state.store.projects().clone();
+    let project = projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    let dir = verify_deletable(&state, project)?;
+    let nested: Vec<&str> = projects
+        .iter()
+        .filter(|p| p.id != project_id)
+        .filter(|p| {
+            Path::new(&p.path)
+                .canonicalize()
+                .is_ok_and(|path| path.starts_with(&dir))
+        })
+        .map(|p| p.name.as_str())
+        .collect();
+    if !nested.is_empty() {
+        return Err(format!("目录中还包含其它项目: {}", nested.join(", ")));
+    }
+
+    ::trash::delete(&dir).map_err(|e| format!("移到回收站失败: {e}"))?;
+    tracing::info!(project = %project.name, path = %dir.display(), "项目目录已移到回收站");
+
+    // 移动期间记录可能已被删除或调整顺序，按 id 重新查找
+    let mut projects = state.store.projects_mut();
+    let Some(idx) = projects.iter().position(|p| p.id == project_id) else {
+        return Ok(());
+    };
+    let removed = projects.remove(idx);
+    persist::mark_dirty();
+    drop(projects);
+    forget_project_data(&state, &removed);
+    tray::refresh_tray_menu(&app);
+    Ok(())
+}
@@ -1,10 +1,24 @@
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{Menu, MenuBuilder, MenuItemBuilder, Submenu, SubmenuBuilder},
     tray::TrayIconBuilder,
-    Manager, Runtime,
+    Emitter, Manager, Runtime,
 };
 
+use crate::settings::{TrayClickAction, TraySettings};
+
+const TRAY_ID: &str = "main";
+const FAVORITE_ITEM_PREFIX: &str = "fav:";
+/// 每个收藏项目的子菜单最多列出的首选 IDE 数量
+const MAX_FAVORITE_IDES: usize = 3;
+
+struct FavoriteEntry {
+    project_id: String,
+    name: String,
+    /// (ide_id, ide_name)，第一个为默认 IDE
+    ides: Vec<(String, String)>,
+}
+
 #[derive(Clone, Copy)]
 enum WindowMode {
     Main,
@@ -36,6 +50,7 @@ fn show_window_mode<R: Runtime>(app: &tauri::AppHandle<R>, mode: WindowMode) {
                 let _ = main_win.hide();
             }
             if let Some(mini_win) = app.get_webview_window("mini") {
+                crate::window_state::restore_mini_window_position(&mini_win);
                 let _ = mini_win.show();
                 let _ = mini_win.set_focus();
             }
@@ -43,28 +58,187 @@ fn show_window_mode<R: Runtime>(app: &tauri::AppHandle<R>, mode: WindowMode) {
     }
 }
 
-pub fn create_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
-    let show_main = MenuItemBuilder::with_id("show_main", "显示主窗口").build(app)?;
-    let show_mini = MenuItemBuilder::with_id("show_mini", "显示迷你窗口").build(app)?;
-    let hide_all = MenuItemBuilder::with_id("hide_all", "隐藏所有窗口").build(app)?;
-    let quit = MenuItemBuilder::with_id("quit", "退出程序").build(app)?;
+fn last_window_mode<R: Runtime>(app: &tauri::AppHandle<R>) -> WindowMode {
+    let state = app.state::<crate::AppState>();
+    let last_window = state
+        .last_active_window
+        .lock()
+        .expect("last_active_window lock poisoned");
+    WindowMode::from_str(last_window.as_deref().unwrap_or("main"))
+}
+
+fn tray_settings<R: Runtime>(app: &tauri::AppHandle<R>) -> TraySettings {
+    app.state::<crate::AppState>()
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .tray
+        .clone()
+}
+
+fn run_click_action<R: Runtime>(app: &tauri::AppHandle<R>, action: TrayClickAction) {
+    match action {
+        TrayClickAction::RestoreLast => show_window_mode(app, last_window_mode(app)),
+        TrayClickAction::ShowMain => show_window_mode(app, WindowMode::Main),
+        TrayClickAction::ShowMini => show_window_mode(app, WindowMode::Mini),
+        TrayClickAction::Toggle => {
+            let next = match last_window_mode(app) {
+                WindowMode::Main => WindowMode::Mini,
+                WindowMode::Mini => WindowMode::Main,
+            };
+            show_window_mode(app, next);
+        }
+        // 菜单由系统在左键单击时弹出，见 apply_tray_settings
+        TrayClickAction::ShowMenu | TrayClickAction::Nothing => {}
+    }
+}
+
+/// 设置变更后同步托盘的左键菜单行为
+pub fn apply_tray_settings<R: Runtime>(app: &tauri::AppHandle<R>, settings: &TraySettings) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ =
+            tray.set_show_menu_on_left_click(settings.single_click == TrayClickAction::ShowMenu);
+    }
+}
+
+fn favorite_entries<R: Runtime, M: Manager<R>>(manager: &M) -> Vec<FavoriteEntry> {
+    let state = manager.state::<crate::AppState>();
+    let projects = state.store.projects();
+    let ides = state.store.ides();
+    let default_ide = ides.iter().min_by_key(|i| i.priority);
+
+    let mut favorites: Vec<&crate::Project> = projects.iter().filter(|p| p.favorite).collect();
+    favorites.sort_by(|a, b| {
+        a.display_order
+            .cmp(&b.display_order)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    favorites
+        .into_iter()
+        .map(|project| {
+            let mut ides: Vec<(String, String)> = project
+                .metadata
+                .ide_preferences
+                .iter()
+                .filter_map(|id| ides.iter().find(|i| i.id == *id))
+                .take(MAX_FAVORITE_IDES)
+                .map(|i| (i.id.clone(), i.name.clone()))
+                .collect();
+            if ides.is_empty() {
+                ides.extend(default_ide.map(|i| (i.id.clone(), i.name.clone())));
+            }
+            FavoriteEntry {
+                project_id: project.id.clone(),
+                name: project.name.clone(),
+                ides,
+            }
+        })
+        .collect()
+}
+
+fn build_favorites_submenu<R: Runtime, M: Manager<R>>(manager: &M) -> tauri::Result<Submenu<R>> {
+    let favorites = favorite_entries(manager);
+    let mut submenu = SubmenuBuilder::new(manager, "收藏项目");
+    if favorites.is_empty() {
+        let empty = MenuItemBuilder::with_id("fav_empty", "暂无收藏项目")
+            .enabled(false)
+            .build(manager)?;
+        return submenu.item(&empty).build();
+    }
+
+    for favorite in favorites {
+        let item_id =
+            |ide_id: &str| format!("{FAVORITE_ITEM_PREFIX}{}:{ide_id}", favorite.project_id);
+        match favorite.ides.as_slice() {
+            [] => {
+                let item = MenuItemBuilder::with_id(
+                    item_id(""),
+                    format!("{}（无可用 IDE）", favorite.name),
+                )
+                .enabled(false)
+                .build(manager)?;
+                submenu = submenu.item(&item);
+            }
+            [(ide_id, ide_name)] => {
+                let item = MenuItemBuilder::with_id(
+                    item_id(ide_id),
+                    format!("{} · {ide_name}", favorite.name),
+                )
+                .build(manager)?;
+                submenu = submenu.item(&item);
+            }
+            ides => {
+                // 多个首选 IDE 时展开为子菜单，第一个为默认
+                let mut ide_menu = SubmenuBuilder::new(manager, &favorite.name);
+                for (idx, (ide_id, ide_name)) in ides.iter().enumerate() {
+                    let text = if idx == 0 {
+                        format!("{ide_name}（默认）")
+                    } else {
+                        ide_name.clone()
+                    };
+                    let item = MenuItemBuilder::with_id(item_id(ide_id), text).build(manager)?;
+                    ide_menu = ide_menu.item(&item);
+                }
+                submenu = submenu.item(&ide_menu.build()?);
+            }
+        }
+    }
+    submenu.build()
+}
 
-    let menu = MenuBuilder::new(app)
+fn build_tray_menu<R: Runtime, M: Manager<R>>(manager: &M) -> tauri::Result<Menu<R>> {
+    let favorites = build_favorites_submenu(manager)?;
+    let show_main = MenuItemBuilder::with_id("show_main", "显示主窗口").build(manager)?;
+    let show_mini = MenuItemBuilder::with_id("show_mini", "显示迷你窗口").build(manager)?;
+    let hide_all = MenuItemBuilder::with_id("hide_all", "隐藏所有窗口").build(manager)?;
+    let quit = MenuItemBuilder::with_id("quit", "退出程序").build(manager)?;
+
+    MenuBuilder::new(manager)
+        .item(&favorites)
+        .separator()
         .item(&show_main)
         .item(&show_mini)
         .item(&hide_all)
         .separator()
         .item(&quit)
-        .build()?;
+        .build()
+}
 
-    let _tray = TrayIconBuilder::new()
+/// 收藏或 IDE 偏好变化后重建托盘菜单；调用方不能持有 store 锁
+pub fn refresh_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if let Ok(menu) = build_tray_menu(app) {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+fn launch_favorite<R: Runtime>(app: &tauri::AppHandle<R>, item_id: &str) {
+    let Some((project_id, ide_id)) = item_id.split_once(':') else {
+        return;
+    };
+    let state = app.state::<crate::AppState>();
+    if let Err(err) = crate::launch_project(project_id.to_string(), Some(ide_id.to_string()), state)
+    {
+        tracing::warn!(project_id, ide_id, error = %err, "从托盘启动收藏项目失败");
+        let _ = app.emit("tray://launch-error", err);
+    }
+}
+
+pub fn create_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app)?;
+    let settings = tray_settings(app.handle());
+
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(Image::from_path("icons/icon.ico").unwrap_or_else(|_| {
             app.default_window_icon()
                 .cloned()
                 .unwrap_or_else(|| Image::new(&[], 0, 0))
         }))
         .menu(&menu)
-        .show_menu_on_left_click(false)
+        .show_menu_on_left_click(settings.single_click == TrayClickAction::ShowMenu)
         .on_menu_event(|app_handle, event| match event.id().as_ref() {
             "show_main" => {
                 show_window_mode(app_handle, WindowMode::Main);
@@ -83,49 +257,26 @@ pub fn create_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::e
             "quit" => {
                 app_handle.exit(0);
             }
-            _ => {}
+            id => {
+                if let Some(item_id) = id.strip_prefix(FAVORITE_ITEM_PREFIX) {
+                    launch_favorite(app_handle, item_id);
+                }
+            }
         })
         .on_tray_icon_event(|tray_icon, event| {
-            if let tauri::tray::TrayIconEvent::Click {
-                button: tauri::tray::MouseButton::Left,
-                ..
-            } = event
-            {
-                let app = tray_icon.app_handle();
-                // 获取最后激活的窗口模式
-                let last_window = app.state::<crate::AppState>();
-                let last_window_guard = last_window.last_active_window.lock().unwrap();
-                let mode = WindowMode::from_str(
-                    last_window_guard
-                        .as_ref()
-                        .as_deref()
-                        .unwrap_or(&"main".to_string())
-                );
-                drop(last_window_guard);
-                show_window_mode(&app, mode);
-            } else if let tauri::tray::TrayIconEvent::DoubleClick {
-                button: tauri::tray::MouseButton::Left,
-                ..
-            } = event
-            {
-                let app = tray_icon.app_handle();
-                // 双击切换到另一个窗口
-                let last_window = app.state::<crate::AppState>();
-                let last_window_guard = last_window.last_active_window.lock().unwrap();
-                let current = WindowMode::from_str(
-                    last_window_guard
-                        .as_ref()
-                        .as_deref()
-                        .unwrap_or(&"main".to_string())
-                );
-                drop(last_window_guard);
-
-                let new_mode = match current {
-                    WindowMode::Main => WindowMode::Mini,
-                    WindowMode::Mini => WindowMode::Main,
-                };
-                show_window_mode(&app, new_mode);
-            }
+            let action = match event {
+                tauri::tray::TrayIconEvent::Click {
+                    button: tauri::tray::MouseButton::Left,
+                    button_state: tauri::tray::MouseButtonState::Up,
+                    ..
+                } => tray_settings(tray_icon.app_handle()).single_click,
+                tauri::tray::TrayIconEvent::DoubleClick {
+                    button: tauri::tray::MouseButton::Left,
+                    ..
+                } => tray_settings(tray_icon.app_handle()).double_click,
+                _ => return,
+            };
+            run_click_action(tray_icon.app_handle(), action);
         })
         .build(app)?;
 
@@ -0,0 +1,154 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::{persist, project_merge_value, tray, AppState, AppStore, StoreState};
+
+/// 只保留最近的若干步
+const MAX_UNDO_STEPS: usize = 20;
+
+/// 某一类条目的逆操作：恢复操作修改或删除的条目，删除操作新增的条目
+struct Inverse<T> {
+    /// 操作前的版本及其原来的位置
+    restore: Vec<(usize, T)>,
+    remove: Vec<String>,
+}
+
+impl<T: Clone> Inverse<T> {
+    /// value 为比较用的内容，不同即视为操作修改了该条目
+    fn between(
+        before: Vec<T>,
+        after: &[T],
+        key: impl Fn(&T) -> String,
+        value: impl Fn(&T) -> serde_json::Value,
+    ) -> Self {
+        let after_map: HashMap<String, serde_json::Value> =
+            after.iter().map(|item| (key(item), value(item))).collect();
+        let before_keys: HashSet<String> = before.iter().map(&key).collect();
+        let restore = before
+            .into_iter()
+            .enumerate()
+            .filter(|(_, item)| after_map.get(&key(item)) != Some(&value(item)))
+            .collect();
+        let remove = after
+            .iter()
+            .map(&key)
+            .filter(|k| !before_keys.contains(k))
+            .collect();
+        Inverse { restore, remove }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.restore.is_empty() && self.remove.is_empty()
+    }
+
+    /// 只改动逆操作涉及的条目，操作之后其它条目上的修改保留
+    fn apply(self, items: &mut Vec<T>, key: impl Fn(&T) -> String) {
+        let remove: HashSet<String> = self.remove.into_iter().collect();
+        items.retain(|item| !remove.contains(&key(item)));
+        for (index, item) in self.restore {
+            let k = key(&item);
+            match items.iter().position(|existing| key(existing) == k) {
+                Some(pos) => items[pos] = item,
+                None => items.insert(index.min(items.len()), item),
+            }
+        }
+    }
+}
+
+struct UndoEntry {
+    label: String,
+    projects: Inverse<crate::Project>,
+    ides: Inverse<crate::IdeConfig>,
+    trash: Inverse<crate::trash::TrashedProject>,
+    tags: Inverse<crate::tags::TagDefinition>,
+}
+
+fn json_value<T: Serialize>(item: &T) -> serde_json::Value {
+    serde_json::to_value(item).unwrap_or_default()
+}
+
+fn project_key(project: &crate::Project) -> String {
+    project.id.clone()
+}
+
+fn ide_key(ide: &crate::IdeConfig) -> String {
+    ide.id.clone()
+}
+
+fn trash_key(item: &crate::trash::TrashedProject) -> String {
+    item.project.id.clone()
+}
+
+fn tag_key(tag: &crate::tags::TagDefinition) -> String {
+    tag.name().to_lowercase()
+}
+
+/// 破坏性操作的逆操作，仅保存在内存中，重启后清空
+#[derive(Default)]
+pub struct UndoStack {
+    entries: Mutex<Vec<UndoEntry>>,
+}
+
+impl UndoStack {
+    /// before 为操作执行前的 store，与当前数据比较得出逆操作；调用方不能持有 store 的写锁
+    pub fn push(&self, label: &str, before: AppStore, store: &StoreState) {
+        let after = store.snapshot();
+        let entry = UndoEntry {
+            label: label.to_string(),
+            // 最后修改时间与 missing 按磁盘状态刷新，不算作操作的修改
+            projects: Inverse::between(
+                before.projects,
+                &after.projects,
+                project_key,
+                project_merge_value,
+            ),
+            ides: Inverse::between(before.ides, &after.ides, ide_key, json_value),
+            trash: Inverse::between(before.trash, &after.trash, trash_key, json_value),
+            tags: Inverse::between(before.tags, &after.tags, tag_key, json_value),
+        };
+        if entry.projects.is_empty()
+            && entry.ides.is_empty()
+            && entry.trash.is_empty()
+            && entry.tags.is_empty()
+        {
+            return;
+        }
+        let mut entries = self.entries.lock().expect("undo lock poisoned");
+        entries.push(entry);
+        if entries.len() > MAX_UNDO_STEPS {
+            entries.remove(0);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().expect("undo lock poisoned").clear();
+    }
+
+    fn pop(&self) -> Option<UndoEntry> {
+        self.entries.lock().expect("undo lock poisoned").pop()
+    }
+}
+
+/// 撤销最近一次破坏性操作，只恢复该操作改动的条目，返回被撤销操作的描述
+#[tauri::command]
+pub fn undo_last_operation(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    let entry = state
+        .undo
+        .pop()
+        .ok_or_else(|| "没有可撤销的操作".to_string())?;
+    state.store.update_all(|store| {
+        entry.projects.apply(&mut store.projects, project_key);
+        entry.ides.apply(&mut store.ides, ide_key);
+        entry.trash.apply(&mut store.trash, trash_key);
+        entry.tags.apply(&mut store.tags, tag_key);
+    });
+    persist::mark_dirty();
+    tray::refresh_tray_menu(&app);
+    tracing::info!(operation = %entry.label, "已撤销操作");
+    Ok(entry.label)
+}
@@ -0,0 +1,335 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::PathBuf,
+    process::Command,
+    time::Duration,
+};
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{network, secrets, settings::UpdateChannel, AppState};
+
+const RELEASES_API: &str = "https://api.github.com/repos/FanLu1994/dev-boom/releases?per_page=20";
+const USER_AGENT: &str = concat!("dev-boom/", env!("CARGO_PKG_VERSION"), " updater");
+/// 发布构建时注入的 minisign 公钥（`tauri signer generate` 生成的 base64 形式），
+/// 没有公钥的构建不会自动安装更新
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("DEV_BOOM_UPDATER_PUBKEY");
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    version: String,
+    current_version: String,
+    name: Option<String>,
+    notes: Option<String>,
+    published_at: Option<String>,
+    prerelease: bool,
+    release_url: String,
+    /// 当前平台可用的安装包，没有匹配的安装包时只能打开发布页手动下载
+    asset_name: Option<String>,
+    download_url: Option<String>,
+    size: Option<u64>,
+    /// 安装包旁的 `.sig` 签名文件
+    #[serde(skip)]
+    signature_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+fn current_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("invalid package version")
+}
+
+fn parse_tag(tag: &str) -> Option<Version> {
+    Version::parse(tag.trim().trim_start_matches(['v', 'V'])).ok()
+}
+
+fn arch_tokens() -> &'static [&'static str] {
+    match std::env::consts::ARCH {
+        "x86_64" => &["x64", "amd64", "x86_64"],
+        "aarch64" => &["aarch64", "arm64"],
+        "x86" => &["x86", "i386", "i686"],
+        _ => &[],
+    }
+}
+
+/// 按 tauri bundler 的产物命名挑选当前平台的安装包，靠前的后缀优先
+fn pick_asset(assets: &[GithubAsset]) -> Option<&GithubAsset> {
+    let suffixes: &[&str] = if cfg!(target_os = "windows") {
+        &["-setup.exe", ".msi"]
+    } else if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else {
+        &[".appimage", ".deb", ".rpm"]
+    };
+    let arch = arch_tokens();
+    suffixes.iter().find_map(|suffix| {
+        assets.iter().find(|asset| {
+            let name = asset.name.to_ascii_lowercase();
+            name.ends_with(suffix) && arch.iter().any(|token| name.contains(token))
+        })
+    })
+}
+
+fn http_client(timeout: Duration) -> Result<reqwest::blocking::Client, String> {
+    network::client(timeout, USER_AGENT)
+}
+
+/// 稳定通道只看正式版，测试通道同时包含预发布版本，取版本号最高且高于当前版本的一个
+fn fetch_latest(channel: UpdateChannel) -> Result<Option<UpdateInfo>, String> {
+    let mut request = http_client(Duration::from_secs(15))?
+        .get(RELEASES_API)
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json");
+    // 匿名访问每小时只有 60 次额度，保存了 GitHub 令牌时带上
+    if let Some(token) = secrets::get(secrets::GITHUB_TOKEN) {
+        request = request.bearer_auth(token);
+    }
+    let body = request
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("检查更新失败: {e}"))?
+        .bytes()
+        .map_err(|e| format!("检查更新失败: {e}"))?;
+    let releases: Vec<GithubRelease> =
+        serde_json::from_slice(&body).map_err(|e| format!("解析发布信息失败: {e}"))?;
+
+    let current = current_version();
+    let latest = releases
+        .into_iter()
+        .filter(|r| !r.draft && (channel == UpdateChannel::Beta || !r.prerelease))
+        .filter_map(|r| parse_tag(&r.tag_name).map(|v| (v, r)))
+        .filter(|(v, _)| *v > current)
+        .max_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(latest.map(|(version, release)| {
+        let asset = pick_asset(&release.assets);
+        UpdateInfo {
+            version: version.to_string(),
+            current_version: current.to_string(),
+            name: release.name.clone(),
+            notes: release.body.clone(),
+            published_at: release.published_at.clone(),
+            prerelease: release.prerelease,
+            release_url: release.html_url.clone(),
+            asset_name: asset.map(|a| a.name.clone()),
+            download_url: asset.map(|a| a.browser_download_url.clone()),
+            size: asset.map(|a| a.size).filter(|size| *size > 0),
+            signature_url: asset.and_then(|a| {
+                let sig_name = format!("{}.sig", a.name);
+                release
+                    .assets
+                    .iter()
+                    .find(|s| s.name == sig_name)
+                    .map(|s| s.browser_download_url.clone())
+            }),
+        }
+    }))
+}
+
+fn update_channel(app: &AppHandle) -> UpdateChannel {
+    app.state::<AppState>()
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .update_channel
+}
+
+fn check_and_notify(app: &AppHandle) -> Result<Option<UpdateInfo>, String> {
+    let update = fetch_latest(update_channel(app))?;
+    if let Some(update) = &update {
+        tracing::info!(version = %update.version, "发现新版本");
+        let _ = app.emit("update://available", update.clone());
+    }
+    Ok(update)
+}
+
+/// 启动时在后台检查一次，发现新版本时发送 `update://available` 事件
+pub fn check_on_startup(app: &AppHandle) {
+    let enabled = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .expect("settings lock poisoned")
+        .auto_check_updates;
+    if !enabled || network::is_offline() {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(err) = check_and_notify(&app) {
+            tracing::warn!("{err}");
+        }
+    });
+}
+
+fn download(app: &AppHandle, url: &str, file_name: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join("dev-boom-update");
+    fs::create_dir_all(&dir).map_err(|e| format!("创建目录失败: {e}"))?;
+    let path = dir.join(file_name);
+
+    let mut response = http_client(Duration::from_secs(600))?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("下载更新失败: {e}"))?;
+    let total = response.content_length();
+    let mut file = fs::File::create(&path).map_err(|e| format!("写入安装包失败: {e}"))?;
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut downloaded = 0u64;
+    loop {
+        let read = response
+            .read(&mut buf)
+            .map_err(|e| format!("下载更新失败: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buf[..read])
+            .map_err(|e| format!("写入安装包失败: {e}"))?;
+        downloaded += read as u64;
+        let _ = app.emit("update://progress", DownloadProgress { downloaded, total });
+    }
+    Ok(path)
+}
+
+fn decode_base64(value: &str) -> Result<String, String> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .map_err(|e| format!("签名格式无效: {e}"))?;
+    String::from_utf8(bytes).map_err(|e| format!("签名格式无效: {e}"))
+}
+
+fn fetch_signature(url: &str) -> Result<String, String> {
+    http_client(Duration::from_secs(30))?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.text())
+        .map_err(|e| format!("下载签名失败: {e}"))
+}
+
+/// 用内置公钥校验安装包的 minisign 签名，签名与公钥都是 tauri 签名工具输出的 base64 形式
+fn verify_signature(path: &std::path::Path, signature: &str) -> Result<(), String> {
+    let public_key =
+        UPDATE_PUBLIC_KEY.ok_or("当前版本没有内置更新签名公钥，请前往发布页手动下载")?;
+    let public_key = minisign_verify::PublicKey::decode(&decode_base64(public_key)?)
+        .map_err(|e| format!("更新签名公钥无效: {e}"))?;
+    let signature = minisign_verify::Signature::decode(&decode_base64(signature)?)
+        .map_err(|e| format!("签名格式无效: {e}"))?;
+    let data = fs::read(path).map_err(|e| format!("读取安装包失败: {e}"))?;
+    public_key
+        .verify(&data, &signature, true)
+        .map_err(|_| "安装包签名校验失败，已取消安装".to_string())
+}
+
+/// Windows 上直接运行安装程序并退出，以便覆盖正在使用的文件；其它平台交给系统打开安装包
+fn run_installer(app: &AppHandle, path: PathBuf) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        let is_msi = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("msi"))
+            .unwrap_or(false);
+        let result = if is_msi {
+            Command::new("msiexec").arg("/i").arg(&path).spawn()
+        } else {
+            Command::new(&path).spawn()
+        };
+        result.map_err(|e| format!("启动安装程序失败: {e}"))?;
+        app.exit(0);
+        Ok(())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = app;
+        let is_appimage = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("appimage"))
+            .unwrap_or(false);
+        if is_appimage {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o755));
+            }
+            crate::process::spawn_detached(Command::new(&path))
+                .map_err(|e| format!("启动新版本失败: {e}"))?;
+            return Ok(());
+        }
+        crate::open_in_file_manager(path.to_string_lossy().to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || check_and_notify(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 重新获取最新版本后下载安装包，通过 `update://progress` 报告下载进度；
+/// 安装包必须带有能用内置公钥验证的签名
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let update = fetch_latest(update_channel(&app))?.ok_or("当前已是最新版本")?;
+        let (Some(url), Some(file_name)) = (update.download_url, update.asset_name) else {
+            return Err(format!(
+                "没有适用于当前平台的安装包，请前往发布页下载: {}",
+                update.release_url
+            ));
+        };
+        let Some(signature_url) = update.signature_url else {
+            return Err(format!(
+                "安装包没有签名，请前往发布页下载: {}",
+                update.release_url
+            ));
+        };
+        let signature = fetch_signature(&signature_url)?;
+        let path = download(&app, &url, &file_name)?;
+        if let Err(err) = verify_signature(&path, &signature) {
+            let _ = fs::remove_file(&path);
+            tracing::warn!(version = %update.version, "{err}");
+            return Err(err);
+        }
+        tracing::info!(version = %update.version, "开始安装新版本");
+        run_installer(&app, path)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
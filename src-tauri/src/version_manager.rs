@@ -0,0 +1,240 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::{find_project, persist, AppState, Project};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum VersionManager {
+    Nvm,
+    Fnm,
+    Pyenv,
+    Asdf,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedVersion {
+    tool: String,
+    version: String,
+    /// 声明版本的文件，如 `.nvmrc`
+    source: &'static str,
+    manager: VersionManager,
+    /// 已安装的匹配版本所在目录，未安装时为空
+    install_dir: Option<String>,
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn read_first_line(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// 在已安装版本中选出与声明版本匹配的最高版本，`18` 匹配 `v18.17.0`
+fn best_installed(versions_dir: &Path, pinned: &str) -> Option<PathBuf> {
+    let pinned = pinned.trim_start_matches('v');
+    fs::read_dir(versions_dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let bare = name.trim_start_matches('v');
+            let matches = bare == pinned || bare.starts_with(&format!("{pinned}."));
+            let version = semver::Version::parse(bare).ok();
+            matches.then(|| (version, entry.path()))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, path)| path)
+}
+
+fn nvm_install_dir(version: &str) -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        let root = env::var_os("NVM_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("APPDATA").map(|v| PathBuf::from(v).join("nvm")))?;
+        best_installed(&root, version)
+    } else {
+        let root = env::var_os("NVM_DIR")
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|h| h.join(".nvm")))?;
+        best_installed(&root.join("versions").join("node"), version).map(|p| p.join("bin"))
+    }
+}
+
+fn fnm_install_dir(version: &str) -> Option<PathBuf> {
+    let root = env::var_os("FNM_DIR").map(PathBuf::from).or_else(|| {
+        if cfg!(target_os = "windows") {
+            env::var_os("APPDATA").map(|v| PathBuf::from(v).join("fnm"))
+        } else if cfg!(target_os = "macos") {
+            home_dir().map(|h| h.join("Library/Application Support/fnm"))
+        } else {
+            home_dir().map(|h| h.join(".local/share/fnm"))
+        }
+    })?;
+    let installation = best_installed(&root.join("node-versions"), version)?.join("installation");
+    Some(if cfg!(target_os = "windows") {
+        installation
+    } else {
+        installation.join("bin")
+    })
+}
+
+fn pyenv_install_dir(version: &str) -> Option<PathBuf> {
+    let root = env::var_os("PYENV_ROOT").map(PathBuf::from).or_else(|| {
+        let home = home_dir()?;
+        Some(if cfg!(target_os = "windows") {
+            home.join(".pyenv").join("pyenv-win")
+        } else {
+            home.join(".pyenv")
+        })
+    })?;
+    let dir = best_installed(&root.join("versions"), version)?;
+    Some(if cfg!(target_os = "windows") {
+        dir
+    } else {
+        dir.join("bin")
+    })
+}
+
+/// node 优先使用 nvm，找不到已安装版本时再尝试 fnm
+fn node_pin(root: &Path) -> Option<PinnedVersion> {
+    let (source, version) = [".nvmrc", ".node-version"]
+        .into_iter()
+        .find_map(|name| read_first_line(&root.join(name)).map(|v| (name, v)))?;
+    let (manager, install_dir) = match nvm_install_dir(&version) {
+        Some(dir) => (VersionManager::Nvm, Some(dir)),
+        None => match fnm_install_dir(&version) {
+            Some(dir) => (VersionManager::Fnm, Some(dir)),
+            None => (VersionManager::Nvm, None),
+        },
+    };
+    Some(PinnedVersion {
+        tool: "node".to_string(),
+        version,
+        source,
+        manager,
+        install_dir: install_dir.map(|p| p.to_string_lossy().to_string()),
+    })
+}
+
+fn python_pin(root: &Path) -> Option<PinnedVersion> {
+    let version = read_first_line(&root.join(".python-version"))?;
+    Some(PinnedVersion {
+        tool: "python".to_string(),
+        install_dir: pyenv_install_dir(&version).map(|p| p.to_string_lossy().to_string()),
+        version,
+        source: ".python-version",
+        manager: VersionManager::Pyenv,
+    })
+}
+
+fn asdf_pins(root: &Path) -> Vec<PinnedVersion> {
+    let Ok(content) = fs::read_to_string(root.join(".tool-versions")) else {
+        return vec![];
+    };
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let tool = parts.next()?;
+            let version = parts.next()?;
+            Some(PinnedVersion {
+                tool: tool.to_string(),
+                version: version.to_string(),
+                source: ".tool-versions",
+                manager: VersionManager::Asdf,
+                install_dir: None,
+            })
+        })
+        .collect()
+}
+
+pub fn detect_pins(root: &Path) -> Vec<PinnedVersion> {
+    let mut pins: Vec<PinnedVersion> = node_pin(root).into_iter().collect();
+    pins.extend(python_pin(root));
+    pins.extend(asdf_pins(root));
+    pins
+}
+
+/// 计算与 `nvm use` / `pyenv shell` / `asdf shell` 等效的环境变量：
+/// 已安装版本的目录加到 PATH 最前面，pyenv 与 asdf 另外设置各自的版本变量供 shim 读取
+pub fn activation_env(root: &Path) -> Vec<(String, String)> {
+    let pins = detect_pins(root);
+    let mut vars = vec![];
+    let mut path_dirs: Vec<PathBuf> = vec![];
+    for pin in &pins {
+        match pin.manager {
+            VersionManager::Pyenv => vars.push(("PYENV_VERSION".to_string(), pin.version.clone())),
+            VersionManager::Asdf => vars.push((
+                format!(
+                    "ASDF_{}_VERSION",
+                    pin.tool.to_ascii_uppercase().replace('-', "_")
+                ),
+                pin.version.clone(),
+            )),
+            VersionManager::Nvm | VersionManager::Fnm => {}
+        }
+        if let Some(dir) = &pin.install_dir {
+            path_dirs.push(PathBuf::from(dir));
+        }
+    }
+    if !path_dirs.is_empty() {
+        let current = env::var_os("PATH").unwrap_or_default();
+        path_dirs.extend(env::split_paths(&current));
+        if let Ok(joined) = env::join_paths(path_dirs) {
+            vars.push(("PATH".to_string(), joined.to_string_lossy().to_string()));
+        }
+    }
+    vars
+}
+
+/// 项目开启了版本管理器激活时，为即将启动的命令设置对应环境变量
+pub fn apply_activation(command: &mut Command, project: &Project) {
+    if !project.metadata.activate_version_managers {
+        return;
+    }
+    command.envs(activation_env(Path::new(&project.path)));
+}
+
+#[tauri::command]
+pub fn get_pinned_versions(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<PinnedVersion>, String> {
+    let project = find_project(&state, &project_id)?;
+    Ok(detect_pins(Path::new(&project.path)))
+}
+
+/// 开启后，启动命令行工具与终端时按项目中声明的版本激活 nvm/fnm/pyenv/asdf
+#[tauri::command]
+pub fn set_project_version_activation(
+    project_id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    project.metadata.activate_version_managers = enabled;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
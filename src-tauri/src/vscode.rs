@@ -0,0 +1,189 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::{
+    add_project, detect_project_type, normalize_windows_path_for_ui, AppState, NewProjectInput,
+    Project, ProjectType,
+};
+
+/// 各发行版的用户数据目录名
+const FLAVORS: &[&str] = &["Code", "Code - Insiders", "VSCodium", "Cursor"];
+const RECENT_KEY: &str = "history.recentlyOpenedPathsList";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VsCodeProject {
+    name: String,
+    path: String,
+    project_type: ProjectType,
+    already_added: bool,
+    /// 来自哪个发行版，如 `Code - Insiders`
+    source: String,
+}
+
+fn config_root() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
+
+/// 新版本保存在 state.vscdb（SQLite）中
+fn read_state_db(path: &Path) -> Option<Value> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()?;
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT value FROM ItemTable WHERE key = ?1",
+            [RECENT_KEY],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()?;
+    serde_json::from_str(&raw?).ok()
+}
+
+/// 旧版本保存在 storage.json 的 openedPathsList 中
+fn read_storage_json(path: &Path) -> Option<Value> {
+    let content: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    content.get("openedPathsList").cloned()
+}
+
+fn folder_uris(list: &Value) -> Vec<String> {
+    let mut uris = vec![];
+    for entry in list
+        .get("entries")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if let Some(uri) = entry.get("folderUri").and_then(Value::as_str) {
+            uris.push(uri.to_string());
+        }
+    }
+    // 更早的格式：workspaces3 中直接是文件夹 URI 字符串
+    for entry in list
+        .get("workspaces3")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if let Some(uri) = entry.as_str() {
+            uris.push(uri.to_string());
+        }
+    }
+    uris
+}
+
+/// 只处理本地文件夹，远程（vscode-remote://）与虚拟文件系统忽略
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let url = url::Url::parse(uri).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+    url.to_file_path().ok()
+}
+
+fn recent_folders(flavor_dir: &Path) -> Vec<PathBuf> {
+    let global_storage = flavor_dir.join("User").join("globalStorage");
+    let lists = [
+        read_state_db(&global_storage.join("state.vscdb")),
+        read_storage_json(&global_storage.join("storage.json")),
+        read_storage_json(&flavor_dir.join("storage.json")),
+    ];
+    lists
+        .iter()
+        .flatten()
+        .flat_map(folder_uris)
+        .filter_map(|uri| uri_to_path(&uri))
+        .collect()
+}
+
+/// 读取 VS Code 及其衍生版本最近打开的文件夹，过滤掉已不存在的目录，按最近使用顺序返回
+#[tauri::command(async)]
+pub fn import_from_vscode(state: State<'_, AppState>) -> Result<Vec<VsCodeProject>, String> {
+    let root = config_root().ok_or("无法定位 VS Code 配置目录")?;
+    let existing: HashSet<String> = state
+        .store
+        .projects()
+        .iter()
+        .map(|p| p.path.clone())
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut result = vec![];
+    for flavor in FLAVORS {
+        for folder in recent_folders(&root.join(flavor)) {
+            if !folder.is_dir() {
+                continue;
+            }
+            let Ok(canonical) = folder.canonicalize() else {
+                continue;
+            };
+            let path = normalize_windows_path_for_ui(&canonical.to_string_lossy());
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            result.push(VsCodeProject {
+                name: canonical
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.clone()),
+                project_type: detect_project_type(&canonical),
+                already_added: existing.contains(&path),
+                path,
+                source: flavor.to_string(),
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// 批量添加选中的目录，已存在或无法读取的目录跳过，返回成功添加的项目
+#[tauri::command(async)]
+pub fn add_vscode_projects(
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Project>, String> {
+    let snapshot = state.store.snapshot();
+    let mut added = vec![];
+    for path in paths {
+        let input = NewProjectInput {
+            name: String::new(),
+            path: path.clone(),
+            project_type: None,
+            favorite: None,
+            tags: None,
+            description: None,
+            ide_preferences: None,
+        };
+        match add_project(input, state.clone()) {
+            Ok(project) => added.push(project),
+            Err(err) => tracing::warn!(path = %path, "导入 VS Code 项目失败: {err}"),
+        }
+    }
+    tracing::info!(added = added.len(), "从 VS Code 导入项目");
+    if !added.is_empty() {
+        state
+            .undo
+            .push("从 VS Code 导入项目", snapshot, &state.store);
+    }
+    Ok(added)
+}
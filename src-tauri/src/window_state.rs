@@ -0,0 +1,252 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Monitor, PhysicalPosition, PhysicalSize, Runtime, State, WebviewWindow};
+
+use crate::AppState;
+
+/// 移动/缩放结束后多久写盘，避免拖动过程中频繁写文件
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+/// 窗口至少要有这么大一块落在某个显示器工作区内才算可见
+const MIN_VISIBLE_WIDTH: i64 = 120;
+const MIN_VISIBLE_HEIGHT: i64 = 40;
+
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MiniWindowPosition {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MainWindowState {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    maximized: bool,
+}
+
+fn window_state_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("window-state.json")
+}
+
+fn mini_position_path(store_file_path: &Path) -> PathBuf {
+    store_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("mini-window-position.json")
+}
+
+fn load_state(store_file_path: &Path) -> Option<MainWindowState> {
+    let content = fs::read_to_string(window_state_path(store_file_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn work_area(monitor: &Monitor) -> Rect {
+    let area = monitor.work_area();
+    Rect {
+        x: area.position.x,
+        y: area.position.y,
+        width: area.size.width,
+        height: area.size.height,
+    }
+}
+
+fn overlap(a: i64, a_len: i64, b: i64, b_len: i64) -> i64 {
+    ((a + a_len).min(b + b_len) - a.max(b)).max(0)
+}
+
+fn is_visible_on(rect: Rect, area: Rect) -> bool {
+    let width = overlap(
+        rect.x as i64,
+        rect.width as i64,
+        area.x as i64,
+        area.width as i64,
+    );
+    let height = overlap(
+        rect.y as i64,
+        rect.height as i64,
+        area.y as i64,
+        area.height as i64,
+    );
+    width >= MIN_VISIBLE_WIDTH.min(rect.width as i64)
+        && height >= MIN_VISIBLE_HEIGHT.min(rect.height as i64)
+}
+
+fn center_distance(rect: Rect, area: Rect) -> i64 {
+    let cx = rect.x as i64 + rect.width as i64 / 2;
+    let cy = rect.y as i64 + rect.height as i64 / 2;
+    let ax = area.x as i64 + area.width as i64 / 2;
+    let ay = area.y as i64 + area.height as i64 / 2;
+    (cx - ax).pow(2) + (cy - ay).pow(2)
+}
+
+/// 窗口足够可见时原样返回；否则（如保存时所在的显示器已拔掉）移到距离最近的工作区内，
+/// 尺寸不超过该工作区。没有任何显示器信息时返回 None
+pub fn place_on_monitors(rect: Rect, monitors: &[Monitor]) -> Option<Rect> {
+    let areas: Vec<Rect> = monitors.iter().map(work_area).collect();
+    if areas.iter().any(|area| is_visible_on(rect, *area)) {
+        return Some(rect);
+    }
+    let area = areas
+        .into_iter()
+        .min_by_key(|area| center_distance(rect, *area))?;
+    let width = rect.width.min(area.width);
+    let height = rect.height.min(area.height);
+    let max_x = area.x as i64 + (area.width - width) as i64;
+    let max_y = area.y as i64 + (area.height - height) as i64;
+    Some(Rect {
+        x: (rect.x as i64).clamp(area.x as i64, max_x) as i32,
+        y: (rect.y as i64).clamp(area.y as i64, max_y) as i32,
+        width,
+        height,
+    })
+}
+
+fn save_now<R: Runtime>(window: &WebviewWindow<R>) {
+    if window.is_minimized().unwrap_or(false) {
+        return;
+    }
+    let state = window.app_handle().state::<AppState>();
+    let maximized = window.is_maximized().unwrap_or(false);
+    // 最大化时只记录标志，保留还原后的位置与大小
+    let saved = if maximized {
+        load_state(&state.file_path).map(|s| MainWindowState {
+            maximized: true,
+            ..s
+        })
+    } else {
+        match (window.outer_position(), window.inner_size()) {
+            (Ok(position), Ok(size)) => Some(MainWindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized: false,
+            }),
+            _ => None,
+        }
+    };
+    if let Some(saved) = saved {
+        if let Ok(content) = serde_json::to_string(&saved) {
+            let _ = fs::write(window_state_path(&state.file_path), content);
+        }
+    }
+}
+
+/// 在主窗口 Moved/Resized 事件中调用，停止变化一段时间后才写盘
+pub fn schedule_save<R: Runtime>(window: &WebviewWindow<R>) {
+    let generation = SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let window = window.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(SAVE_DEBOUNCE);
+        if SAVE_GENERATION.load(Ordering::SeqCst) == generation {
+            save_now(&window);
+        }
+    });
+}
+
+pub fn restore_main_window<R: Runtime>(window: &WebviewWindow<R>, store_file_path: &Path) {
+    let Some(saved) = load_state(store_file_path) else {
+        return;
+    };
+    let monitors = window.available_monitors().unwrap_or_default();
+    let rect = Rect {
+        x: saved.x,
+        y: saved.y,
+        width: saved.width,
+        height: saved.height,
+    };
+    let Some(rect) = place_on_monitors(rect, &monitors) else {
+        return;
+    };
+    let _ = window.set_size(PhysicalSize::new(rect.width, rect.height));
+    let _ = window.set_position(PhysicalPosition::new(rect.x, rect.y));
+    if saved.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// 按迷你窗口当前大小校验保存的位置，落在已断开的显示器上时移回可见区域
+fn validated_mini_position<R: Runtime>(
+    window: &WebviewWindow<R>,
+    store_file_path: &Path,
+) -> Option<MiniWindowPosition> {
+    let content = fs::read_to_string(mini_position_path(store_file_path)).ok()?;
+    let saved: MiniWindowPosition = serde_json::from_str(&content).ok()?;
+    let size = window.outer_size().ok()?;
+    let monitors = window.available_monitors().unwrap_or_default();
+    let rect = place_on_monitors(
+        Rect {
+            x: saved.x,
+            y: saved.y,
+            width: size.width,
+            height: size.height,
+        },
+        &monitors,
+    )?;
+    Some(MiniWindowPosition {
+        x: rect.x,
+        y: rect.y,
+    })
+}
+
+/// 显示迷你窗口前调用
+pub fn restore_mini_window_position<R: Runtime>(window: &WebviewWindow<R>) {
+    let state = window.app_handle().state::<AppState>();
+    if let Some(position) = validated_mini_position(window, &state.file_path) {
+        let _ = window.set_position(PhysicalPosition::new(position.x, position.y));
+    }
+}
+
+#[tauri::command]
+pub fn save_mini_window_position(x: i32, y: i32, state: State<'_, AppState>) -> Result<(), String> {
+    let pos = MiniWindowPosition { x, y };
+    let content = serde_json::to_string(&pos).map_err(|e| e.to_string())?;
+    fs::write(mini_position_path(&state.file_path), content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn load_mini_window_position(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Option<MiniWindowPosition> {
+    let window = app.get_webview_window("mini")?;
+    validated_mini_position(&window, &state.file_path)
+}
+
+/// 清除保存的位置并把迷你窗口居中
+#[tauri::command]
+pub fn reset_mini_window_position(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let path = mini_position_path(&state.file_path);
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    if let Some(window) = app.get_webview_window("mini") {
+        window.center().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use tauri::State;
+
+use crate::{find_project, goto_style_for_ide, persist, AppState, GotoStyle, IdeConfig, Project};
+
+const WORKSPACE_EXTENSION: &str = "code-workspace";
+
+pub fn is_workspace_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(WORKSPACE_EXTENSION))
+}
+
+/// 项目根目录下的所有 `*.code-workspace` 文件名，按名称排序
+pub fn list_workspace_files(root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return vec![];
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_workspace_file(path))
+        .filter_map(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// 优先选择与目录同名的工作区文件，否则取第一个
+pub fn detect_workspace_file(root: &Path) -> Option<String> {
+    let names = list_workspace_files(root);
+    let dir_name = root.file_name()?.to_string_lossy().to_lowercase();
+    names
+        .iter()
+        .find(|name| {
+            Path::new(name.as_str())
+                .file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().to_lowercase() == dir_name)
+        })
+        .or_else(|| names.first())
+        .cloned()
+}
+
+/// 已记录的工作区文件仍存在时保留，否则重新检测
+pub fn refresh_workspace_file(project: &mut Project) {
+    let root = Path::new(&project.path);
+    let still_exists = project
+        .metadata
+        .workspace_file
+        .as_deref()
+        .is_some_and(|name| root.join(name).is_file());
+    if !still_exists {
+        project.metadata.workspace_file = detect_workspace_file(root);
+    }
+}
+
+/// 开启了工作区打开方式且 IDE 属于 VS Code 系列时，返回要传给 IDE 的工作区文件路径
+pub fn launch_workspace(project: &Project, ide: &IdeConfig) -> Option<String> {
+    if !project.metadata.open_workspace_file || goto_style_for_ide(ide) != GotoStyle::VsCode {
+        return None;
+    }
+    let path: PathBuf = Path::new(&project.path).join(project.metadata.workspace_file.as_deref()?);
+    path.is_file().then(|| path.to_string_lossy().to_string())
+}
+
+/// 将参数中的项目路径替换为工作区文件
+pub fn apply_workspace(project: &Project, ide: &IdeConfig, args: &mut [String]) {
+    let Some(workspace) = launch_workspace(project, ide) else {
+        return;
+    };
+    for arg in args.iter_mut().filter(|arg| **arg == project.path) {
+        *arg = workspace.clone();
+    }
+}
+
+#[tauri::command]
+pub fn get_workspace_files(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let project = find_project(&state, &project_id)?;
+    Ok(list_workspace_files(Path::new(&project.path)))
+}
+
+/// 设置是否用工作区文件打开；传入 workspace_file 时同时切换使用的文件
+#[tauri::command]
+pub fn set_project_workspace(
+    project_id: String,
+    enabled: bool,
+    workspace_file: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Project, String> {
+    let mut projects = state.store.projects_mut();
+    let project = projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "项目不存在".to_string())?;
+    if let Some(name) = workspace_file
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        let path = Path::new(&project.path).join(&name);
+        if !path.is_file() || !is_workspace_file(&path) {
+            return Err(format!("工作区文件不存在: {}", path.display()));
+        }
+        project.metadata.workspace_file = Some(name);
+    }
+    if enabled && project.metadata.workspace_file.is_none() {
+        return Err("项目目录中没有 .code-workspace 文件".to_string());
+    }
+    project.metadata.open_workspace_file = enabled;
+    let updated = project.clone();
+    persist::mark_dirty();
+    Ok(updated)
+}
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { IdeConfig, IdeForm, LanguageStats, Project } from "../types/project";
+import type { IdeConfig, IdeForm, LanguageStats, Project, ScanReport } from "../types/project";
 
 export async function getProjects() {
   return invoke<Project[]>("get_projects");
@@ -13,8 +13,26 @@ export async function addIde(input: IdeForm) {
   return invoke("add_ide", { input });
 }
 
-export async function scanProjects(rootPath: string, maxDepth = 3) {
-  return invoke("scan_projects", { rootPath, maxDepth });
+export async function scanProjects(
+  rootPath: string,
+  maxDepth = 3,
+  computeLanguageStats = false,
+  autoDescription = false,
+) {
+  return invoke<ScanReport>("scan_projects", {
+    rootPath,
+    maxDepth,
+    computeLanguageStats,
+    autoDescription,
+  });
+}
+
+export async function updateProjectDescription(projectId: string, description: string | null) {
+  return invoke<Project>("update_project_description", { projectId, description });
+}
+
+export async function fillAutoDescriptions(projectIds: string[] = []) {
+  return invoke<Project[]>("fill_auto_descriptions", { projectIds });
 }
 
 export async function removeProject(projectId: string) {
@@ -57,8 +75,15 @@ export async function removeIde(ideId: string) {
   return invoke("remove_ide", { ideId });
 }
 
-export async function scanProjectLanguageStats(projectId: string) {
-  return invoke<LanguageStats>("scan_project_language_stats", { projectId });
+export async function scanProjectLanguageStats(projectId: string, directoryDepth?: number) {
+  return invoke<LanguageStats>("scan_project_language_stats", { projectId, directoryDepth });
+}
+
+export async function refreshLanguageStats(projectIds: string[] = []) {
+  return invoke<{ updated: string[]; failed: { projectId: string; error: string }[] }>(
+    "refresh_language_stats",
+    { projectIds },
+  );
 }
 
 export async function getProjectLanguageStats(projectId: string) {
@@ -220,18 +220,12 @@ export function useProjectManager() {
     }
 
     try {
-      const added = await scanProjectsApi(projectForm.value.path, projectForm.value.maxDepth);
-      const projects = Array.isArray(added) ? added : [];
+      const report = await scanProjectsApi(projectForm.value.path, projectForm.value.maxDepth);
       projectForm.value = { ...EMPTY_PROJECT_FORM };
       showProjectDialog.value = false;
 
-      // 统计新项目和更新的项目
-      const now = Date.now();
-      const newCount = projects.filter((p: Project) => {
-        const createdAt = new Date(p.createdAt).getTime();
-        return Math.abs(createdAt - now) < 5000; // 5秒内创建的算是新项目
-      }).length;
-      const updatedCount = projects.length - newCount;
+      const newCount = report.added.length;
+      const updatedCount = report.updated.length;
 
       let message = "";
       if (newCount > 0 && updatedCount > 0) {
@@ -1,4 +1,20 @@
-export type ProjectType = "Rust" | "Nodejs" | "Python" | "Java" | "Go" | "Dotnet" | "Generic";
+export type ProjectType =
+  | "Rust"
+  | "Nodejs"
+  | "Python"
+  | "Java"
+  | "Go"
+  | "Dotnet"
+  | "Flutter"
+  | "Dart"
+  | "Php"
+  | "Ruby"
+  | "Elixir"
+  | "Cpp"
+  | "Zig"
+  | "Unity"
+  | "Terraform"
+  | "Generic";
 export type IdeCategory = "Gui" | "Cli" | "Terminal" | "Browser";
 export type ThemeMode = "light" | "dark";
 
@@ -9,16 +25,27 @@ export interface LanguageEntry {
   percentage: number;
 }
 
+export interface DirectoryStats {
+  path: string;
+  lines: number;
+  files: number;
+  percentage: number;
+  languages: LanguageEntry[];
+}
+
 export interface LanguageStats {
   totalLines: number;
   languages: LanguageEntry[];
   scannedAt: string;
+  directories?: DirectoryStats[] | null;
 }
 
 export interface ProjectMetadata {
   idePreferences: string[];
   gitUrl: string | null;
   description: string | null;
+  /** 自动填写的描述的来源文件，手动填写时为 null */
+  descriptionSource?: string | null;
   languageStats: LanguageStats | null;
 }
 
@@ -29,12 +56,27 @@ export interface Project {
   projectType: ProjectType;
   favorite: boolean;
   tags: string[];
+  aliases?: string[];
   lastOpened: string | null;
   lastModified: string | null;
   createdAt: string;
   metadata: ProjectMetadata;
 }
 
+export interface TagDefinition {
+  name: string;
+  color: string;
+  icon: string | null;
+  description: string | null;
+}
+
+export interface ScanReport {
+  added: Project[];
+  updated: Project[];
+  unchanged: Project[];
+  missing: Project[];
+}
+
 export interface IdeConfig {
   id: string;
   name: string;
@@ -66,5 +108,14 @@ export const TYPE_COLORS: Record<ProjectType, string> = {
   Java: "#dc2626",
   Go: "#0891b2",
   Dotnet: "#0f766e",
+  Flutter: "#0284c7",
+  Dart: "#0369a1",
+  Php: "#6366f1",
+  Ruby: "#be123c",
+  Elixir: "#7c3aed",
+  Cpp: "#1d4ed8",
+  Zig: "#d97706",
+  Unity: "#334155",
+  Terraform: "#7e22ce",
   Generic: "#64748b",
 };